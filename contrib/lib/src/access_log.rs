@@ -0,0 +1,435 @@
+//! Fairing for logging every request and response in an access log.
+//!
+//! [`AccessLog`] records one line per request, including the client's IP
+//! address, the request's method and URI, the response's status and size, how
+//! long the request took to handle, the client's user agent, and the name of
+//! the matched route. Lines are formatted in the [Common Log Format], the
+//! [Combined Log Format], or a custom [template](#templates), and are written
+//! to standard output or appended to a file.
+//!
+//! [Common Log Format]: https://httpd.apache.org/docs/current/logs.html#common
+//! [Combined Log Format]: https://httpd.apache.org/docs/current/logs.html#combined
+//!
+//! # Enabling
+//!
+//! This module is only available when the `access_log` feature is enabled.
+//! Enable it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["access_log"]
+//! ```
+//!
+//! # Usage
+//!
+//! Attach an instance of [`AccessLog`] to your application:
+//!
+//! ```rust
+//! # extern crate rocket;
+//! # extern crate rocket_contrib;
+//! use rocket_contrib::access_log::AccessLog;
+//!
+//! # fn main() -> std::io::Result<()> {
+//! // Log every request to stdout in the Combined Log Format.
+//! rocket::ignite().attach(AccessLog::combined());
+//!
+//! // Append every request to `access.log` in the Common Log Format.
+//! let log = AccessLog::common().to_file("access.log")?;
+//! rocket::ignite().attach(log);
+//! # std::fs::remove_file("access.log")
+//! # }
+//! ```
+//!
+//! # Templates
+//!
+//! A template is a string with placeholders of the form `{field}` that are
+//! replaced with the corresponding value for each request. Literal braces are
+//! written as `{{` and `}}`. When a value is unknown, it is logged as `-`. The
+//! supported fields are:
+//!
+//! | Field          | Value                                                 |
+//! |----------------|-------------------------------------------------------|
+//! | `remote_ip`    | The client's IP address. See [`Request::client_ip()`]. |
+//! | `time`         | The time of the response, as `10/Oct/2000:13:55:36 +0000`. |
+//! | `method`       | The request's method.                                 |
+//! | `uri`          | The request's URI.                                    |
+//! | `protocol`     | The request's HTTP version, as `HTTP/1.1`.            |
+//! | `request_line` | The request line: `{method} {uri} {protocol}`.        |
+//! | `status`       | The response's status code.                           |
+//! | `bytes`        | The size of the response's body, if it is known.      |
+//! | `duration_ms`  | Milliseconds spent handling the request.              |
+//! | `user_agent`   | The value of the `User-Agent` header.                 |
+//! | `referer`      | The value of the `Referer` header.                    |
+//! | `route`        | The name of the matched route.                        |
+//!
+//! ```rust
+//! # extern crate rocket_contrib;
+//! use rocket_contrib::access_log::AccessLog;
+//!
+//! let log = AccessLog::template("{remote_ip} {request_line} {status} {duration_ms}ms")
+//!     .expect("valid template");
+//! ```
+//!
+//! The size and duration are measured when the response is ready to be sent,
+//! before its body is written. The size of a streamed body is not known in
+//! advance and is logged as `-`.
+//!
+//! [`Request::client_ip()`]: rocket::Request::client_ip()
+
+use std::fmt::{self, Write as _};
+use std::fs::{File, OpenOptions};
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Data, Request, Response};
+
+use time::OffsetDateTime;
+
+/// The Common Log Format, as a template.
+const COMMON: &str = "{remote_ip} - - [{time}] \"{request_line}\" {status} {bytes}";
+
+/// The Combined Log Format, as a template.
+const COMBINED: &str = "{remote_ip} - - [{time}] \"{request_line}\" {status} {bytes} \
+    \"{referer}\" \"{user_agent}\"";
+
+/// A [`Fairing`](../../rocket/fairing/trait.Fairing.html) that writes a line
+/// to an access log for every response.
+///
+/// See the [module level documentation](crate::access_log) for the supported
+/// formats and usage.
+pub struct AccessLog {
+    format: Vec<Token>,
+    sink: Sink,
+}
+
+/// An error in an access log template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// The template contains a placeholder for an unknown field.
+    UnknownField(String),
+    /// The template contains an unterminated `{` or an unmatched `}`.
+    Unbalanced,
+}
+
+enum Sink {
+    Stdout,
+    File(Mutex<LineWriter<File>>),
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Literal(String),
+    Field(Field),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    RemoteIp,
+    Time,
+    Method,
+    Uri,
+    Protocol,
+    RequestLine,
+    Status,
+    Bytes,
+    DurationMs,
+    UserAgent,
+    Referer,
+    Route,
+}
+
+/// Value stored in request-local state.
+struct RequestStart(Option<Instant>);
+
+impl Field {
+    fn from_name(name: &str) -> Option<Field> {
+        Some(match name {
+            "remote_ip" => Field::RemoteIp,
+            "time" => Field::Time,
+            "method" => Field::Method,
+            "uri" => Field::Uri,
+            "protocol" => Field::Protocol,
+            "request_line" => Field::RequestLine,
+            "status" => Field::Status,
+            "bytes" => Field::Bytes,
+            "duration_ms" => Field::DurationMs,
+            "user_agent" => Field::UserAgent,
+            "referer" => Field::Referer,
+            "route" => Field::Route,
+            _ => return None,
+        })
+    }
+}
+
+/// Parses `template` into a sequence of literals and fields.
+fn parse(template: &str) -> Result<Vec<Token>, TemplateError> {
+    let mut tokens = vec![];
+    let mut literal = String::new();
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let rest = chars.as_str();
+                let end = rest.find('}').ok_or(TemplateError::Unbalanced)?;
+                let name = &rest[..end];
+                let field = Field::from_name(name)
+                    .ok_or_else(|| TemplateError::UnknownField(name.into()))?;
+
+                if !literal.is_empty() {
+                    tokens.push(Token::Literal(std::mem::take(&mut literal)));
+                }
+
+                tokens.push(Token::Field(field));
+                chars = rest[end + 1..].chars();
+            }
+            '}' => return Err(TemplateError::Unbalanced),
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        tokens.push(Token::Literal(literal));
+    }
+
+    Ok(tokens)
+}
+
+/// Appends `value` to `line`, escaping quotes, backslashes, and control
+/// characters so that client-provided values can't forge log lines.
+fn push_escaped(line: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '"' => line.push_str("\\\""),
+            '\\' => line.push_str("\\\\"),
+            c if c.is_control() => { let _ = write!(line, "\\x{:02x}", c as u32); }
+            c => line.push(c),
+        }
+    }
+}
+
+impl AccessLog {
+    /// Returns an `AccessLog` that writes lines in the [Common Log Format] to
+    /// standard output.
+    ///
+    /// [Common Log Format]: https://httpd.apache.org/docs/current/logs.html#common
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::access_log::AccessLog;
+    ///
+    /// let log = AccessLog::common();
+    /// ```
+    pub fn common() -> Self {
+        AccessLog::template(COMMON).expect("valid common log format template")
+    }
+
+    /// Returns an `AccessLog` that writes lines in the [Combined Log Format]
+    /// to standard output.
+    ///
+    /// [Combined Log Format]: https://httpd.apache.org/docs/current/logs.html#combined
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::access_log::AccessLog;
+    ///
+    /// let log = AccessLog::combined();
+    /// ```
+    pub fn combined() -> Self {
+        AccessLog::template(COMBINED).expect("valid combined log format template")
+    }
+
+    /// Returns an `AccessLog` that writes lines formatted according to
+    /// `template` to standard output. See [templates](crate::access_log#templates)
+    /// for the template syntax.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `template` names an unknown field or contains
+    /// unbalanced braces.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::access_log::{AccessLog, TemplateError};
+    ///
+    /// let log = AccessLog::template("{method} {uri} -> {status} ({route})");
+    /// assert!(log.is_ok());
+    ///
+    /// let log = AccessLog::template("{method} {path}");
+    /// assert_eq!(log.err(), Some(TemplateError::UnknownField("path".into())));
+    /// ```
+    pub fn template(template: &str) -> Result<Self, TemplateError> {
+        Ok(AccessLog { format: parse(template)?, sink: Sink::Stdout })
+    }
+
+    /// Appends lines to the file at `path` instead of writing them to standard
+    /// output. The file is created if it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened for appending.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::access_log::AccessLog;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let log = AccessLog::combined().to_file("access.log")?;
+    /// # std::fs::remove_file("access.log")
+    /// # }
+    /// ```
+    pub fn to_file<P: AsRef<Path>>(self, path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(AccessLog { sink: Sink::File(Mutex::new(LineWriter::new(file))), ..self })
+    }
+
+    async fn format(&self, req: &Request<'_>, res: &mut Response<'_>) -> String {
+        let elapsed = req.local_cache(|| RequestStart(None)).0.map(|start| start.elapsed());
+        let bytes = match res.body_mut() {
+            Some(body) => body.size().await,
+            None => Some(0),
+        };
+
+        let mut line = String::new();
+        for token in &self.format {
+            let field = match token {
+                Token::Literal(literal) => { line.push_str(literal); continue; }
+                Token::Field(field) => *field,
+            };
+
+            let _ = match field {
+                Field::RemoteIp => match req.client_ip() {
+                    Some(ip) => write!(line, "{}", ip),
+                    None => write!(line, "-"),
+                },
+                Field::Time => {
+                    let now = OffsetDateTime::now_utc();
+                    write!(line, "{}", now.format("%d/%b/%Y:%H:%M:%S %z"))
+                }
+                Field::Method => write!(line, "{}", req.method()),
+                Field::Uri => write!(line, "{}", req.uri()),
+                Field::Protocol => write!(line, "{}", req.version()),
+                Field::RequestLine => {
+                    write!(line, "{} {} {}", req.method(), req.uri(), req.version())
+                }
+                Field::Status => write!(line, "{}", res.status().code),
+                Field::Bytes => match bytes {
+                    Some(bytes) if bytes > 0 => write!(line, "{}", bytes),
+                    _ => write!(line, "-"),
+                },
+                Field::DurationMs => match elapsed {
+                    Some(elapsed) => write!(line, "{}", elapsed.as_millis()),
+                    None => write!(line, "-"),
+                },
+                Field::UserAgent | Field::Referer => {
+                    let name = if field == Field::UserAgent { "User-Agent" } else { "Referer" };
+                    match req.headers().get_one(name) {
+                        Some(value) => { push_escaped(&mut line, value); Ok(()) }
+                        None => write!(line, "-"),
+                    }
+                }
+                Field::Route => match req.route().and_then(|r| r.name) {
+                    Some(name) => write!(line, "{}", name),
+                    None => write!(line, "-"),
+                },
+            };
+        }
+
+        line
+    }
+
+    fn write(&self, line: &str) {
+        let result = match &self.sink {
+            Sink::Stdout => writeln!(io::stdout().lock(), "{}", line),
+            Sink::File(file) => {
+                let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+                writeln!(file, "{}", line)
+            }
+        };
+
+        if let Err(e) = result {
+            warn_!("Access Log: failed to write log line: {}", e);
+        }
+    }
+}
+
+impl Default for AccessLog {
+    /// Returns an `AccessLog` that writes lines in the Combined Log Format to
+    /// standard output. This is equivalent to [`AccessLog::combined()`].
+    fn default() -> Self {
+        AccessLog::combined()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for AccessLog {
+    fn info(&self) -> Info {
+        Info {
+            name: "Access Log",
+            kind: Kind::Request | Kind::Response
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data) {
+        request.local_cache(|| RequestStart(Some(Instant::now())));
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let line = self.format(request, response).await;
+        self.write(&line);
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::UnknownField(name) => write!(f, "unknown field `{}`", name),
+            TemplateError::Unbalanced => write!(f, "unbalanced braces"),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError { }
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Field, Token, TemplateError};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("{status} {{ok}}").unwrap(), vec![
+            Token::Field(Field::Status),
+            Token::Literal(" {ok}".into()),
+        ]);
+
+        assert_eq!(parse("[{time}]").unwrap(), vec![
+            Token::Literal("[".into()),
+            Token::Field(Field::Time),
+            Token::Literal("]".into()),
+        ]);
+
+        assert_eq!(parse("{nope}"), Err(TemplateError::UnknownField("nope".into())));
+        assert_eq!(parse("{status"), Err(TemplateError::Unbalanced));
+        assert_eq!(parse("status}"), Err(TemplateError::Unbalanced));
+    }
+}
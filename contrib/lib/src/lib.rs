@@ -19,11 +19,16 @@
 //! * [json*](type@json) - JSON (de)serialization
 //! * [serve*](serve) - Static File Serving
 //! * [msgpack](msgpack) - MessagePack (de)serialization
+//! * [serde_query](serde_query) - Query String Deserialization via Serde
+//! * [negotiated](negotiated) - Content-Negotiated (de)serialization
+//! * [ndjson](ndjson) - Streaming Newline-Delimited JSON
 //! * [handlebars_templates](templates) - Handlebars Templating
 //! * [tera_templates](templates) - Tera Templating
 //! * [uuid](uuid) - UUID (de)serialization
 //! * [${database}_pool](databases) - Database Configuration and Pooling
 //! * [helmet](helmet) - Fairing for Security and Privacy Headers
+//! * [access_log](access_log) - Fairing for Access Logging
+//! * [metrics](metrics) - Prometheus Metrics
 //!
 //! The recommend way to include features from this crate via Rocket in your
 //! project is by adding a `[dependencies.rocket_contrib]` section to your
@@ -46,10 +51,15 @@
 #[cfg(feature="json")] #[macro_use] pub mod json;
 #[cfg(feature="serve")] pub mod serve;
 #[cfg(feature="msgpack")] pub mod msgpack;
+#[cfg(feature="serde_query")] pub mod serde_query;
+#[cfg(feature="negotiated")] pub mod negotiated;
+#[cfg(feature="ndjson")] pub mod ndjson;
 #[cfg(feature="templates")] pub mod templates;
 #[cfg(feature="uuid")] pub mod uuid;
 #[cfg(feature="databases")] pub mod databases;
 #[cfg(feature = "helmet")] pub mod helmet;
+#[cfg(feature = "access_log")] pub mod access_log;
+#[cfg(feature = "metrics")] pub mod metrics;
 // TODO.async: Migrate compression, reenable this, tests, and add to docs.
 //#[cfg(any(feature="brotli_compression", feature="gzip_compression"))] pub mod compression;
 
@@ -0,0 +1,229 @@
+//! Prometheus metrics for requests, labeled by route.
+//!
+//! [`PrometheusMetrics`] is a fairing that counts requests, measures their
+//! latency, and tracks how many are in flight, labeling each by the request's
+//! method and, once handled, the route that handled it. It also mounts a
+//! `GET /metrics` route that serves all collected metrics in the Prometheus
+//! [text format].
+//!
+//! [text format]: https://prometheus.io/docs/instrumenting/exposition_formats/
+//!
+//! # Enabling
+//!
+//! This module is only available when the `metrics` feature is enabled. Enable
+//! it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["metrics"]
+//! ```
+//!
+//! # Metrics
+//!
+//! | Metric                                 | Type      | Labels                             |
+//! |----------------------------------------|-----------|------------------------------------|
+//! | `rocket_http_requests_total`           | counter   | `method`, `route`, `uri`, `status` |
+//! | `rocket_http_request_duration_seconds` | histogram | `method`, `route`, `uri`, `status` |
+//! | `rocket_http_requests_in_flight`       | gauge     | `method`                           |
+//!
+//! The `route` label is the route's [name](rocket::Route::name), or `unnamed`,
+//! and the `uri` label is the route's URI template, such as `/hello/<name>`,
+//! rather than the request's path. Requests that no route handled are labeled
+//! with `unmatched`. Because fairings run before routing, the route handling an
+//! in-flight request isn't yet known, so in-flight requests are labeled by
+//! method alone.
+//!
+//! # Usage
+//!
+//! Attach an instance of [`PrometheusMetrics`] to your application. To export
+//! your own metrics, register their collectors with its
+//! [`registry()`](PrometheusMetrics::registry()):
+//!
+//! ```rust
+//! # extern crate rocket;
+//! # extern crate rocket_contrib;
+//! use rocket_contrib::metrics::PrometheusMetrics;
+//! use rocket_contrib::metrics::prometheus::IntCounter;
+//!
+//! let metrics = PrometheusMetrics::new();
+//! let logins = IntCounter::new("logins_total", "Number of logins").unwrap();
+//! metrics.registry().register(Box::new(logins.clone())).unwrap();
+//!
+//! rocket::ignite()
+//!     .manage(logins)
+//!     .attach(metrics)
+//! # ;
+//! ```
+
+use std::time::Instant;
+
+use prometheus::{Encoder, TextEncoder, Registry, Opts, HistogramOpts};
+use prometheus::{IntCounterVec, HistogramVec, IntGaugeVec, IntGauge};
+
+use rocket::{Rocket, Request, Response, Data, Route};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::handler::{Handler, Outcome};
+use rocket::http::{Method, ContentType, Status};
+use rocket::response::Content;
+
+#[doc(inline)]
+pub use prometheus;
+
+/// A [`Fairing`](../../rocket/fairing/trait.Fairing.html) that collects
+/// request metrics and serves them at `/metrics` in the Prometheus text format.
+///
+/// See the [module level documentation](crate::metrics) for the collected
+/// metrics and usage.
+#[derive(Clone)]
+pub struct PrometheusMetrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    durations: HistogramVec,
+    in_flight: IntGaugeVec,
+}
+
+/// Serves the metrics in a registry.
+#[derive(Clone)]
+struct MetricsHandler(Registry);
+
+/// Value stored in request-local state.
+struct RequestMetrics(Option<(Instant, IntGauge)>);
+
+/// Returns the `route` and `uri` label values for `route`.
+fn route_labels(route: Option<&Route>) -> (&'static str, String) {
+    match route {
+        Some(route) => (route.name.unwrap_or("unnamed"), route.uri.to_string()),
+        None => ("unmatched", "unmatched".into()),
+    }
+}
+
+impl PrometheusMetrics {
+    /// Returns a new `PrometheusMetrics` with a new, empty registry.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::metrics::PrometheusMetrics;
+    ///
+    /// let metrics = PrometheusMetrics::new();
+    /// ```
+    pub fn new() -> Self {
+        PrometheusMetrics::with_registry(Registry::new())
+    }
+
+    /// Returns a new `PrometheusMetrics` that registers its collectors with,
+    /// and serves the metrics in, `registry`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `registry` already contains a collector for one of the
+    /// [metrics](crate::metrics#metrics) collected by `PrometheusMetrics`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::metrics::PrometheusMetrics;
+    /// use rocket_contrib::metrics::prometheus::Registry;
+    ///
+    /// let registry = Registry::new_custom(Some("app".into()), None).unwrap();
+    /// let metrics = PrometheusMetrics::with_registry(registry);
+    /// ```
+    pub fn with_registry(registry: Registry) -> Self {
+        let labels = &["method", "route", "uri", "status"];
+        let opts = Opts::new("rocket_http_requests_total", "Number of requests handled.");
+        let requests = IntCounterVec::new(opts, labels).expect("valid counter");
+
+        let help = "Time taken to handle requests, in seconds.";
+        let opts = HistogramOpts::new("rocket_http_request_duration_seconds", help);
+        let durations = HistogramVec::new(opts, labels).expect("valid histogram");
+
+        let opts = Opts::new("rocket_http_requests_in_flight", "Number of requests in flight.");
+        let in_flight = IntGaugeVec::new(opts, &["method"]).expect("valid gauge");
+
+        registry.register(Box::new(requests.clone())).expect("unique requests metric");
+        registry.register(Box::new(durations.clone())).expect("unique durations metric");
+        registry.register(Box::new(in_flight.clone())).expect("unique in-flight metric");
+
+        PrometheusMetrics { registry, requests, durations, in_flight }
+    }
+
+    /// Returns the registry whose metrics are served. Custom collectors
+    /// registered with it are served alongside Rocket's.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::metrics::PrometheusMetrics;
+    /// use rocket_contrib::metrics::prometheus::IntGauge;
+    ///
+    /// let metrics = PrometheusMetrics::new();
+    /// let sessions = IntGauge::new("sessions", "Number of open sessions").unwrap();
+    /// metrics.registry().register(Box::new(sessions.clone())).unwrap();
+    /// ```
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+}
+
+impl Default for PrometheusMetrics {
+    fn default() -> Self {
+        PrometheusMetrics::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for PrometheusMetrics {
+    fn info(&self) -> Info {
+        Info {
+            name: "Prometheus Metrics",
+            kind: Kind::Attach | Kind::Request | Kind::Response
+        }
+    }
+
+    async fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        let mut route = Route::new(Method::Get, "/", MetricsHandler(self.registry.clone()));
+        route.name = Some("metrics");
+        Ok(rocket.mount("/metrics", vec![route]))
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data) {
+        let gauge = self.in_flight.with_label_values(&[request.method().as_str()]);
+        gauge.inc();
+
+        request.local_cache(|| RequestMetrics(Some((Instant::now(), gauge))));
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let (start, gauge) = match request.local_cache(|| RequestMetrics(None)) {
+            RequestMetrics(Some((start, gauge))) => (start, gauge),
+            RequestMetrics(None) => return,
+        };
+
+        gauge.dec();
+        let (name, uri) = route_labels(request.route());
+        let status = response.status().code.to_string();
+        let labels = &[request.method().as_str(), name, &uri, &status];
+        self.requests.with_label_values(labels).inc();
+        self.durations.with_label_values(labels).observe(start.elapsed().as_secs_f64());
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for MetricsHandler {
+    async fn handle<'r, 's: 'r>(&'s self, req: &'r Request<'_>, _: Data) -> Outcome<'r> {
+        let encoder = TextEncoder::new();
+        let mut buffer = vec![];
+        if let Err(e) = encoder.encode(&self.0.gather(), &mut buffer) {
+            error_!("Failed to encode Prometheus metrics: {}", e);
+            return Outcome::failure(Status::InternalServerError);
+        }
+
+        let content_type = ContentType::with_params("text", "plain", ("version", "0.0.4"));
+        Outcome::from(req, Content(content_type, buffer))
+    }
+}
@@ -0,0 +1,284 @@
+//! Streaming newline-delimited JSON (NDJSON) support.
+//!
+//! See the [`NdJson`](crate::ndjson::NdJson) and
+//! [`NdJsonStream`](crate::ndjson::NdJsonStream) types for further details.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `ndjson` feature is enabled. Enable
+//! it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["ndjson"]
+//! ```
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::convert::Infallible;
+
+use rocket::futures::{ready, stream::{self, Stream}};
+use rocket::tokio::io::{AsyncRead, AsyncReadExt, AsyncBufReadExt, BufReader, ReadBuf};
+
+use rocket::request::Request;
+use rocket::outcome::Outcome::*;
+use rocket::data::{self, Data, DataStream, ByteUnit, FromData};
+use rocket::response::{self, Responder, Response};
+use rocket::http::ContentType;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// A data guard that deserializes newline-delimited JSON records as they
+/// arrive.
+///
+/// `NdJson<T>` is a [`Stream`] of `Result<T, NdJsonError>`: one item for each
+/// non-empty line of the request body, where each line is a JSON encoded `T`.
+/// Records are read and deserialized as the stream is polled, so the body is
+/// never buffered in its entirety. `T` must implement [`DeserializeOwned`]
+/// from [`serde`].
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// # type Record = usize;
+/// use rocket::futures::StreamExt;
+/// use rocket_contrib::ndjson::NdJson;
+///
+/// #[post("/ingest", data = "<records>")]
+/// async fn ingest(mut records: NdJson<Record>) -> String {
+///     let mut count = 0;
+///     while let Some(record) = records.next().await {
+///         match record {
+///             Ok(_) => count += 1,
+///             Err(e) => return format!("stopped at record {}: {:?}", count, e),
+///         }
+///     }
+///
+///     format!("ingested {} records", count)
+/// }
+/// ```
+///
+/// A line that fails to deserialize as a `T` results in an
+/// [`NdJsonError::Parse`] and the stream continues with the next line. A line
+/// that exceeds the line limit or a failure to read the body results in an
+/// [`NdJsonError::LineTooLong`] or [`NdJsonError::Io`], respectively, after
+/// which the stream ends. Blank lines and a trailing `\r` on any line are
+/// ignored.
+///
+/// ## Incoming Data Limits
+///
+/// The total size of the body is unlimited. Instead, every line, excluding its
+/// line ending, is limited to 1MiB by default. The limit can be changed by
+/// setting the `limits.ndjson` configuration parameter. For instance, to limit
+/// lines to 64KiB, you may add the following to your `Rocket.toml`:
+///
+/// ```toml
+/// [global.limits]
+/// ndjson = 65536
+/// ```
+pub struct NdJson<T> {
+    records: Pin<Box<dyn Stream<Item = Result<T, NdJsonError>> + Send>>,
+}
+
+/// An error that occurs while reading a record from an [`NdJson`] stream.
+#[derive(Debug)]
+pub enum NdJsonError {
+    /// An I/O error occurred while reading the incoming request data.
+    Io(io::Error),
+
+    /// A line exceeded the `ndjson` limit.
+    LineTooLong,
+
+    /// A line was received successfully but failed to parse as valid JSON or
+    /// as the requested type.
+    Parse(serde_json::error::Error),
+}
+
+const DEFAULT_LINE_LIMIT: ByteUnit = ByteUnit::Mebibyte(1);
+
+/// The state of an [`NdJson`] stream between records.
+struct Lines {
+    reader: BufReader<DataStream>,
+    limit: u64,
+}
+
+impl Lines {
+    /// Reads the next non-blank line, without its line ending, into `line`.
+    /// Returns `Ok(false)` if there are no more lines.
+    async fn read(&mut self, line: &mut Vec<u8>) -> Result<bool, NdJsonError> {
+        loop {
+            line.clear();
+            let limit = self.limit + 2;
+            let read = (&mut self.reader).take(limit).read_until(b'\n', line).await;
+            if read.map_err(NdJsonError::Io)? == 0 {
+                return Ok(false);
+            }
+
+            // The line ending, `\n` or `\r\n`, doesn't count toward the limit.
+            if line.last() == Some(&b'\n') {
+                line.pop();
+            }
+
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+
+            if line.len() as u64 > self.limit {
+                return Err(NdJsonError::LineTooLong);
+            }
+
+            if !line.iter().all(u8::is_ascii_whitespace) {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<T: DeserializeOwned + Send + 'static> FromData for NdJson<T> {
+    type Error = Infallible;
+
+    async fn from_data(req: &Request<'_>, data: Data) -> data::Outcome<Self, Infallible> {
+        let limit = req.limits().get("ndjson").unwrap_or(DEFAULT_LINE_LIMIT);
+        let reader = BufReader::new(data.open(ByteUnit::max_value()));
+        let lines = Lines { reader, limit: limit.as_u64() };
+        let records = stream::unfold(Some(lines), |lines| async move {
+            let mut lines = match lines {
+                Some(lines) => lines,
+                None => return None,
+            };
+
+            let mut line = vec![];
+            match lines.read(&mut line).await {
+                Ok(true) => {
+                    let record = serde_json::from_slice::<T>(&line).map_err(NdJsonError::Parse);
+                    Some((record, Some(lines)))
+                }
+                Ok(false) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        });
+
+        Success(NdJson { records: Box::pin(records) })
+    }
+}
+
+impl<T> Stream for NdJson<T> {
+    type Item = Result<T, NdJsonError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.records.as_mut().poll_next(cx)
+    }
+}
+
+/// A responder that serializes a [`Stream`] of records as newline-delimited
+/// JSON.
+///
+/// Each item of the wrapped stream, which must implement [`Serialize`] from
+/// [`serde`], is serialized into JSON followed by a `\n` and sent to the
+/// client as soon as the stream yields it. The content type of the response
+/// is set to `application/x-ndjson`.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// use rocket::futures::stream::{self, Stream};
+/// use rocket_contrib::ndjson::NdJsonStream;
+///
+/// #[get("/numbers")]
+/// fn numbers() -> NdJsonStream<impl Stream<Item = usize>> {
+///     NdJsonStream(stream::iter(0..100))
+/// }
+/// ```
+///
+/// Records from an [`NdJson`] data guard can be streamed back as they arrive:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// # type Record = usize;
+/// use rocket::futures::{future, stream::{Stream, StreamExt}};
+/// use rocket_contrib::ndjson::{NdJson, NdJsonStream};
+///
+/// #[post("/echo", data = "<records>")]
+/// fn echo(records: NdJson<Record>) -> NdJsonStream<impl Stream<Item = Record>> {
+///     NdJsonStream(records.filter_map(|record| future::ready(record.ok())))
+/// }
+/// ```
+///
+/// If serialization of a record fails, the response is abandoned, and the
+/// response ends abruptly. An error is printed to the console with an
+/// indication of what went wrong.
+#[derive(Debug)]
+pub struct NdJsonStream<S>(pub S);
+
+impl<S> NdJsonStream<S> {
+    /// Consumes the `NdJsonStream` wrapper and returns the wrapped stream.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rocket_contrib::ndjson::NdJsonStream;
+    /// let stream = NdJsonStream(rocket::futures::stream::empty::<usize>());
+    /// let inner = stream.into_inner();
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+/// Reads the serialized records of a stream.
+struct RecordReader<S> {
+    stream: Pin<Box<S>>,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl<S: Stream> AsyncRead for RecordReader<S> where S::Item: Serialize {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        while this.position == this.buffer.len() {
+            this.buffer.clear();
+            this.position = 0;
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(record) => {
+                    serde_json::to_writer(&mut this.buffer, &record).map_err(|e| {
+                        error_!("NDJSON record failed to serialize: {:?}", e);
+                        io::Error::from(e)
+                    })?;
+
+                    this.buffer.push(b'\n');
+                }
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+
+        let remaining = &this.buffer[this.position..];
+        let n = std::cmp::min(remaining.len(), buf.remaining());
+        buf.put_slice(&remaining[..n]);
+        this.position += n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Streams the serialized records to the client with a Content-Type of
+/// `application/x-ndjson`.
+impl<'r, 'o: 'r, S> Responder<'r, 'o> for NdJsonStream<S>
+    where S: Stream + Send + 'o, S::Item: Serialize
+{
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
+        let reader = RecordReader { stream: Box::pin(self.0), buffer: vec![], position: 0 };
+        Response::build()
+            .header(ContentType::new("application", "x-ndjson"))
+            .streamed_body(reader)
+            .ok()
+    }
+}
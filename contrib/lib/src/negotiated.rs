@@ -0,0 +1,300 @@
+//! Content-negotiated (de)serialization support for JSON, MessagePack, and
+//! forms.
+//!
+//! See the [`Negotiated`](crate::negotiated::Negotiated) type for further
+//! details.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `negotiated` feature is enabled,
+//! which also enables the `json`, `msgpack`, and `serde_query` features.
+//! Enable it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["negotiated"]
+//! ```
+
+use std::io;
+use std::ops::{Deref, DerefMut};
+
+use tokio::io::AsyncReadExt;
+
+use rocket::request::{Request, FormItems};
+use rocket::outcome::Outcome::*;
+use rocket::data::{Data, ByteUnit, Transform::*, Transformed};
+use rocket::data::{FromTransformedData, TransformFuture, FromDataFuture};
+use rocket::response::{self, Responder, Response};
+use rocket::http::{Status, MediaType};
+
+use serde::Serialize;
+use serde::de::Deserialize;
+
+use crate::json::Json;
+use crate::msgpack::MsgPack;
+use crate::serde_query::{self, SerdeQueryError};
+
+/// The `Negotiated` type: implements [`FromTransformedData`] and
+/// [`Responder`], allowing you to consume and respond with JSON or MessagePack
+/// data, and to consume forms, from a single route.
+///
+/// ## Receiving Data
+///
+/// Add a `data` parameter of type `Negotiated<T>` to your route, where `T`
+/// implements [`Deserialize`] from [`serde`]. The body is deserialized
+/// according to the request's `Content-Type`:
+///
+///   * `application/json` and any type with a `+json` suffix, such as
+///     `application/vnd.api+json`, are deserialized as JSON.
+///   * `application/msgpack` is deserialized as MessagePack.
+///   * `application/x-www-form-urlencoded` is deserialized as a form with
+///     nested keys, as described in
+///     [`SerdeQuery`](crate::serde_query::SerdeQuery).
+///
+/// Requests with any other `Content-Type`, or without one, are forwarded.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// # type User = usize;
+/// use rocket_contrib::negotiated::Negotiated;
+///
+/// #[post("/users", data = "<user>")]
+/// fn new_user(user: Negotiated<User>) {
+///     /* ... */
+/// }
+/// ```
+///
+/// A body that fails to deserialize results in a `422 Unprocessable Entity`
+/// if it is well-formed but doesn't match `T` and a `400 Bad Request`
+/// otherwise.
+///
+/// ## Sending Data
+///
+/// Return a `Negotiated<T>`, where `T` implements [`Serialize`] from
+/// [`serde`], to respond with the format the client prefers according to the
+/// request's `Accept` header: MessagePack if `application/msgpack` is
+/// preferred over every JSON type, and JSON otherwise, including when the
+/// request has no `Accept` header or accepts neither format. The response's
+/// `Content-Type` is set accordingly, and a `Vary: Accept` header is added.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// # type User = usize;
+/// use rocket_contrib::negotiated::Negotiated;
+///
+/// #[get("/users/<id>")]
+/// fn user(id: usize) -> Negotiated<User> {
+///     let user_from_id = User::from(id);
+///     /* ... */
+///     Negotiated(user_from_id)
+/// }
+/// ```
+///
+/// ## Incoming Data Limits
+///
+/// Incoming data is limited by the limit for its format: `limits.json` and
+/// `limits.msgpack`, each 1MiB by default, and `limits.forms`, 32KiB by
+/// default.
+#[derive(Debug)]
+pub struct Negotiated<T>(pub T);
+
+impl<T> Negotiated<T> {
+    /// Consumes the `Negotiated` wrapper and returns the wrapped item.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rocket_contrib::negotiated::Negotiated;
+    /// let string = "Hello".to_string();
+    /// let my_value = Negotiated(string);
+    /// assert_eq!(my_value.into_inner(), "Hello".to_string());
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// An error returned by the [`Negotiated`] data guard when incoming data fails
+/// to deserialize.
+#[derive(Debug)]
+pub enum NegotiatedError {
+    /// An I/O error occurred while reading the incoming request data.
+    Io(io::Error),
+
+    /// The data failed to deserialize as JSON.
+    Json(serde_json::error::Error),
+
+    /// The data failed to deserialize as MessagePack.
+    MsgPack(rmp_serde::decode::Error),
+
+    /// The data failed to deserialize as a form.
+    Form(SerdeQueryError),
+}
+
+/// A format that `Negotiated` (de)serializes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Json,
+    MsgPack,
+    Form,
+}
+
+impl Format {
+    /// Returns the format of data of type `media_type`, if it is supported.
+    fn of(media_type: &MediaType) -> Option<Format> {
+        let sub = media_type.sub().as_str();
+        let json_suffix = sub.len() > 5 && sub.get((sub.len() - 5)..)
+            .map_or(false, |suffix| suffix.eq_ignore_ascii_case("+json"));
+
+        if media_type.is_json() || json_suffix {
+            Some(Format::Json)
+        } else if media_type.is_msgpack() {
+            Some(Format::MsgPack)
+        } else if media_type.is_form() {
+            Some(Format::Form)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the format of the request's body, if it is supported.
+    fn of_request(request: &Request<'_>) -> Option<Format> {
+        request.content_type().and_then(|ct| Format::of(ct))
+    }
+
+    /// Returns the format that the request prefers a response in.
+    fn accepted_by(request: &Request<'_>) -> Format {
+        let mut preferred: Option<(f32, Format)> = None;
+        for media_type in request.accept().into_iter().flat_map(|accept| accept.iter()) {
+            let weight = media_type.weight_or(1.0);
+            match Format::of(media_type) {
+                Some(Format::Form) | None => continue,
+                Some(_) if weight <= 0.0 => continue,
+                Some(format) => match preferred {
+                    Some((max, _)) if max >= weight => continue,
+                    _ => preferred = Some((weight, format)),
+                }
+            }
+        }
+
+        preferred.map_or(Format::Json, |(_, format)| format)
+    }
+
+    /// The name and default value of the limit for data in this format.
+    fn limit(self) -> (&'static str, ByteUnit) {
+        match self {
+            Format::Json => ("json", ByteUnit::Mebibyte(1)),
+            Format::MsgPack => ("msgpack", ByteUnit::Mebibyte(1)),
+            Format::Form => ("forms", ByteUnit::Kibibyte(32)),
+        }
+    }
+}
+
+impl<'a, T: Deserialize<'a>> FromTransformedData<'a> for Negotiated<T> {
+    type Error = NegotiatedError;
+    type Owned = Vec<u8>;
+    type Borrowed = [u8];
+
+    fn transform<'r>(r: &'r Request<'_>, d: Data) -> TransformFuture<'r, Self::Owned, Self::Error> {
+        Box::pin(async move {
+            let format = match Format::of_request(r) {
+                Some(format) => format,
+                None => {
+                    warn_!("Data does not have a JSON, MessagePack, or form content type.");
+                    return Borrowed(Forward(d));
+                }
+            };
+
+            let (name, default) = format.limit();
+            let size_limit = r.limits().get(name).unwrap_or(default);
+            let mut buf = Vec::new();
+            match d.open(size_limit).read_to_end(&mut buf).await {
+                Ok(_) => Borrowed(Success(buf)),
+                Err(e) => Borrowed(Failure((Status::BadRequest, NegotiatedError::Io(e)))),
+            }
+        })
+    }
+
+    fn from_data(r: &'a Request<'_>, o: Transformed<'a, Self>) -> FromDataFuture<'a, Self, Self::Error> {
+        Box::pin(async move {
+            let buf = try_outcome!(o.borrowed());
+            let result = match Format::of_request(r) {
+                Some(Format::Json) => serde_json::from_slice(buf).map_err(|e| {
+                    let status = if e.is_data() {
+                        Status::UnprocessableEntity
+                    } else {
+                        Status::BadRequest
+                    };
+
+                    (status, NegotiatedError::Json(e))
+                }),
+                Some(Format::MsgPack) => rmp_serde::from_slice(buf).map_err(|e| {
+                    use rmp_serde::decode::Error::*;
+
+                    let status = match e {
+                        TypeMismatch(_) | OutOfRange | LengthMismatch(_) => {
+                            Status::UnprocessableEntity
+                        }
+                        _ => Status::BadRequest,
+                    };
+
+                    (status, NegotiatedError::MsgPack(e))
+                }),
+                _ => match std::str::from_utf8(buf) {
+                    Ok(string) => serde_query::from_items(FormItems::from(string))
+                        .map_err(|e| (Status::UnprocessableEntity, NegotiatedError::Form(e))),
+                    Err(e) => {
+                        let e = io::Error::new(io::ErrorKind::InvalidData, e);
+                        Err((Status::BadRequest, NegotiatedError::Io(e)))
+                    }
+                }
+            };
+
+            match result {
+                Ok(value) => Success(Negotiated(value)),
+                Err((status, e)) => {
+                    error_!("Couldn't parse request body: {:?}", e);
+                    Failure((status, e))
+                }
+            }
+        })
+    }
+}
+
+/// Serializes the wrapped value into MessagePack if the request prefers it,
+/// and into JSON otherwise. Returns a response with the corresponding
+/// Content-Type, a `Vary: Accept` header, and a fixed-size body with the
+/// serialized value. If serialization fails, an `Err` of
+/// `Status::InternalServerError` is returned.
+impl<'r, T: Serialize> Responder<'r, 'static> for Negotiated<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let response = match Format::accepted_by(req) {
+            Format::MsgPack => MsgPack(self.0).respond_to(req)?,
+            _ => Json(self.0).respond_to(req)?,
+        };
+
+        Response::build_from(response)
+            .raw_header("Vary", "Accept")
+            .ok()
+    }
+}
+
+impl<T> Deref for Negotiated<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Negotiated<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
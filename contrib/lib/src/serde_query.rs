@@ -0,0 +1,530 @@
+//! Query string deserialization via `serde`.
+//!
+//! See the [`SerdeQuery`](crate::serde_query::SerdeQuery) type for further
+//! details.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `serde_query` feature is enabled.
+//! Enable it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["serde_query"]
+//! ```
+
+use std::fmt;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+
+use rocket::http::Status;
+use rocket::request::{Request, Query, FromQuery, FormItem};
+use rocket::response::{self, Responder, status};
+
+use serde::de::{self, Deserialize, Deserializer, DeserializeOwned, DeserializeSeed, Visitor};
+use serde::de::{IntoDeserializer, value::{MapDeserializer, SeqDeserializer}};
+use serde::forward_to_deserialize_any;
+
+/// A query guard that deserializes a `T` from a query string via [`serde`].
+///
+/// `SerdeQuery<T>` implements [`FromQuery`] for every `T` that implements
+/// [`DeserializeOwned`], allowing types that already derive
+/// [`Deserialize`](serde::Deserialize) to be used as trailing query parameters
+/// without also deriving `FromForm`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// use serde::Deserialize;
+/// use rocket_contrib::serde_query::SerdeQuery;
+///
+/// #[derive(Deserialize)]
+/// struct Filter {
+///     name: Option<String>,
+///     #[serde(default)]
+///     tags: Vec<String>,
+///     page: Page,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Page {
+///     number: usize,
+///     size: usize,
+/// }
+///
+/// #[get("/search?<filter..>")]
+/// fn search(filter: SerdeQuery<Filter>) { /* ... */ }
+/// ```
+///
+/// ## Query Structure
+///
+/// Keys are nested with `.` or brackets, as in `page.size=10` or
+/// `page[size]=10`, and deserialize into structures and maps. Values of a
+/// sequence are given either by repeating a key, as in `tags=a&tags=b`, by
+/// empty brackets, as in `tags[]=a&tags[]=b`, or by an index, as in
+/// `tags[0]=a&tags[1]=b`. Elements with an index are ordered by it, followed
+/// by those without one in the order in which they appear. A key that is
+/// repeated where a single value is expected takes its
+/// last value. Keys and values are URL decoded before deserializing.
+///
+/// The `search` route above matches a request to
+/// `/search?name=Bob&tags=a&tags=b&page.number=2&page[size]=10`.
+///
+/// An enum deserializes from a value naming a unit variant, as in `kind=card`,
+/// or from a key naming a variant with data, as in `kind[card][number]=42`.
+///
+/// ## Errors
+///
+/// When the query fails to deserialize, a [`SerdeQueryError`] naming the
+/// offending key is logged and the request fails with a `400 Bad Request`. To
+/// instead respond with a body that names the key, take a `Result` and return
+/// the error, which implements [`Responder`]:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// # use serde::Deserialize;
+/// use rocket_contrib::serde_query::{SerdeQuery, SerdeQueryError};
+///
+/// #[derive(Deserialize)]
+/// struct Page {
+///     number: usize,
+///     size: usize,
+/// }
+///
+/// #[get("/items?<page..>")]
+/// fn items(page: Result<SerdeQuery<Page>, SerdeQueryError>) -> Result<String, SerdeQueryError> {
+///     let page = page?;
+///     Ok(format!("page {} of size {}", page.number, page.size))
+/// }
+/// ```
+///
+/// Here, a request to `/items?number=2&size=many` results in a `400 Bad
+/// Request` with a body of `size: invalid value: "many"`.
+#[derive(Debug)]
+pub struct SerdeQuery<T>(pub T);
+
+impl<T> SerdeQuery<T> {
+    /// Consumes the `SerdeQuery` wrapper and returns the wrapped item.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rocket_contrib::serde_query::SerdeQuery;
+    /// let query = SerdeQuery(10);
+    /// assert_eq!(query.into_inner(), 10);
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'q, T: DeserializeOwned> FromQuery<'q> for SerdeQuery<T> {
+    type Error = SerdeQueryError;
+
+    fn from_query(query: Query<'q>) -> Result<Self, Self::Error> {
+        from_items(query).map(SerdeQuery)
+    }
+
+    fn failure_status(_: &SerdeQueryError) -> Option<Status> {
+        Some(Status::BadRequest)
+    }
+}
+
+/// Deserializes a `T` from `items` as [`SerdeQuery`] deserializes from a query.
+pub(crate) fn from_items<'de, 'f, T, I>(items: I) -> Result<T, SerdeQueryError>
+    where T: Deserialize<'de>, I: IntoIterator<Item = FormItem<'f>>
+{
+    let mut root = Node::new(String::new());
+    for item in items {
+        root.insert(item);
+    }
+
+    T::deserialize(root)
+}
+
+impl<T> Deref for SerdeQuery<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for SerdeQuery<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// An error returned by the [`SerdeQuery`] query guard when the query fails to
+/// deserialize.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerdeQueryError {
+    key: Option<String>,
+    message: String,
+}
+
+impl SerdeQueryError {
+    /// Returns the URL decoded key of the value that failed to deserialize, as
+    /// in `page.size`, if the error concerns a particular key.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Returns a description of the error, as in `invalid value: "many"`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Sets the key of `self` to `key` if it doesn't already have one.
+    fn at(mut self, key: &str) -> Self {
+        if self.key.is_none() && !key.is_empty() {
+            self.key = Some(key.into());
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for SerdeQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.key {
+            Some(ref key) => write!(f, "{}: {}", key, self.message),
+            None => self.message.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SerdeQueryError {  }
+
+impl de::Error for SerdeQueryError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        SerdeQueryError { key: None, message: message.to_string() }
+    }
+}
+
+/// Responds with a `400 Bad Request` and a plain text body naming the
+/// offending key and describing the error.
+impl<'r> Responder<'r, 'static> for SerdeQueryError {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        status::BadRequest(Some(self.to_string())).respond_to(req)
+    }
+}
+
+/// The items in a query that share a key prefix.
+struct Node<'q> {
+    /// The URL decoded key prefix, as in `page` or `tags.0`.
+    path: String,
+    /// The items whose key is exactly `path`.
+    values: Vec<FormItem<'q>>,
+    /// The nodes nested in this one by the next segment of their keys, in the
+    /// order in which they first appear.
+    children: Vec<(String, Node<'q>)>,
+    /// The index in `children` of the child with a given nonempty name.
+    indices: HashMap<String, usize>,
+}
+
+impl<'q> Node<'q> {
+    fn new(path: String) -> Self {
+        Node { path, values: vec![], children: vec![], indices: HashMap::new() }
+    }
+
+    /// Inserts `item`, whose key is relative to `self`, into the tree.
+    fn insert(&mut self, item: FormItem<'q>) {
+        if item.key.is_empty() {
+            self.values.push(item);
+            return;
+        }
+
+        let (segment, rest) = item.split_key();
+        let name = segment.url_decode_lossy();
+        // Each item with an empty segment gets a child of its own.
+        let i = match self.indices.get(&name) {
+            Some(&i) => i,
+            None => {
+                if !name.is_empty() {
+                    self.indices.insert(name.clone(), self.children.len());
+                }
+
+                let path = rest.prefix().url_decode_lossy();
+                let node = Node::new(path.trim_end_matches('.').to_string());
+                self.children.push((name, node));
+                self.children.len() - 1
+            }
+        };
+
+        self.children[i].1.insert(rest);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.children.is_empty()
+    }
+
+    /// The key to report in errors about the value of `self`.
+    fn key(&self) -> String {
+        match self.values.last() {
+            Some(item) => item.full_key().url_decode_lossy(),
+            None => self.path.clone(),
+        }
+    }
+
+    /// Returns an error about the value of `self` with the message `message`.
+    fn error<M: fmt::Display>(&self, message: M) -> SerdeQueryError {
+        <SerdeQueryError as de::Error>::custom(message).at(&self.key())
+    }
+
+    /// Returns the URL decoded last value of `self`.
+    fn value(&self) -> Result<String, SerdeQueryError> {
+        let item = self.values.last().ok_or_else(|| self.error("expected a value"))?;
+        item.value.url_decode()
+            .map_err(|_| self.error(format!("invalid value: {:?}", item.value.as_str())))
+    }
+
+    /// Parses the value of `self` as a `T`.
+    fn parse<T: std::str::FromStr>(&self) -> Result<T, SerdeQueryError> {
+        let value = self.value()?;
+        value.parse().map_err(|_| self.error(format!("invalid value: {:?}", value)))
+    }
+
+    /// Returns the nodes that make up the elements of a sequence: the children
+    /// of `self`, ordered by index, if there are any, and otherwise each of its
+    /// values.
+    fn elements(mut self) -> Vec<Node<'q>> {
+        if !self.children.is_empty() {
+            // Indexed children by index, then the rest as they appear.
+            self.children.sort_by_key(|(name, _)| match name.parse::<usize>() {
+                Ok(index) => (false, index),
+                Err(_) => (true, 0),
+            });
+
+            return self.children.into_iter().map(|(_, node)| node).collect();
+        }
+
+        let path = self.path;
+        self.values.into_iter()
+            .map(|item| {
+                let mut node = Node::new(path.clone());
+                node.values.push(item);
+                node
+            })
+            .collect()
+    }
+}
+
+impl<'de> IntoDeserializer<'de, SerdeQueryError> for Node<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),*) => ($(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeQueryError> {
+            let value = self.parse()?;
+            visitor.$visit(value).map_err(|e| e.at(&self.key()))
+        }
+    )*)
+}
+
+impl<'de> Deserializer<'de> for Node<'_> {
+    type Error = SerdeQueryError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if !self.children.is_empty() {
+            self.deserialize_map(visitor)
+        } else if self.values.len() > 1 {
+            self.deserialize_seq(visitor)
+        } else if !self.values.is_empty() {
+            self.deserialize_string(visitor)
+        } else {
+            visitor.visit_unit()
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let value = match self.value()?.as_str() {
+            "on" | "true" => true,
+            "off" | "false" => false,
+            value => return Err(self.error(format!("invalid value: {:?}", value))),
+        };
+
+        visitor.visit_bool(value).map_err(|e| e.at(&self.key()))
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let value = self.value()?;
+        visitor.visit_string(value).map_err(|e| e.at(&self.key()))
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let value = self.value()?;
+        visitor.visit_byte_buf(value.into_bytes()).map_err(|e| e.at(&self.key()))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let path = self.path.clone();
+        let mut seq = SeqDeserializer::new(self.elements().into_iter());
+        let value = visitor.visit_seq(&mut seq).map_err(|e| e.at(&path))?;
+        seq.end().map_err(|e| e.at(&path))?;
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _: usize,
+        visitor: V
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let path = self.path;
+        let mut map = MapDeserializer::new(self.children.into_iter());
+        let value = visitor.visit_map(&mut map).map_err(|e| e.at(&path))?;
+        map.end().map_err(|e| e.at(&path))?;
+        Ok(value)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, Self::Error> {
+        let key = self.key();
+        let variant = match self.children.len() {
+            0 => Variant { name: self.value()?, node: None },
+            1 => {
+                let (name, node) = self.children.into_iter().next().expect("one child");
+                Variant { name, node: Some(node) }
+            }
+            _ => return Err(self.error("expected a single variant")),
+        };
+
+        visitor.visit_enum(variant).map_err(|e| e.at(&key))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        unit unit_struct identifier
+    }
+}
+
+/// An enum variant named `name` with its data, if any, in `node`.
+struct Variant<'q> {
+    name: String,
+    node: Option<Node<'q>>,
+}
+
+impl<'q> Variant<'q> {
+    fn node(self) -> Result<Node<'q>, SerdeQueryError> {
+        self.node.ok_or_else(|| de::Error::custom("expected a variant with data"))
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for Variant<'_> {
+    type Error = SerdeQueryError;
+    type Variant = Self;
+
+    fn variant_seed<S>(mut self, seed: S) -> Result<(S::Value, Self), Self::Error>
+        where S: DeserializeSeed<'de>
+    {
+        let name = std::mem::take(&mut self.name);
+        let name: de::value::StringDeserializer<SerdeQueryError> = name.into_deserializer();
+        Ok((seed.deserialize(name)?, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Variant<'_> {
+    type Error = SerdeQueryError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+        where S: DeserializeSeed<'de>
+    {
+        seed.deserialize(self.node()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.node()?.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, Self::Error> {
+        self.node()?.deserialize_map(visitor)
+    }
+}
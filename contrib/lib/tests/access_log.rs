@@ -0,0 +1,62 @@
+#[macro_use]
+#[cfg(feature = "access_log")]
+extern crate rocket;
+
+#[cfg(feature = "access_log")]
+mod access_log_tests {
+    use std::path::PathBuf;
+
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    use rocket_contrib::access_log::AccessLog;
+
+    #[get("/hello")] fn hello() -> &'static str { "Hello, world!" }
+
+    fn log_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rocket-access-log-{}.log", name));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn dispatch(log: AccessLog, uri: &str) {
+        let rocket = rocket::ignite().mount("/", routes![hello]).attach(log);
+        let client = Client::tracked(rocket).unwrap();
+        client.get(uri)
+            .header(Header::new("User-Agent", "test \"agent\""))
+            .header(Header::new("Referer", "http://example.com/"))
+            .dispatch();
+    }
+
+    #[test]
+    fn common_log_format() {
+        let path = log_path("common");
+        dispatch(AccessLog::common().to_file(&path).unwrap(), "/hello");
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        assert!(log.starts_with("- - - ["), "{}", log);
+        assert!(log.ends_with("] \"GET /hello HTTP/1.1\" 200 13\n"), "{}", log);
+    }
+
+    #[test]
+    fn combined_log_format() {
+        let path = log_path("combined");
+        dispatch(AccessLog::combined().to_file(&path).unwrap(), "/missing");
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let expected = "\"GET /missing HTTP/1.1\" 404 ";
+        assert!(log.contains(expected), "{}", log);
+        assert!(log.ends_with(" \"http://example.com/\" \"test \\\"agent\\\"\"\n"), "{}", log);
+    }
+
+    #[test]
+    fn template_log_format() {
+        let path = log_path("template");
+        let log = AccessLog::template("{method} {uri} {status} {bytes} {route} {{x}}").unwrap();
+        dispatch(log.to_file(&path).unwrap(), "/hello");
+        dispatch(AccessLog::template("{route}").unwrap().to_file(&path).unwrap(), "/missing");
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(log, "GET /hello 200 13 hello {x}\n-\n");
+    }
+}
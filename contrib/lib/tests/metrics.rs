@@ -0,0 +1,56 @@
+#[macro_use]
+#[cfg(feature = "metrics")]
+extern crate rocket;
+
+#[cfg(feature = "metrics")]
+mod metrics_tests {
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+
+    use rocket_contrib::metrics::PrometheusMetrics;
+    use rocket_contrib::metrics::prometheus::IntCounter;
+
+    #[get("/hello/<name>")] fn hello(name: String) -> String { name }
+
+    fn client(metrics: PrometheusMetrics) -> Client {
+        let rocket = rocket::ignite().mount("/", routes![hello]).attach(metrics);
+        Client::tracked(rocket).unwrap()
+    }
+
+    #[test]
+    fn metrics_are_labeled_by_route() {
+        let client = client(PrometheusMetrics::new());
+        client.get("/hello/bob").dispatch();
+        client.get("/hello/alice").dispatch();
+        client.get("/missing").dispatch();
+
+        let response = client.get("/metrics").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::Plain));
+
+        let body = response.into_string().unwrap();
+        let hello = r#"method="GET",route="hello",status="200",uri="/hello/<name>""#;
+        assert!(body.contains(&format!("rocket_http_requests_total{{{}}} 2", hello)), "{}", body);
+        assert!(body.contains(&format!("rocket_http_request_duration_seconds_count{{{}}} 2", hello)));
+
+        let unmatched = r#"method="GET",route="unmatched",status="404",uri="unmatched""#;
+        assert!(body.contains(&format!("rocket_http_requests_total{{{}}} 1", unmatched)));
+
+        // The only request in flight is the one for `/metrics` itself.
+        let in_flight = r#"rocket_http_requests_in_flight{method="GET"} 1"#;
+        assert!(body.contains(in_flight), "{}", body);
+        assert!(!body.contains("bob"));
+    }
+
+    #[test]
+    fn custom_collectors_are_served() {
+        let metrics = PrometheusMetrics::new();
+        let counter = IntCounter::new("custom_total", "A custom counter.").unwrap();
+        metrics.registry().register(Box::new(counter.clone())).unwrap();
+        counter.inc_by(3);
+
+        let client = client(metrics);
+        let body = client.get("/metrics").dispatch().into_string().unwrap();
+        assert!(body.contains("custom_total 3"), "{}", body);
+    }
+}
@@ -0,0 +1,95 @@
+#[macro_use]
+#[cfg(feature = "ndjson")]
+extern crate rocket;
+
+#[cfg(feature = "ndjson")]
+mod ndjson_tests {
+    use rocket::futures::{future, stream::{self, Stream, StreamExt}};
+    use rocket::http::{Status, ContentType};
+    use rocket::local::blocking::Client;
+    use rocket::data::{Limits, ToByteUnit};
+    use rocket_contrib::ndjson::{NdJson, NdJsonError, NdJsonStream};
+    use serde::{Serialize, Deserialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[post("/summary", data = "<points>")]
+    async fn summary(points: NdJson<Point>) -> String {
+        let summaries: Vec<String> = points.map(|point| match point {
+            Ok(point) => format!("{}:{}", point.x, point.y),
+            Err(NdJsonError::Parse(_)) => "parse".into(),
+            Err(NdJsonError::LineTooLong) => "too long".into(),
+            Err(NdJsonError::Io(_)) => "io".into(),
+        }).collect().await;
+
+        summaries.join(",")
+    }
+
+    #[post("/echo", data = "<points>")]
+    fn echo(points: NdJson<Point>) -> NdJsonStream<impl Stream<Item = Point>> {
+        NdJsonStream(points.filter_map(|point| future::ready(point.ok())))
+    }
+
+    #[get("/points")]
+    fn points() -> NdJsonStream<impl Stream<Item = Point>> {
+        NdJsonStream(stream::iter((0..3).map(|i| Point { x: i, y: -i })))
+    }
+
+    fn client(line_limit: u64) -> Client {
+        let limits = Limits::default().limit("ndjson", line_limit.bytes());
+        let config = rocket::Config::figment().merge(("limits", limits));
+        let rocket = rocket::custom(config).mount("/", routes![summary, echo, points]);
+        Client::tracked(rocket).unwrap()
+    }
+
+    #[test]
+    fn reads_records() {
+        let client = client(1024);
+        let body = "{\"x\":1,\"y\":2}\n\n{\"x\":3,\"y\":4}\r\n  \n{\"x\":\"five\",\"y\":6}\n{\"x\":7,\"y\":8}";
+        let response = client.post("/summary").body(body).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "1:2,3:4,parse,7:8");
+
+        let response = client.post("/summary").body("").dispatch();
+        assert_eq!(response.into_string().unwrap(), "");
+    }
+
+    #[test]
+    fn limits_lines() {
+        let client = client(13);
+        let body = "{\"x\":1,\"y\":2}\n{\"x\":10,\"y\":20}\n{\"x\":3,\"y\":4}\n";
+        let response = client.post("/summary").body(body).dispatch();
+        assert_eq!(response.into_string().unwrap(), "1:2,too long");
+
+        let response = client.post("/summary").body("{\"x\":1,\"y\":2}").dispatch();
+        assert_eq!(response.into_string().unwrap(), "1:2");
+
+        let response = client.post("/summary").body("{\"x\":10,\"y\":2}").dispatch();
+        assert_eq!(response.into_string().unwrap(), "too long");
+
+        // A `\r\n` line ending doesn't count toward the limit.
+        let body = "{\"x\":1,\"y\":2}\r\n{\"x\":3,\"y\":4}\r\n";
+        let response = client.post("/summary").body(body).dispatch();
+        assert_eq!(response.into_string().unwrap(), "1:2,3:4");
+
+        let response = client.post("/summary").body("{\"x\":10,\"y\":2}\r\n").dispatch();
+        assert_eq!(response.into_string().unwrap(), "too long");
+    }
+
+    #[test]
+    fn writes_records() {
+        let client = client(1024);
+        let response = client.get("/points").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::new("application", "x-ndjson")));
+        assert_eq!(response.into_string().unwrap(),
+            "{\"x\":0,\"y\":0}\n{\"x\":1,\"y\":-1}\n{\"x\":2,\"y\":-2}\n");
+
+        let body = "{\"x\":1,\"y\":2}\n{\"x\":true}\n{\"x\":3,\"y\":4}";
+        let response = client.post("/echo").body(body).dispatch();
+        assert_eq!(response.into_string().unwrap(), "{\"x\":1,\"y\":2}\n{\"x\":3,\"y\":4}\n");
+    }
+}
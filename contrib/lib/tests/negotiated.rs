@@ -0,0 +1,110 @@
+#[macro_use]
+#[cfg(feature = "negotiated")]
+extern crate rocket;
+
+#[cfg(feature = "negotiated")]
+mod negotiated_tests {
+    use rocket::http::{Status, ContentType, Header};
+    use rocket::local::blocking::Client;
+    use rocket_contrib::negotiated::Negotiated;
+    use serde::{Serialize, Deserialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct User {
+        name: String,
+        age: u8,
+        address: Address,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Address {
+        city: String,
+    }
+
+    #[post("/", data = "<user>")]
+    fn echo(user: Negotiated<User>) -> Negotiated<User> {
+        user
+    }
+
+    fn client() -> Client {
+        Client::tracked(rocket::ignite().mount("/", routes![echo])).unwrap()
+    }
+
+    fn bob() -> User {
+        User { name: "Bob".into(), age: 30, address: Address { city: "Paris".into() } }
+    }
+
+    const BOB_JSON: &str = r#"{"name":"Bob","age":30,"address":{"city":"Paris"}}"#;
+
+    #[test]
+    fn deserializes_by_content_type() {
+        let client = client();
+        let response = client.post("/").header(ContentType::JSON).body(BOB_JSON).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), BOB_JSON);
+
+        let api_json = ContentType::new("application", "vnd.api+json");
+        let response = client.post("/").header(api_json).body(BOB_JSON).dispatch();
+        assert_eq!(response.into_string().unwrap(), BOB_JSON);
+
+        let msgpack = rmp_serde::to_vec(&bob()).unwrap();
+        let response = client.post("/").header(ContentType::MsgPack).body(msgpack).dispatch();
+        assert_eq!(response.into_string().unwrap(), BOB_JSON);
+
+        let form = "name=Bob&age=30&address.city=Paris";
+        let response = client.post("/").header(ContentType::Form).body(form).dispatch();
+        assert_eq!(response.into_string().unwrap(), BOB_JSON);
+    }
+
+    #[test]
+    fn unsupported_content_types_forward() {
+        let client = client();
+        let response = client.post("/").header(ContentType::Plain).body(BOB_JSON).dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.post("/").body(BOB_JSON).dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn bad_data_fails() {
+        let client = client();
+        let response = client.post("/").header(ContentType::JSON).body("{").dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let body = r#"{"name":"Bob","age":"old","address":{"city":"Paris"}}"#;
+        let response = client.post("/").header(ContentType::JSON).body(body).dispatch();
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+
+        let form = "name=Bob&age=300&address.city=Paris";
+        let response = client.post("/").header(ContentType::Form).body(form).dispatch();
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+    }
+
+    #[test]
+    fn serializes_by_accept() {
+        let client = client();
+        let post = |accept: Option<&'static str>| {
+            let mut request = client.post("/").header(ContentType::JSON).body(BOB_JSON);
+            if let Some(accept) = accept {
+                request.add_header(Header::new("Accept", accept));
+            }
+
+            let response = request.dispatch();
+            assert_eq!(response.headers().get_one("Vary"), Some("Accept"));
+            (response.content_type(), response.into_bytes().unwrap())
+        };
+
+        let (json, msgpack) = (BOB_JSON.as_bytes().to_vec(), rmp_serde::to_vec(&bob()).unwrap());
+        assert_eq!(post(None), (Some(ContentType::JSON), json.clone()));
+        assert_eq!(post(Some("text/html")), (Some(ContentType::JSON), json.clone()));
+        assert_eq!(post(Some("application/msgpack")), (Some(ContentType::MsgPack), msgpack.clone()));
+        assert_eq!(post(Some("application/json, application/msgpack")), (Some(ContentType::JSON), json.clone()));
+
+        let accept = "application/json; q=0.5, application/msgpack; q=0.8";
+        assert_eq!(post(Some(accept)), (Some(ContentType::MsgPack), msgpack));
+
+        let accept = "application/msgpack; q=0.2, application/ld+json";
+        assert_eq!(post(Some(accept)), (Some(ContentType::JSON), json));
+    }
+}
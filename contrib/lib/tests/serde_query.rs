@@ -0,0 +1,159 @@
+#[macro_use]
+#[cfg(feature = "serde_query")]
+extern crate rocket;
+
+#[cfg(feature = "serde_query")]
+mod serde_query_tests {
+    use std::collections::HashMap;
+
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+    use rocket_contrib::serde_query::{SerdeQuery, SerdeQueryError};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Filter {
+        name: Option<String>,
+        #[serde(default)]
+        tags: Vec<String>,
+        page: Page,
+        #[serde(default)]
+        scores: HashMap<String, u8>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Page {
+        number: usize,
+        size: usize,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Kind {
+        Cash,
+        Card { number: u32 },
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Payment {
+        kind: Kind,
+        paid: bool,
+    }
+
+    #[get("/search?<filter..>")]
+    fn search(filter: SerdeQuery<Filter>) -> String {
+        let mut scores: Vec<_> = filter.scores.iter().collect();
+        scores.sort();
+        format!("{:?} {:?} {}x{} {:?}", filter.name, filter.tags,
+            filter.page.number, filter.page.size, scores)
+    }
+
+    #[get("/pay?<payment..>")]
+    fn pay(payment: SerdeQuery<Payment>) -> String {
+        format!("{:?} {}", payment.kind, payment.paid)
+    }
+
+    #[get("/items?<page..>")]
+    fn items(page: Result<SerdeQuery<Page>, SerdeQueryError>) -> Result<String, SerdeQueryError> {
+        let page = page?;
+        Ok(format!("page {} of size {}", page.number, page.size))
+    }
+
+    #[get("/key?<filter..>")]
+    fn key(filter: Result<SerdeQuery<Filter>, SerdeQueryError>) -> String {
+        format!("{:?}", filter.err().and_then(|e| e.key().map(String::from)))
+    }
+
+    fn client() -> Client {
+        let routes = routes![search, pay, items, key];
+        Client::tracked(rocket::ignite().mount("/", routes)).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str) -> (Status, String) {
+        let response = client.get(uri.to_string()).dispatch();
+        (response.status(), response.into_string().unwrap_or_default())
+    }
+
+    #[test]
+    fn nested_and_repeated_keys() {
+        let client = client();
+        let (status, body) = get(&client, "/search?name=Bob+Smith&page.number=2&page[size]=10");
+        assert_eq!(status, Status::Ok);
+        assert_eq!(body, r#"Some("Bob Smith") [] 2x10 []"#);
+
+        let (_, body) = get(&client, "/search?tags=a&page.number=1&tags=b%20c&page.size=5");
+        assert_eq!(body, r#"None ["a", "b c"] 1x5 []"#);
+
+        let (_, body) = get(&client, "/search?tags[]=a&tags[]=b&page[number]=1&page[size]=5");
+        assert_eq!(body, r#"None ["a", "b"] 1x5 []"#);
+
+        let (_, body) = get(&client, "/search?tags[0]=x&tags[1]=y&tags[0]=z&page.number=1&page.size=5");
+        assert_eq!(body, r#"None ["z", "y"] 1x5 []"#);
+
+        let (_, body) = get(&client, "/search?tags[10]=c&tags[1]=b&tags[0]=a&page.number=1&page.size=5");
+        assert_eq!(body, r#"None ["a", "b", "c"] 1x5 []"#);
+
+        let (_, body) = get(&client, "/search?page.number=1&page.size=5&page.size=6&scores[bob]=3&scores.al=7");
+        assert_eq!(body, r#"None [] 1x6 [("al", 7), ("bob", 3)]"#);
+    }
+
+    #[test]
+    fn enums() {
+        let client = client();
+        let (_, body) = get(&client, "/pay?kind=cash&paid=on");
+        assert_eq!(body, "Cash true");
+
+        let (_, body) = get(&client, "/pay?kind[card][number]=42&paid=false");
+        assert_eq!(body, "Card { number: 42 } false");
+
+        let (status, _) = get(&client, "/pay?kind=cheque&paid=true");
+        assert_eq!(status, Status::BadRequest);
+    }
+
+    #[test]
+    fn failures_are_bad_requests() {
+        let client = client();
+        let (status, _) = get(&client, "/search?page.number=1");
+        assert_eq!(status, Status::BadRequest);
+
+        let (status, _) = get(&client, "/search?page.number=one&page.size=5");
+        assert_eq!(status, Status::BadRequest);
+    }
+
+    #[test]
+    fn errors_name_the_key() {
+        let client = client();
+        let (status, body) = get(&client, "/items?number=2&size=many");
+        assert_eq!(status, Status::BadRequest);
+        assert_eq!(body, r#"size: invalid value: "many""#);
+
+        let (status, body) = get(&client, "/items?number=2");
+        assert_eq!(status, Status::BadRequest);
+        assert_eq!(body, "missing field `size`");
+
+        let (status, body) = get(&client, "/items?number=2&size=3");
+        assert_eq!(status, Status::Ok);
+        assert_eq!(body, "page 2 of size 3");
+    }
+
+    #[test]
+    fn error_keys() {
+        let client = client();
+        let (_, body) = get(&client, "/key?page[number]=x&page.size=1");
+        assert_eq!(body, r#"Some("page[number]")"#);
+
+        let (_, body) = get(&client, "/key?page.number=1&page.size=1&scores.al=300");
+        assert_eq!(body, r#"Some("scores.al")"#);
+
+        let (_, body) = get(&client, "/key?page.number=1");
+        assert_eq!(body, r#"Some("page")"#);
+
+        let (_, body) = get(&client, "/key?page.number=1&page.size=1");
+        assert_eq!(body, "None");
+
+        let error: SerdeQueryError = serde::de::Error::custom("oops");
+        assert_eq!(error.key(), None);
+        assert_eq!(error.message(), "oops");
+        assert_eq!(error.to_string(), "oops");
+    }
+}
@@ -24,6 +24,8 @@ struct RouteAttribute {
     data: Option<SpanWrapped<DataSegment>>,
     format: Option<MediaType>,
     rank: Option<isize>,
+    timeout: Option<u32>,
+    concurrency: Option<SpanWrapped<usize>>,
 }
 
 /// The raw, parsed `#[method]` (e.g, `get`, `put`, `post`, etc.) attribute.
@@ -34,6 +36,8 @@ struct MethodRouteAttribute {
     data: Option<SpanWrapped<DataSegment>>,
     format: Option<MediaType>,
     rank: Option<isize>,
+    timeout: Option<u32>,
+    concurrency: Option<SpanWrapped<usize>>,
 }
 
 /// This structure represents the parsed `route` attribute and associated items.
@@ -84,6 +88,14 @@ fn parse_route(attr: RouteAttribute, function: syn::ItemFn) -> Result<Route> {
     attr.path.query.as_ref().map(|q| dup_check(&mut segments, q.iter(), &mut diags));
     dup_check(&mut segments, attr.data.as_ref().map(|s| &s.value.0).into_iter(), &mut diags);
 
+    // A limit of `0` would never admit a request.
+    if let Some(ref concurrency) = attr.concurrency {
+        if concurrency.value == 0 {
+            diags.push(concurrency.span.error("`concurrency` must be nonzero")
+                .help("omit `concurrency` to leave the route unlimited"));
+        }
+    }
+
     // Check the validity of function arguments.
     let mut inputs = vec![];
     let mut fn_segments: IndexSet<Segment> = IndexSet::new();
@@ -173,10 +185,12 @@ fn param_expr(seg: &Segment, ident: &syn::Ident, ty: &syn::Type) -> TokenStream
 }
 
 fn data_expr(ident: &syn::Ident, ty: &syn::Type) -> TokenStream {
-    define_vars_and_mods!(req, data, FromTransformedData, Outcome, Transform);
+    define_vars_and_mods!(req, data, FromTransformedData, Outcome, Transform, Span);
     let span = ident.span().join(ty.span()).unwrap_or_else(|| ty.span());
     quote_spanned! { span =>
-        let __transform = <#ty as #FromTransformedData>::transform(#req, #data).await;
+        let __span = #Span::data_guard(::std::stringify!(#ty));
+        let __transform = <#ty as #FromTransformedData>::transform(#req, #data);
+        let __transform = __span.instrument(__transform).await;
 
         #[allow(unreachable_patterns, unreachable_code)]
         let __outcome = match __transform {
@@ -192,8 +206,9 @@ fn data_expr(ident: &syn::Ident, ty: &syn::Type) -> TokenStream {
             #Transform::Owned(__o) => #Transform::Owned(__o),
         };
 
+        let __from_data = <#ty as #FromTransformedData>::from_data(#req, __outcome);
         #[allow(non_snake_case, unreachable_patterns, unreachable_code)]
-        let #ident: #ty = match <#ty as #FromTransformedData>::from_data(#req, __outcome).await {
+        let #ident: #ty = match __span.instrument(__from_data).await {
             #Outcome::Success(__d) => __d,
             #Outcome::Forward(__d) => return #Outcome::Forward(__d),
             #Outcome::Failure((__c, _)) => return #Outcome::Failure(__c),
@@ -272,7 +287,10 @@ fn query_exprs(route: &Route) -> Option<TokenStream> {
                     #_Ok(__v) => __v,
                     #_Err(__e) => {
                         #log::warn_(&format!("Failed to parse '{}': {:?}", #name, __e));
-                        return #Outcome::Forward(#data);
+                        return match <#ty as #request::FromQuery>::failure_status(&__e) {
+                            #_Some(__status) => #Outcome::Failure(__status),
+                            #_None => #Outcome::Forward(#data),
+                        };
                     }
                 };
             },
@@ -307,11 +325,13 @@ fn query_exprs(route: &Route) -> Option<TokenStream> {
 }
 
 fn request_guard_expr(ident: &syn::Ident, ty: &syn::Type) -> TokenStream {
-    define_vars_and_mods!(req, data, request, Outcome);
+    define_vars_and_mods!(req, data, request, Outcome, Span);
     let span = ident.span().join(ty.span()).unwrap_or_else(|| ty.span());
     quote_spanned! { span =>
+        let __span = #Span::request_guard(::std::stringify!(#ty));
+        let __guard = <#ty as #request::FromRequest>::from_request(#req);
         #[allow(non_snake_case, unreachable_patterns, unreachable_code)]
-        let #ident: #ty = match <#ty as #request::FromRequest>::from_request(#req).await {
+        let #ident: #ty = match __span.instrument(__guard).await {
             #Outcome::Success(__v) => __v,
             #Outcome::Forward(_) => return #Outcome::Forward(#data),
             #Outcome::Failure((__c, _)) => return #Outcome::Failure(__c),
@@ -419,6 +439,8 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     let path = route.attribute.path.origin.0.to_string();
     let rank = Optional(route.attribute.rank);
     let format = Optional(route.attribute.format);
+    let timeout = Optional(route.attribute.timeout);
+    let concurrency = Optional(route.attribute.concurrency.map(|c| c.value));
 
     Ok(quote! {
         #user_handler_fn
@@ -451,6 +473,8 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
                     handler: monomorphized_function,
                     format: #format,
                     rank: #rank,
+                    timeout: #timeout,
+                    concurrency: #concurrency,
                 }
             }
         }
@@ -514,6 +538,8 @@ fn incomplete_route(
         data: method_attribute.data,
         format: method_attribute.format,
         rank: method_attribute.rank,
+        timeout: method_attribute.timeout,
+        concurrency: method_attribute.concurrency,
     };
 
     codegen_route(parse_route(attribute, function)?)
@@ -1,11 +1,16 @@
-use devise::{*, ext::{TypeExt, Split3, SpanDiagnosticExt}};
+use std::collections::HashMap;
 
-use crate::proc_macro2::{Span, TokenStream};
+use devise::{*, ext::{TypeExt, SpanDiagnosticExt}};
+
+use crate::proc_macro2::{Span, TokenStream, TokenTree, Group, Delimiter};
+use crate::syn::{self, Ident, Token};
+use crate::syn::parse::{self, Parse, ParseStream};
+use crate::syn::punctuated::Punctuated;
 use crate::syn_ext::NameSource;
 
 #[derive(FromMeta)]
 pub struct Form {
-    pub field: FormField,
+    pub field: Option<FormField>,
 }
 
 pub struct FormField {
@@ -35,19 +40,234 @@ impl FromMeta for FormField {
     }
 }
 
-fn validate_struct(_: &DeriveGenerator, data: Struct<'_>) -> Result<()> {
-    if data.fields().is_empty() {
-        return Err(data.fields.span().error("at least one field is required"));
+/// An argument to a `#[form]` attribute: either `default = EXPR` or the tokens
+/// of any other argument.
+enum FormArg {
+    Default(Ident, syn::Expr),
+    Other(TokenStream),
+}
+
+impl Parse for FormArg {
+    fn parse(input: ParseStream<'_>) -> parse::Result<Self> {
+        let is_default = input.fork().parse::<Ident>().map_or(false, |id| id == "default");
+        if is_default && input.peek2(Token![=]) {
+            let key = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            return Ok(FormArg::Default(key, input.parse()?));
+        }
+
+        let mut tokens = TokenStream::new();
+        while !input.is_empty() && !input.peek(Token![,]) {
+            tokens.extend(Some(input.parse::<TokenTree>()?));
+        }
+
+        Ok(FormArg::Other(tokens))
     }
+}
 
-    let mut names = ::std::collections::HashMap::new();
-    for field in data.fields().iter() {
-        let id = field.ident.as_ref().expect("named field");
-        let field = match Form::from_attrs("form", &field.attrs) {
+/// Removes the `default = EXPR` argument from the `#[form]` attribute `attr`,
+/// storing it in `default`. Devise parses attributes as `syn::Meta`, which
+/// can't hold arbitrary expressions, so the argument is parsed here instead.
+fn strip_default(attr: &syn::Attribute, default: &mut Option<syn::Expr>) -> Result<syn::Attribute> {
+    let group = match attr.tokens.clone().into_iter().next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group,
+        _ => return Ok(attr.clone())
+    };
+
+    let parser = Punctuated::<FormArg, Token![,]>::parse_terminated;
+    let args = parse::Parser::parse2(parser, group.stream()).map_err(Diagnostic::from)?;
+    let mut rest = Punctuated::<TokenStream, Token![,]>::new();
+    for arg in args {
+        match arg {
+            FormArg::Default(key, _) if default.is_some() => {
+                return Err(key.span().error("duplicate attribute parameter: default"));
+            }
+            FormArg::Default(_, expr) => *default = Some(expr),
+            FormArg::Other(tokens) => rest.push(tokens),
+        }
+    }
+
+    let mut new_group = Group::new(Delimiter::Parenthesis, quote!(#rest));
+    new_group.set_span(group.span());
+
+    let mut attr = attr.clone();
+    attr.tokens = quote!(#new_group);
+    Ok(attr)
+}
+
+/// The `#[form]` attribute of a named field, with defaults filled in.
+pub struct FormAttr {
+    /// The name of the field in the form.
+    pub field: FormField,
+    /// The value of the field when it is missing, if any.
+    pub default: Option<syn::Expr>,
+}
+
+impl FormAttr {
+    /// Parses the `#[form]` attribute in `attrs` of the field named `ident`.
+    pub fn from_attrs(ident: &Ident, attrs: &[syn::Attribute]) -> Result<Self> {
+        let mut default = None;
+        let attrs = attrs.iter()
+            .map(|attr| if attr.path.is_ident("form") {
+                strip_default(attr, &mut default)
+            } else {
+                Ok(attr.clone())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let field = match Form::from_attrs("form", &attrs) {
             Some(result) => result?.field,
-            None => FormField { span: Spanned::span(&id), name: id.clone().into() }
+            None => None
         };
 
+        let field = field.unwrap_or_else(|| {
+            FormField { span: ident.span(), name: ident.clone().into() }
+        });
+
+        Ok(FormAttr { field, default })
+    }
+}
+
+/// A `validate = CALL` argument to a `#[field]` attribute.
+struct FieldArg {
+    key: Ident,
+    value: syn::Expr,
+}
+
+impl Parse for FieldArg {
+    fn parse(input: ParseStream<'_>) -> parse::Result<Self> {
+        let key = input.parse::<Ident>()?;
+        input.parse::<Token![=]>()?;
+        Ok(FieldArg { key, value: input.parse()? })
+    }
+}
+
+/// A validator from a `#[field(validate = ...)]` attribute.
+struct Validator {
+    /// The validator call with `__v` as its first argument.
+    call: TokenStream,
+    /// Whether the call refers to `self`, rewritten to `__form`.
+    uses_self: bool,
+    /// The span of the call in the attribute.
+    span: Span,
+}
+
+/// Returns whether `func` is a path into Rocket's validators, `validate::f`.
+fn is_builtin(func: &syn::Expr) -> bool {
+    match func {
+        syn::Expr::Path(e) => e.qself.is_none()
+            && e.path.leading_colon.is_none()
+            && e.path.segments.len() > 1
+            && e.path.segments[0].ident == "validate",
+        _ => false
+    }
+}
+
+/// Replaces every `self` followed by a `.` in `tokens` with `__form`,
+/// returning the new tokens and whether any replacement occurred.
+fn replace_self(tokens: TokenStream) -> (TokenStream, bool) {
+    let is_dot = |tree: Option<&TokenTree>| match tree {
+        Some(TokenTree::Punct(p)) => p.as_char() == '.',
+        _ => false
+    };
+
+    let (mut replaced, mut trees) = (false, tokens.into_iter().peekable());
+    let mut tokens = TokenStream::new();
+    while let Some(tree) = trees.next() {
+        let tree = match tree {
+            TokenTree::Ident(ident) if ident == "self" && is_dot(trees.peek()) => {
+                replaced = true;
+                TokenTree::Ident(Ident::new("__form", ident.span()))
+            }
+            TokenTree::Group(group) => {
+                let (stream, inner) = replace_self(group.stream());
+                replaced |= inner;
+                let mut new = Group::new(group.delimiter(), stream);
+                new.set_span(group.span());
+                TokenTree::Group(new)
+            }
+            tree => tree
+        };
+
+        tokens.extend(Some(tree));
+    }
+
+    (tokens, replaced)
+}
+
+/// Returns the validators in the `#[field]` attributes in `attrs`.
+fn field_validators(attrs: &[syn::Attribute]) -> Result<Vec<Validator>> {
+    let mut validators = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("field")) {
+        let args = attr.parse_args_with(Punctuated::<FieldArg, Token![,]>::parse_terminated)
+            .map_err(Diagnostic::from)?;
+
+        for arg in args {
+            if arg.key != "validate" {
+                return Err(arg.key.span().error("unknown `field` attribute parameter")
+                    .help("the only parameter is `validate`"));
+            }
+
+            let span = arg.value.span();
+            let call = match arg.value {
+                syn::Expr::Call(call) => call,
+                value => return Err(value.span().error("expected a validator call")
+                    .help("validators are called without their first argument, \
+                        as in `validate::len(1..)`"))
+            };
+
+            // Only `validate::` paths name Rocket's validators; everything
+            // else resolves in the caller's scope, as written.
+            let (func, args) = (&call.func, &call.args);
+            let func = match is_builtin(func) {
+                true => quote!(::rocket::request::#func),
+                false => quote!(#func),
+            };
+
+            let (call, uses_self) = replace_self(quote!(#func(__v, #args)));
+            validators.push(Validator { call, uses_self, span });
+        }
+    }
+
+    Ok(validators)
+}
+
+/// The `#[form]` attribute of an enum.
+#[derive(FromMeta)]
+struct EnumAttr {
+    tag: FormField,
+}
+
+/// The `#[form]` attribute of an enum variant.
+#[derive(FromMeta)]
+struct VariantAttr {
+    value: NameSource,
+}
+
+impl EnumAttr {
+    fn from_enum_attrs(gen: &DeriveGenerator) -> Result<Self> {
+        EnumAttr::from_attrs("form", &gen.input.attrs).unwrap_or_else(|| {
+            Err(gen.input.ident.span().error("enums require a tag field")
+                .help("specify the field that selects the variant: `#[form(tag = \"kind\")]`"))
+        })
+    }
+}
+
+impl VariantAttr {
+    fn name(variant: &Variant<'_>) -> Result<NameSource> {
+        Ok(VariantAttr::from_attrs("form", &variant.attrs)
+            .unwrap_or_else(|| Ok(VariantAttr { value: variant.ident.clone().into() }))?
+            .value)
+    }
+}
+
+/// Validates the names of `fields`, which may be empty, returning their spans.
+fn validate_fields(fields: Fields<'_>) -> Result<HashMap<NameSource, Span>> {
+    let mut names = HashMap::new();
+    for field in fields.iter() {
+        let id = field.ident.as_ref().expect("named field");
+        let field = FormAttr::from_attrs(id, &field.attrs)?.field;
+
         if let Some(span) = names.get(&field.name) {
             return Err(field.span.error("duplicate field name")
                        .span_note(*span, "previous definition here"));
@@ -56,25 +276,252 @@ fn validate_struct(_: &DeriveGenerator, data: Struct<'_>) -> Result<()> {
         names.insert(field.name, field.span);
     }
 
+    Ok(names)
+}
+
+fn validate_struct(_: &DeriveGenerator, data: Struct<'_>) -> Result<()> {
+    if data.fields().is_empty() {
+        return Err(data.fields.span().error("at least one field is required"));
+    }
+
+    validate_fields(data.fields()).map(|_| ())
+}
+
+fn validate_enum(gen: &DeriveGenerator, data: Enum<'_>) -> Result<()> {
+    if data.variants().next().is_none() {
+        return Err(data.brace_token.span.error("enum must have at least one variant"));
+    }
+
+    let tag = EnumAttr::from_enum_attrs(gen)?.tag;
+    let mut values = HashMap::new();
+    for variant in data.variants() {
+        if variant.fields().are_unnamed() {
+            return Err(variant.fields().span().error("tuple variants are not supported"));
+        }
+
+        let names = validate_fields(variant.fields())?;
+        if let Some(&span) = names.get(&tag.name) {
+            return Err(span.error("field name conflicts with the enum's tag")
+                       .span_note(tag.span, "tag declared here"));
+        }
+
+        let value = VariantAttr::name(&variant)?;
+        let span = variant.ident.span();
+        if let Some(previous) = values.insert(value.name().to_lowercase(), span) {
+            return Err(span.error("duplicate variant name")
+                       .span_note(previous, "previous definition here"));
+        }
+    }
+
     Ok(())
 }
 
+/// Returns `T` if `ty` is an `Option<T>`.
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let path = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None
+    };
+
+    let segment = path.segments.last()?;
+    let mut args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if segment.ident == "Option" => args.args.iter(),
+        _ => return None
+    };
+
+    match (args.next(), args.next()) {
+        (Some(syn::GenericArgument::Type(inner)), None) => Some(inner),
+        _ => None
+    }
+}
+
+/// Returns the body of `from_form` for the named `fields`, parsed from
+/// `__items`, which evaluates to a `Result` of `#build { fields.. }`. The
+/// caller declares `__prefix`, the item prefix of the fields, which is set from
+/// the first item if it's empty.
+fn fields_parser(fields: Fields<'_>, build: TokenStream, in_variant: bool) -> Result<TokenStream> {
+    define_vars_and_mods!(_Some, _Ok, _Err, _Vec);
+    let form_error = quote!(::rocket::request::FormFieldError);
+    let form_errors = quote!(::rocket::request::FormParseErrors);
+    let form_error_kind = quote!(::rocket::request::FormErrorKind);
+
+    if fields.is_empty() {
+        return Ok(quote! {
+            let mut __errors = #form_errors::new();
+            for __item in __items {
+                if __strict && __item.key != "_method" {
+                    __errors.push(#form_error::for_item(&__item, #form_error_kind::Unknown));
+                }
+            }
+
+            if __errors.is_empty() { #_Ok(#build) } else { #_Err(__errors) }
+        });
+    }
+
+    let mut names = vec![];
+    let fields = fields.iter().enumerate().map(|(i, field)| {
+        let (ident, span) = (&field.ident, field.span());
+        let attr = FormAttr::from_attrs(ident.as_ref().expect("named"), &field.attrs)?;
+        let name = attr.field.name;
+
+        let constructor = quote_spanned!(span => let mut #ident = #_Vec::new(););
+
+        let name = name.name().to_string();
+        let matcher = quote_spanned!(span => #name => #ident.push(__item),);
+
+        let option = option_inner(&field.ty);
+        let parse = match option {
+            Some(inner) => {
+                let ty = inner.with_stripped_lifetimes();
+                quote_spanned! { span =>
+                    ::rocket::request::parse_optional_field::<#ty>(
+                        #ident, __strict, &mut __errors
+                    )
+                }
+            }
+            None => {
+                let ty = field.ty.with_stripped_lifetimes();
+                quote_spanned! { span =>
+                    ::rocket::request::parse_field::<#ty>(
+                        #ident, __strict, __prefix, #name, &mut __errors
+                    )
+                }
+            }
+        };
+
+        let mut parser = match attr.default {
+            Some(default) => {
+                let default = quote_spanned!(default.span() => #_Some(#default));
+                quote_spanned! { span =>
+                    let #ident = if #ident.is_empty() { #default } else { #parse };
+                }
+            }
+            None => quote_spanned!(span => let #ident = #parse;),
+        };
+
+        // Validators run on a `__v` reference to the parsed value.
+        // Those that refer to `self` run after `__form` is built.
+        let validators = field_validators(&field.attrs)?;
+        let value = Ident::new(&format!("__value_{}", i), span);
+        let check = |validator: &Validator| {
+            let call = &validator.call;
+            quote_spanned! { span =>
+                let __result = #call;
+                if let #_Err(__e) = __result {
+                    let __path = ::rocket::request::field_path(__prefix, #name);
+                    let __message: ::std::borrow::Cow<'static, str> =
+                        ::std::string::ToString::to_string(&__e).into();
+
+                    for __value in &#value {
+                        let __kind = #form_error_kind::Invalid(__message.clone());
+                        __errors.push(#form_error::new(__path.clone(), *__value, __kind));
+                    }
+                }
+            }
+        };
+
+        let (cross, local): (Vec<_>, Vec<_>) = validators.iter()
+            .partition(|validator| validator.uses_self);
+
+        if let (true, Some(validator)) = (in_variant, cross.first()) {
+            return Err(validator.span.error("validators of enum variants cannot refer to `self`"));
+        }
+
+        if !validators.is_empty() {
+            parser = quote!(let #value = ::rocket::request::field_values(&#ident); #parser);
+        }
+
+        if !local.is_empty() {
+            let checks = local.into_iter().map(check);
+            parser.extend(match option {
+                Some(_) => quote!(if let #_Some(#_Some(ref __v)) = #ident { #(#checks)* }),
+                None => quote!(if let #_Some(ref __v) = #ident { #(#checks)* }),
+            });
+        }
+
+        let mut validator = quote!();
+        if !cross.is_empty() {
+            let checks = cross.into_iter().map(check);
+            validator = match option {
+                Some(_) => quote!(if let #_Some(ref __v) = __form.#ident { #(#checks)* }),
+                None => quote!({ let __v = &__form.#ident; #(#checks)* }),
+            };
+        }
+
+        names.push(name);
+        Ok((ident.clone(), constructor, matcher, parser, validator))
+    }).collect::<Result<Vec<_>>>()?;
+
+    let idents = fields.iter().map(|field| &field.0).collect::<Vec<_>>();
+    let constructors = fields.iter().map(|field| &field.1);
+    let matchers = fields.iter().map(|field| &field.2);
+    let parsers = fields.iter().map(|field| &field.3);
+    let validators = fields.iter().map(|field| &field.4);
+
+    Ok(quote! {
+        #(#constructors)*
+
+        let mut __errors = #form_errors::new();
+        let mut __unknown = #form_errors::new();
+        for (__i, __item) in __items.enumerate() {
+            if __i == 0 && __prefix.is_empty() {
+                __prefix = __item.prefix();
+            }
+
+            // A key that names a field exactly takes precedence over
+            // one that nests inside of a field.
+            let (__k, __item) = match __item.key.as_str() {
+                #(#names)|* => (__item.key, ::rocket::request::FormItem {
+                    key: "".into(), ..__item
+                }),
+                _ => __item.split_key(),
+            };
+
+            match __k.as_str() {
+                #(#matchers)*
+                _ if __strict && __k != "_method" => {
+                    let __kind = #form_error_kind::Unknown;
+                    __unknown.push(#form_error::for_item(&__item, __kind));
+                }
+                _ => { /* lenient or "method"; let it pass */ }
+            }
+        }
+
+        #(#parsers)*
+
+        // Validators referring to `self` run whenever every field parsed,
+        // even if another validator failed.
+        let __form = match (#(#idents,)*) {
+            (#(#_Some(#idents),)*) => #build { #(#idents),* },
+            _ => {
+                __errors.extend(__unknown);
+                return #_Err(__errors);
+            }
+        };
+
+        #(#validators)*
+        __errors.extend(__unknown);
+        if __errors.is_empty() { #_Ok(__form) } else { #_Err(__errors) }
+    })
+}
+
 pub fn derive_from_form(input: proc_macro::TokenStream) -> TokenStream {
-    let form_error = quote!(::rocket::request::FormParseError);
-    DeriveGenerator::build_for(input, quote!(impl<'__f> ::rocket::request::FromForm<'__f>))
+    let gen_trait = quote!(impl<'__f> ::rocket::request::FromForm<'__f>);
+    let from_form = DeriveGenerator::build_for(input.clone(), gen_trait)
         .generic_support(GenericSupport::Lifetime | GenericSupport::Type)
         .replace_generic(0, 0)
-        .data_support(DataSupport::NamedStruct)
+        .data_support(DataSupport::NamedStruct | DataSupport::Enum)
         .map_type_generic(|_, ident, _| quote! {
-            #ident : ::rocket::request::FromFormValue<'__f>
+            #ident : ::rocket::request::FromFormField<'__f>
         })
         .validate_generics(|_, generics| match generics.lifetimes().enumerate().last() {
             Some((i, lt)) if i >= 1 => Err(lt.span().error("only one lifetime is supported")),
             _ => Ok(())
         })
         .validate_struct(validate_struct)
+        .validate_enum(validate_enum)
         .function(|_, inner| quote! {
-            type Error = ::rocket::request::FormParseError<'__f>;
+            type Error = ::rocket::request::FormParseErrors<'__f>;
 
             fn from_form(
                 __items: &mut ::rocket::request::FormItems<'__f>,
@@ -83,51 +530,93 @@ pub fn derive_from_form(input: proc_macro::TokenStream) -> TokenStream {
                 #inner
             }
         })
-        .try_map_fields(move |_, fields| {
-            define_vars_and_mods!(_None, _Some, _Ok, _Err);
-            let (constructors, matchers, builders) = fields.iter().map(|field| {
-                let (ident, span) = (&field.ident, field.span());
-                let default_name = NameSource::from(ident.clone().expect("named"));
-                let name = Form::from_attrs("form", &field.attrs)
-                    .map(|result| result.map(|form| form.field.name))
-                    .unwrap_or_else(|| Ok(default_name))?;
+        .try_map_fields(|_, fields| {
+            let body = fields_parser(fields, quote!(Self), false)?;
+            Ok(quote! {
+                let mut __prefix: &::rocket::http::RawStr = "".into();
+                #body
+            })
+        })
+        .try_map_enum(|gen, data| {
+            define_vars_and_mods!(_Err);
+            let form_error = quote!(::rocket::request::FormFieldError);
+            let tag = EnumAttr::from_enum_attrs(gen)?.tag.name;
+            let tag = tag.name();
 
-                let ty = field.ty.with_stripped_lifetimes();
-                let ty = quote_spanned! {
-                    span => <#ty as ::rocket::request::FromFormValue>
-                };
+            let variants = data.variants().map(|variant| {
+                let value = VariantAttr::name(&variant)?;
+                let value = value.name();
+                let ident = &variant.ident;
+                let body = fields_parser(variant.fields(), quote!(Self::#ident), true)?;
+                Ok(quote! {
+                    if __value == #value {
+                        return { #body };
+                    }
+                })
+            }).collect::<Result<Vec<_>>>()?;
 
-                let constructor = quote_spanned!(span => let mut #ident = #_None;);
+            Ok(quote! {
+                let __items: ::std::vec::Vec<_> = __items.collect();
+                #[allow(unused_mut)]
+                let mut __prefix = __items.first()
+                    .map(|__item| __item.prefix())
+                    .unwrap_or_else(|| "".into());
 
-                let name = name.name();
-                let matcher = quote_spanned! { span =>
-                    #name => { #ident = #_Some(#ty::from_form_value(__v)
-                                .map_err(|_| #form_error::BadValue(__k, __v))?); },
-                };
+                let (__tags, __items): (::std::vec::Vec<_>, ::std::vec::Vec<_>) = __items
+                    .into_iter()
+                    .partition(|__item| {
+                        let (__k, __rest) = __item.split_key();
+                        __item.key.as_str() == #tag || (__k == #tag && __rest.key.is_empty())
+                    });
 
-                let builder = quote_spanned! { span =>
-                    #ident: #ident.or_else(#ty::default)
-                        .ok_or_else(|| #form_error::Missing(#name.into()))?,
+                let __tag = match __tags.last() {
+                    ::std::option::Option::Some(__tag) => *__tag,
+                    ::std::option::Option::None => {
+                        let __path = ::rocket::request::field_path(__prefix, #tag);
+                        return #_Err(#form_error::missing(__path).into());
+                    }
                 };
 
-                Ok((constructor, matcher, builder))
-            }).collect::<Result<Vec<_>>>()?.into_iter().split3();
+                let __items = &mut ::rocket::request::FormItems::from(__items);
+                let __decoded = __tag.value.url_decode_lossy();
+                let __value = ::rocket::http::uncased::UncasedStr::new(&__decoded);
+                #(#variants)*
 
-            Ok(quote! {
-                #(#constructors)*
-
-                for (__k, __v) in __items.map(|item| item.key_value()) {
-                    match __k.as_str() {
-                        #(#matchers)*
-                        _ if __strict && __k != "_method" => {
-                            return #_Err(#form_error::Unknown(__k, __v));
-                        }
-                        _ => { /* lenient or "method"; let it pass */ }
-                    }
-                }
-
-                #_Ok(Self { #(#builders)* })
+                let __kind = ::rocket::request::FormErrorKind::BadValue;
+                #_Err(#form_error::for_item(&__tag, __kind).into())
             })
         })
-        .to_tokens2()
+        .try_to_tokens();
+
+    let from_form = match from_form {
+        Ok(tokens) => tokens,
+        Err(diag) => return diag.emit_as_item_tokens()
+    };
+
+    let gen_trait = quote!(impl<'__f> ::rocket::request::FromFormField<'__f>);
+    let from_form_field = DeriveGenerator::build_for(input, gen_trait)
+        .generic_support(GenericSupport::Lifetime | GenericSupport::Type)
+        .replace_generic(0, 0)
+        .data_support(DataSupport::NamedStruct | DataSupport::Enum)
+        .map_type_generic(|_, ident, _| quote! {
+            #ident : ::rocket::request::FromFormField<'__f>
+        })
+        .function(|_, _| quote! {
+            fn from_form_field(
+                __items: &mut ::rocket::request::FormItems<'__f>,
+                __strict: bool,
+            ) -> ::std::result::Result<Self, ::rocket::request::FormParseErrors<'__f>> {
+                <Self as ::rocket::request::FromForm<'__f>>::from_form(__items, __strict)
+            }
+
+            fn default() -> ::std::option::Option<Self> {
+                let mut __items = ::rocket::request::FormItems::from(::std::vec::Vec::new());
+                <Self as ::rocket::request::FromForm<'__f>>::from_form(&mut __items, false).ok()
+            }
+        })
+        .to_tokens();
+
+    let mut ts = TokenStream::from(from_form);
+    ts.extend(TokenStream::from(from_form_field));
+    ts
 }
@@ -0,0 +1,121 @@
+use quote::ToTokens;
+use devise::{*, ext::SpanDiagnosticExt};
+
+use crate::proc_macro2::{TokenStream, TokenTree, Group};
+use crate::syn_ext::NameSource;
+
+#[derive(FromMeta)]
+struct Param {
+    value: NameSource,
+}
+
+/// Returns the path segment that `variant` is parsed from and displayed as:
+/// the `value` of its `#[param]` attribute or, if there is none, its name.
+pub fn variant_name(variant: &syn::Variant) -> Result<NameSource> {
+    let param = Param::from_attrs("param", &variant.attrs)
+        .unwrap_or_else(|| Ok(Param { value: variant.ident.clone().into() }))?;
+
+    Ok(param.value)
+}
+
+/// Replaces every lifetime other than `'static` in `tokens` with `lifetime`.
+fn replace_lifetimes(tokens: TokenStream, lifetime: &TokenStream) -> TokenStream {
+    let is_lifetime_name = |tree: Option<&TokenTree>| match tree {
+        Some(TokenTree::Ident(ident)) => ident != "static",
+        _ => false
+    };
+
+    let (mut trees, mut tokens) = (tokens.into_iter().peekable(), TokenStream::new());
+    while let Some(tree) = trees.next() {
+        match tree {
+            TokenTree::Punct(p) if p.as_char() == '\'' && is_lifetime_name(trees.peek()) => {
+                trees.next();
+                tokens.extend(lifetime.clone());
+            }
+            TokenTree::Group(group) => {
+                let stream = replace_lifetimes(group.stream(), lifetime);
+                let mut new = Group::new(group.delimiter(), stream);
+                new.set_span(group.span());
+                tokens.extend(Some(TokenTree::Group(new)));
+            }
+            tree => tokens.extend(Some(tree)),
+        }
+    }
+
+    tokens
+}
+
+pub fn derive_from_param(input: proc_macro::TokenStream) -> TokenStream {
+    define_vars_and_mods!(_Err, _Result);
+    DeriveGenerator::build_for(input, quote!(impl<'__a> ::rocket::request::FromParam<'__a>))
+        .generic_support(GenericSupport::Lifetime)
+        .replace_generic(0, 0)
+        .data_support(DataSupport::TupleStruct | DataSupport::Enum)
+        .validate_generics(|_, generics| match generics.lifetimes().enumerate().last() {
+            Some((i, lt)) if i >= 1 => Err(lt.span().error("only one lifetime is supported")),
+            _ => Ok(())
+        })
+        .validate_struct(|_, data| match data.fields().count() {
+            1 => Ok(()),
+            _ => Err(data.fields().span.error("struct must have exactly one field"))
+        })
+        .validate_enum(|_, data| {
+            for variant in data.variants() {
+                if !variant.fields().is_empty() {
+                    return Err(variant.fields().span().error("variants cannot have fields"));
+                }
+            }
+
+            if data.variants.is_empty() {
+                return Err(data.brace_token.span.error("enum must have at least one variant"));
+            }
+
+            Ok(())
+        })
+        .function(move |gen, inner| match gen.input.data {
+            syn::Data::Enum(_) => quote! {
+                type Error = &'__a ::rocket::http::RawStr;
+
+                fn from_param(
+                    __param: &'__a ::rocket::http::RawStr
+                ) -> #_Result<Self, Self::Error> {
+                    let __decoded = __param.percent_decode().map_err(|_| __param)?;
+                    let __value = ::rocket::http::uncased::UncasedStr::new(&__decoded);
+                    #inner
+                    #_Err(__param)
+                }
+            },
+            _ => inner
+        })
+        .try_map_struct(|_, data| {
+            define_vars_and_mods!(_Result);
+            let field = data.fields().iter().next().expect("exactly one field");
+            let ty = replace_lifetimes(field.ty.to_token_stream(), &quote!('__a));
+            let from_param = quote_spanned! { field.span().into() =>
+                <#ty as ::rocket::request::FromParam<'__a>>::from_param
+            };
+
+            Ok(quote! {
+                type Error = <#ty as ::rocket::request::FromParam<'__a>>::Error;
+
+                fn from_param(
+                    __param: &'__a ::rocket::http::RawStr
+                ) -> #_Result<Self, Self::Error> {
+                    #from_param(__param).map(Self)
+                }
+            })
+        })
+        .try_map_enum(null_enum_mapper)
+        .try_map_variant(|_, variant| {
+            define_vars_and_mods!(_Ok);
+            let name = variant_name(&variant)?;
+            let name = name.name();
+            let builder = variant.builder(|_| unreachable!("no fields"));
+            Ok(quote! {
+                if __value == #name {
+                    return #_Ok(#builder);
+                }
+            })
+        })
+        .to_tokens2()
+}
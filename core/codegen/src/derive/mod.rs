@@ -1,4 +1,5 @@
 pub mod from_form;
 pub mod from_form_value;
+pub mod from_param;
 pub mod responder;
 pub mod uri_display;
@@ -1,7 +1,8 @@
 
 use devise::{*, ext::SpanDiagnosticExt};
 
-use crate::derive::from_form::Form;
+use crate::derive::from_form::FormAttr;
+use crate::derive::from_param::variant_name;
 use crate::proc_macro2::TokenStream;
 
 const NO_EMPTY_FIELDS: &str = "fieldless structs or variants are not supported";
@@ -9,6 +10,7 @@ const NO_NULLARY: &str = "nullary items are not supported";
 const NO_EMPTY_ENUMS: &str = "empty enums are not supported";
 const ONLY_ONE_UNNAMED: &str = "tuple structs or variants must have exactly one field";
 const EXACTLY_ONE_FIELD: &str = "struct must have exactly one field";
+const NO_VARIANT_FIELDS: &str = "variants cannot have fields";
 
 fn validate_fields(ident: &syn::Ident, fields: Fields<'_>) -> Result<()> {
     if fields.count() == 0 {
@@ -57,9 +59,7 @@ pub fn derive_uri_display_query(input: proc_macro::TokenStream) -> TokenStream {
             let span = field.span().into();
             let accessor = field.accessor();
             let tokens = if let Some(ref ident) = field.ident {
-                let name_source = Form::from_attrs("form", &field.attrs)
-                    .map(|result| result.map(|form| form.field.name))
-                    .unwrap_or_else(|| Ok(ident.clone().into()))?;
+                let name_source = FormAttr::from_attrs(ident, &field.attrs)?.field.name;
 
                 let name = name_source.name();
                 quote_spanned!(span => f.write_named_value(#name, &#accessor)?;)
@@ -133,12 +133,25 @@ pub fn derive_uri_display_path(input: proc_macro::TokenStream) -> TokenStream {
     let FromUriParam = quote!(::rocket::http::uri::FromUriParam);
 
     let uri_display = DeriveGenerator::build_for(input.clone(), quote!(impl #UriDisplay))
-        .data_support(DataSupport::TupleStruct)
+        .data_support(DataSupport::TupleStruct | DataSupport::Enum)
         .generic_support(GenericSupport::Type | GenericSupport::Lifetime)
         .map_type_generic(move |_, ident, _| quote!(#ident : #UriDisplay))
-        .validate_fields(|_, fields| match fields.count() {
+        .validate_struct(|_, data| match data.fields().count() {
             1 => Ok(()),
-            _ => Err(fields.span.error(EXACTLY_ONE_FIELD))
+            _ => Err(data.fields().span.error(EXACTLY_ONE_FIELD))
+        })
+        .validate_enum(|_, data| {
+            if data.variants().count() == 0 {
+                return Err(data.brace_token.span.error(NO_EMPTY_ENUMS));
+            }
+
+            for variant in data.variants() {
+                if !variant.fields().is_empty() {
+                    return Err(variant.fields().span().error(NO_VARIANT_FIELDS));
+                }
+            }
+
+            Ok(())
         })
         .function(move |_, inner| quote! {
             fn fmt(&self, f: &mut #Formatter) -> ::std::fmt::Result {
@@ -151,6 +164,11 @@ pub fn derive_uri_display_path(input: proc_macro::TokenStream) -> TokenStream {
             let accessor = field.accessor();
             quote_spanned!(span => f.write_value(&#accessor)?;)
         })
+        .try_map_variant(|_, variant| {
+            let name = variant_name(&variant)?;
+            let name = name.name();
+            Ok(quote!(f.write_value(#name)?;))
+        })
         .try_to_tokens();
 
     let uri_display = match uri_display {
@@ -95,6 +95,7 @@ vars_and_mods! {
     StaticCatcherInfo => rocket::StaticCatcherInfo,
     Route => rocket::Route,
     Catcher => rocket::Catcher,
+    Span => rocket::trace::Span,
     SmallVec => rocket::http::private::SmallVec,
     Status => rocket::http::Status,
     HandlerFuture => rocket::handler::HandlerFuture,
@@ -212,6 +213,8 @@ macro_rules! route_attribute {
         /// parameter := 'rank' '=' INTEGER
         ///            | 'format' '=' '"' MEDIA_TYPE '"'
         ///            | 'data' '=' '"' SINGLE_PARAM '"'
+        ///            | 'timeout' '=' INTEGER
+        ///            | 'concurrency' '=' INTEGER
         ///
         /// SINGLE_PARAM := '<' IDENT '>'
         /// MULTI_PARAM := '<' IDENT '..>'
@@ -312,9 +315,9 @@ macro_rules! route_attribute {
         ///   2. A static structure used by [`routes!`] to generate a [`Route`].
         ///
         ///      The static structure (and resulting [`Route`]) is populated
-        ///      with the name (the function's name), path, query, rank, and
-        ///      format from the route attribute. The handler is set to the
-        ///      generated handler.
+        ///      with the name (the function's name), path, query, rank,
+        ///      format, timeout, and concurrency from the route attribute. The
+        ///      handler is set to the generated handler.
         ///
         ///   3. A macro used by [`uri!`] to type-check and generate an
         ///      [`Origin`].
@@ -509,22 +512,61 @@ pub fn derive_from_form_value(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
-/// Each field's type is required to implement [`FromFormValue`].
+/// Each field's type is required to implement [`FromFormField`], which is
+/// implemented for all types that implement [`FromFormValue`], for structures
+/// deriving `FromForm`, and for `Vec` and `HashMap` of such types. A field of
+/// type `Option<T>` only requires `T` to implement `FromFormField`.
 ///
 /// The derive generates an implementation of the [`FromForm`] trait. The
 /// implementation parses a form whose field names match the field names of the
-/// structure on which the derive was applied. Each field's value is parsed with
-/// the [`FromFormValue`] implementation of the field's type. The `FromForm`
-/// implementation succeeds only when all of the field parses succeed. If
-/// parsing fails, an error ([`FromForm::Error`]) of type [`FormParseError`] is
-/// returned.
+/// structure on which the derive was applied. Each field is parsed with the
+/// [`FromFormField`] implementation of the field's type from the items whose
+/// keys name the field exactly or nest inside of it, such as `field.name` or
+/// `field[0]`. The `FromForm` implementation succeeds only when all of the
+/// field parses succeed. If parsing fails, an error ([`FromForm::Error`]) of
+/// type [`FormParseErrors`] listing every error in the form is returned.
+///
+/// The derive also generates an implementation of [`FromFormField`] so that
+/// the structure can be nested inside of other forms. When a nested structure
+/// is missing entirely, its default is the structure parsed from an empty
+/// form, if that succeeds.
+///
+/// The derive can also be applied to enums whose variants have named fields
+/// or no fields. The enum must be annotated with `#[form(tag = "name")]`,
+/// naming the _tag_ field whose value selects the variant:
 ///
-/// The derive accepts one field attribute: `form`, with the following syntax:
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromForm)]
+/// #[form(tag = "kind")]
+/// enum Payment {
+///     Card { number: String, cvv: u16 },
+///     #[form(value = "bank")]
+///     Transfer { iban: String },
+///     Cash,
+/// }
+/// ```
+///
+/// The tag's value is URL decoded and matched case-insensitively against the
+/// name of each variant or, if present, the variant's `#[form(value =
+/// "...")]`. The remaining items of the form are then parsed into the selected
+/// variant's fields exactly as they would be for a structure, so the form
+/// `kind=card&number=42&cvv=123` parses as `Payment::Card`. A missing tag or a
+/// tag value that names no variant is an error. Validators in enum variants
+/// cannot refer to `self`.
+///
+/// The derive accepts two field attributes: `form` and `field`. The `form`
+/// attribute has the following syntax:
 ///
 /// ```text
-/// form := 'field' '=' '"' IDENT '"'
+/// form := param (',' param)*
+///
+/// param := 'field' '=' '"' IDENT '"'
+///        | 'default' '=' EXPR
 ///
 /// IDENT := valid identifier, as defined by Rust
+/// EXPR := expression of the field's type
 /// ```
 ///
 /// When applied, the attribute looks as follows:
@@ -536,25 +578,152 @@ pub fn derive_from_form_value(input: TokenStream) -> TokenStream {
 /// struct MyStruct {
 ///     field: usize,
 ///     #[form(field = "renamed_field")]
-///     other: String
+///     other: String,
+///     #[form(field = "page", default = 1)]
+///     page_number: usize,
 /// }
 /// ```
 ///
-/// The field attribute directs that a different incoming field name is
-/// expected, and the value of the `field` attribute is used instead of the
+/// The `field` parameter directs that a different incoming field name is
+/// expected, and the value of the `field` parameter is used instead of the
 /// structure's actual field name when parsing a form. In the example above, the
 /// value of the `MyStruct::other` struct field will be parsed from the incoming
 /// form's `renamed_field` field.
 ///
+/// The `default` parameter provides the value of a field that is missing from
+/// the form, overriding the field type's [`FromFormField::default()`]. In the
+/// example above, `page_number` is `1` when there is no `page` field. A field
+/// that is present but fails to parse is an error regardless.
+///
+/// The `field` attribute, which may be repeated, validates a field after it
+/// parses and has the following syntax:
+///
+/// ```text
+/// field := 'validate' '=' CALL (',' 'validate' '=' CALL)*
+///
+/// CALL := a function call expression without its first argument
+/// ```
+///
+/// When applied, the attribute looks as follows:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromForm)]
+/// struct MyStruct {
+///     #[field(validate = validate::range(1..=10))]
+///     field: usize,
+///     #[field(validate = validate::len(1..), validate = validate::one_of(&["a", "b"]))]
+///     other: String,
+///     #[field(validate = validate::eq(&self.other))]
+///     confirm: String,
+/// }
+/// ```
+///
+/// Each `CALL` is invoked with a reference to the parsed value prepended to
+/// its arguments. A `CALL` to a `validate::` path, like `validate::len(1..)`,
+/// invokes a validator in [`rocket::request::validate`]; any other function is
+/// resolved in the scope of the structure. `self` in a `CALL` refers to the
+/// structure being parsed; such validators run once every field has parsed,
+/// even if other validators failed. A call returns a `Result<(), E>` where `E:
+/// Display`; on `Err`, a [`FormFieldError`] of kind [`FormErrorKind::Invalid`]
+/// with the message and the field's path is added to the returned errors, one
+/// for each value of a field submitted more than once. Validators of
+/// `Option<T>` fields are only invoked when the field is present.
+///
 /// [`FromForm`]: ../rocket/request/trait.FromForm.html
 /// [`FromFormValue`]: ../rocket/request/trait.FromFormValue.html
-/// [`FormParseError`]: ../rocket/request/enum.FormParseError.html
+/// [`FromFormField`]: ../rocket/request/trait.FromFormField.html
+/// [`FromFormField::default()`]: ../rocket/request/trait.FromFormField.html#method.default
+/// [`FormParseErrors`]: ../rocket/request/struct.FormParseErrors.html
 /// [`FromForm::Error`]: ../rocket/request/trait.FromForm.html#associatedtype.Error
-#[proc_macro_derive(FromForm, attributes(form))]
+/// [`rocket::request::validate`]: ../rocket/request/validate/index.html
+/// [`FormFieldError`]: ../rocket/request/struct.FormFieldError.html
+/// [`FormErrorKind::Invalid`]: ../rocket/request/enum.FormErrorKind.html#variant.Invalid
+#[proc_macro_derive(FromForm, attributes(form, field))]
 pub fn derive_from_form(input: TokenStream) -> TokenStream {
     emit!(derive::from_form::derive_from_form(input))
 }
 
+/// Derive for the [`FromParam`] trait.
+///
+/// The [`FromParam`] derive can be applied to enums whose variants have no
+/// fields and to tuple structs with one field:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[derive(FromParam)]
+/// enum Kind {
+///     Rss,
+///     #[param(value = "atom")]
+///     Atom,
+///     Json,
+/// }
+///
+/// #[derive(FromParam)]
+/// struct Id(u32);
+///
+/// #[get("/feed/<kind>/<id>")]
+/// fn feed(kind: Kind, id: Id) { /* ... */ }
+/// ```
+///
+/// For an enum, the derive generates an implementation of [`FromParam`] that
+/// returns successfully when the percent-decoded path segment matches, case
+/// insensitively, the name of a variant, returning said variant. If there is
+/// no match, an error of type [`&RawStr`] is returned, the value of which is
+/// the raw path segment. As with `#[derive(FromFormValue)]`, the string
+/// matched against for a variant can be changed with an attribute, here named
+/// `param`:
+///
+/// ```text
+/// param := 'value' '=' STRING_LIT
+///
+/// STRING_LIT := any valid string literal, as defined by Rust
+/// ```
+///
+/// In the example above, the segments `rss`, `RSS`, and so on parse as
+/// `Kind::Rss`, while `atom` and `json` parse as `Kind::Atom` and `Kind::Json`.
+///
+/// For a tuple struct, the implementation parses the segment with the
+/// [`FromParam`] implementation of the field's type, wrapping the parsed value
+/// in the struct. The error type is the error type of the field's
+/// implementation. The struct may have at most one lifetime parameter, which is
+/// bound to the lifetime of the path segment:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[derive(FromParam)]
+/// struct Slug<'r>(&'r rocket::http::RawStr);
+/// ```
+///
+/// To use the type in a [`uri!`] invocation, additionally derive
+/// `UriDisplayPath`, which displays a variant as the same string it is parsed
+/// from:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[derive(FromParam, UriDisplayPath)]
+/// enum Kind {
+///     #[param(value = "rss")]
+///     Rss,
+///     #[param(value = "atom")]
+///     Atom,
+/// }
+///
+/// #[get("/feed/<kind>")]
+/// fn feed(kind: Kind) { /* ... */ }
+///
+/// assert_eq!(uri!(feed: Kind::Atom).to_string(), "/feed/atom");
+/// ```
+///
+/// [`FromParam`]: ../rocket/request/trait.FromParam.html
+/// [`&RawStr`]: ../rocket/http/struct.RawStr.html
+/// [`uri!`]: macro.uri.html
+#[proc_macro_derive(FromParam, attributes(param))]
+pub fn derive_from_param(input: TokenStream) -> TokenStream {
+    emit!(derive::from_param::derive_from_param(input))
+}
+
 /// Derive for the [`Responder`] trait.
 ///
 /// The [`Responder`] derive can be applied to enums and structs with named
@@ -756,7 +925,7 @@ pub fn derive_uri_display_query(input: TokenStream) -> TokenStream {
 /// Derive for the [`UriDisplay<Path>`] trait.
 ///
 /// The [`UriDisplay<Path>`] derive can only be applied to tuple structs with
-/// one field.
+/// one field and to enums whose variants have no fields.
 ///
 /// ```rust
 /// # #[macro_use] extern crate rocket;
@@ -765,16 +934,27 @@ pub fn derive_uri_display_query(input: TokenStream) -> TokenStream {
 ///
 /// #[derive(UriDisplayPath)]
 /// struct Age(usize);
+///
+/// #[derive(UriDisplayPath)]
+/// enum Kind {
+///     Rss,
+///     #[param(value = "atom")]
+///     Atom,
+/// }
 /// ```
 ///
 /// The field's type is required to implement [`UriDisplay<Path>`].
 ///
 /// The derive generates an implementation of the [`UriDisplay<Path>`] trait.
-/// The implementation calls [`Formatter::write_value()`] for the field.
+/// For a struct, the implementation calls [`Formatter::write_value()`] for the
+/// field. For an enum, it calls [`Formatter::write_value()`] with the name of
+/// the variant or, if it has one, the `value` of its `#[param]` attribute, as
+/// in `#[derive(FromParam)]`. For the `enum` above, `Kind::Rss`
+/// is displayed as `Rss`, and `Kind::Atom` as `atom`.
 ///
 /// [`UriDisplay<Path>`]: ../rocket/http/uri/trait.UriDisplay.html
 /// [`Formatter::write_value()`]: ../rocket/http/uri/struct.Formatter.html#method.write_value
-#[proc_macro_derive(UriDisplayPath)]
+#[proc_macro_derive(UriDisplayPath, attributes(param))]
 pub fn derive_uri_display_path(input: TokenStream) -> TokenStream {
     emit!(derive::uri_display::derive_uri_display_path(input))
 }
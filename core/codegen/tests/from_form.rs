@@ -1,10 +1,12 @@
 #[macro_use] extern crate rocket;
 
-use rocket::request::{FromForm, FormItems, FormParseError};
+use std::collections::HashMap;
+
+use rocket::request::{FromForm, FormItems, FormFieldError, FormParseErrors, FormErrorKind};
 use rocket::http::RawStr;
 
-fn parse<'f, T>(string: &'f str, strict: bool) -> Result<T, FormParseError<'f>>
-    where T: FromForm<'f, Error = FormParseError<'f>>
+fn parse<'f, T>(string: &'f str, strict: bool) -> Result<T, FormParseErrors<'f>>
+    where T: FromForm<'f, Error = FormParseErrors<'f>>
 {
     let mut items = FormItems::from(string);
     let result = T::from_form(items.by_ref(), strict);
@@ -15,18 +17,34 @@ fn parse<'f, T>(string: &'f str, strict: bool) -> Result<T, FormParseError<'f>>
     result
 }
 
-fn strict<'f, T>(string: &'f str) -> Result<T, FormParseError<'f>>
-    where T: FromForm<'f, Error = FormParseError<'f>>
+fn strict<'f, T>(string: &'f str) -> Result<T, FormParseErrors<'f>>
+    where T: FromForm<'f, Error = FormParseErrors<'f>>
 {
     parse(string, true)
 }
 
-fn lenient<'f, T>(string: &'f str) -> Result<T, FormParseError<'f>>
-    where T: FromForm<'f, Error = FormParseError<'f>>
+fn lenient<'f, T>(string: &'f str) -> Result<T, FormParseErrors<'f>>
+    where T: FromForm<'f, Error = FormParseErrors<'f>>
 {
     parse(string, false)
 }
 
+fn errors<'f, T>(result: Result<T, FormParseErrors<'f>>) -> Vec<FormFieldError<'f>> {
+    result.err().expect("form errors").to_vec()
+}
+
+fn bad_value<'f>(path: &str, value: &'f str) -> FormFieldError<'f> {
+    FormFieldError::new(path, Some(value.into()), FormErrorKind::BadValue)
+}
+
+fn unknown<'f>(path: &str, value: &'f str) -> FormFieldError<'f> {
+    FormFieldError::new(path, Some(value.into()), FormErrorKind::Unknown)
+}
+
+fn missing(path: &str) -> FormFieldError<'static> {
+    FormFieldError::missing(path)
+}
+
 #[derive(Debug, PartialEq, FromForm)]
 struct TodoTask {
     description: String,
@@ -297,25 +315,29 @@ fn form_errors() {
     assert_eq!(form, Ok(WhoopsForm { complete: true, other: 781 }));
 
     let form: Result<WhoopsForm, _> = strict("complete=true&other=unknown");
-    assert_eq!(form, Err(FormParseError::BadValue("other".into(), "unknown".into())));
-
-    let form: Result<WhoopsForm, _> = strict("complete=unknown&other=unknown");
-    assert_eq!(form, Err(FormParseError::BadValue("complete".into(), "unknown".into())));
+    assert_eq!(errors(form), vec![bad_value("other", "unknown")]);
 
     let form: Result<WhoopsForm, _> = strict("complete=true&other=1&extra=foo");
-    assert_eq!(form, Err(FormParseError::Unknown("extra".into(), "foo".into())));
-
-    // Bad values take highest precedence.
-    let form: Result<WhoopsForm, _> = strict("complete=unknown&unknown=foo");
-    assert_eq!(form, Err(FormParseError::BadValue("complete".into(), "unknown".into())));
+    assert_eq!(errors(form), vec![unknown("extra", "foo")]);
 
-    // Then unknown key/values for strict parses.
-    let form: Result<WhoopsForm, _> = strict("complete=true&unknown=foo");
-    assert_eq!(form, Err(FormParseError::Unknown("unknown".into(), "foo".into())));
-
-    // Finally, missing.
     let form: Result<WhoopsForm, _> = strict("complete=true");
-    assert_eq!(form, Err(FormParseError::Missing("other".into())));
+    assert_eq!(errors(form), vec![missing("other")]);
+
+    // All errors are collected: first in field order, then unknown fields.
+    let form: Result<WhoopsForm, _> = strict("complete=unknown&other=unknown");
+    assert_eq!(errors(form), vec![
+        bad_value("complete", "unknown"), bad_value("other", "unknown"),
+    ]);
+
+    let form: Result<WhoopsForm, _> = strict("extra=bar&complete=unknown&unknown=foo");
+    assert_eq!(errors(form), vec![
+        bad_value("complete", "unknown"), missing("other"),
+        unknown("extra", "bar"), unknown("unknown", "foo"),
+    ]);
+
+    // Unknown fields are only errors when parsing strictly.
+    let form: Result<WhoopsForm, _> = lenient("complete=true&unknown=foo");
+    assert_eq!(errors(form), vec![missing("other")]);
 }
 
 #[derive(Debug, PartialEq, FromForm)]
@@ -328,3 +350,334 @@ fn raw_ident_form() {
     let form: Result<RawIdentForm, _> = strict("type=a");
     assert_eq!(form, Ok(RawIdentForm { r#type: "a".into() }));
 }
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct LineItem {
+    name: String,
+    quantity: usize,
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Order {
+    address: Address,
+    billing: Option<Address>,
+    items: Vec<LineItem>,
+    tags: Vec<String>,
+    notes: HashMap<String, usize>,
+    coupons: Option<Vec<String>>,
+}
+
+#[test]
+fn nested_structures() {
+    let form_string = &[
+        "address.street=Main", "address.city=Paris",
+        "items[0].name=apple", "items[1].name=pear", "items[0].quantity=3",
+        "items%5B1%5D.quantity=1", "tags=a", "tags=b", "notes[door]=1", "notes.gate=2",
+    ].join("&");
+
+    let order: Result<Order, _> = strict(&form_string);
+    assert_eq!(order, Ok(Order {
+        address: Address { street: "Main".into(), city: "Paris".into() },
+        billing: None,
+        items: vec![
+            LineItem { name: "apple".into(), quantity: 3 },
+            LineItem { name: "pear".into(), quantity: 1 },
+        ],
+        tags: vec!["a".into(), "b".into()],
+        notes: vec![("door".to_string(), 1), ("gate".to_string(), 2)].into_iter().collect(),
+        coupons: None,
+    }));
+
+    let form_string = &[
+        "address[street]=Main", "address[city]=Paris", "billing.street=Side",
+        "billing.city=Lyon", "coupons[]=x", "coupons[]=y",
+    ].join("&");
+
+    let order: Result<Order, _> = strict(&form_string);
+    assert_eq!(order, Ok(Order {
+        address: Address { street: "Main".into(), city: "Paris".into() },
+        billing: Some(Address { street: "Side".into(), city: "Lyon".into() }),
+        items: vec![],
+        tags: vec![],
+        notes: HashMap::new(),
+        coupons: Some(vec!["x".into(), "y".into()]),
+    }));
+
+}
+
+#[test]
+fn nested_errors() {
+    let order: Result<Order, _> = strict("address.street=Main");
+    assert_eq!(errors(order), vec![missing("address.city")]);
+
+    let order: Result<Order, _> = strict("address.street=a&address.city=b&items[0].name=x");
+    assert_eq!(errors(order), vec![missing("items[0].quantity")]);
+
+    let form_string = "address.street=a&address.city=b&items[0].name=x&items[0].quantity=y";
+    let order: Result<Order, _> = strict(form_string);
+    assert_eq!(errors(order), vec![bad_value("items[0].quantity", "y")]);
+
+    let order: Result<Order, _> = strict("address.street=a&address.city=b&notes[x]=y");
+    assert_eq!(errors(order), vec![bad_value("notes[x]", "y")]);
+
+    // Unknown nested fields are rejected when strict, allowed when lenient.
+    let form_string = "address.street=a&address.city=b&address.zip=c";
+    let order: Result<Order, _> = strict(form_string);
+    assert_eq!(errors(order), vec![unknown("address.zip", "c")]);
+
+    let order: Result<Order, _> = lenient(form_string);
+    assert_eq!(order.map(|order| order.address.city), Ok("b".into()));
+
+    // Errors nested in an optional field aren't swallowed.
+    let order: Result<Order, _> = strict("address.street=a&address.city=b&billing.city=c");
+    assert_eq!(errors(order), vec![missing("billing.street")]);
+
+    // Errors in every nested field are collected.
+    let form_string = "address.street=a&items[0].quantity=y&items%5B1%5D.name=z&extra=1";
+    let order: Result<Order, _> = strict(form_string);
+    assert_eq!(errors(order), vec![
+        missing("address.city"), missing("items[0].name"), bad_value("items[0].quantity", "y"),
+        missing("items[1].quantity"), unknown("extra", "1"),
+    ]);
+}
+
+fn invalid<'f>(path: &str, value: &'f str, message: &'static str) -> FormFieldError<'f> {
+    FormFieldError::new(path, Some(value.into()), FormErrorKind::Invalid(message.into()))
+}
+
+fn not_admin(name: &str) -> Result<(), &'static str> {
+    if name == "admin" { Err("is reserved") } else { Ok(()) }
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Signup {
+    #[field(validate = validate::len(1..=8), validate = not_admin())]
+    name: String,
+    #[field(validate = validate::range(13..))]
+    age: u8,
+    #[field(validate = validate::email())]
+    email: String,
+    #[field(validate = validate::url())]
+    website: Option<String>,
+    #[field(validate = validate::one_of(&["free", "pro"]))]
+    plan: String,
+    password: String,
+    #[field(validate = validate::eq(&self.password))]
+    confirm: String,
+    #[field(validate = validate::len(..3))]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Account {
+    #[form(field = "user")]
+    signup: Signup,
+}
+
+#[test]
+fn field_validation() {
+    let form_string = "name=bob&age=13&email=bob@example.com&plan=pro\
+        &password=hunter2&confirm=hunter2&tags=a&tags=b";
+    let signup: Signup = strict(form_string).unwrap();
+    assert_eq!(signup.website, None);
+    assert_eq!(signup.tags, vec!["a".to_string(), "b".to_string()]);
+
+    let signup: Signup = strict(&format!("{}&website=https://rocket.rs", form_string)).unwrap();
+    assert_eq!(signup.website, Some("https://rocket.rs".into()));
+
+    // Every failing validator is reported, including those of `Option` fields
+    // and those referring to `self`, with every value of a repeated field.
+    let form_string = "name=administrator&age=9&email=bob&plan=gold&password=a&confirm=b\
+        &website=rocket&tags=a&tags=b&tags=c";
+    let signup: Result<Signup, _> = strict(form_string);
+    assert_eq!(errors(signup), vec![
+        invalid("name", "administrator", "length must be between 1 and 8"),
+        invalid("age", "9", "must be at least 13"),
+        invalid("email", "bob", "must be an email address"),
+        invalid("website", "rocket", "must be a URL"),
+        invalid("plan", "gold", "must be one of free, pro"),
+        invalid("tags", "a", "length must be less than 3"),
+        invalid("tags", "b", "length must be less than 3"),
+        invalid("tags", "c", "length must be less than 3"),
+        invalid("confirm", "b", "does not match"),
+    ]);
+
+    // User functions are called; parse errors and validation errors mix.
+    let signup: Result<Signup, _> = strict("name=admin&age=old&email=a@b.c&plan=free");
+    assert_eq!(errors(signup), vec![
+        invalid("name", "admin", "is reserved"),
+        bad_value("age", "old"),
+        missing("password"),
+        missing("confirm"),
+    ]);
+
+    // Validators referring to `self` run once every field has parsed.
+    let form_string = "name=bob&age=13&email=bob@example.com&plan=pro&password=a&confirm=b";
+    let signup: Result<Signup, _> = strict(form_string);
+    assert_eq!(errors(signup), vec![invalid("confirm", "b", "does not match")]);
+
+    // Nested fields are reported with their full path.
+    let form_string = "user.name=bob&user.age=1&user.email=bob@example.com&user.plan=pro\
+        &user.password=a&user.confirm=b";
+    let account: Result<Account, _> = strict(form_string);
+    assert_eq!(errors(account), vec![
+        invalid("user.age", "1", "must be at least 13"),
+        invalid("user.confirm", "b", "does not match"),
+    ]);
+}
+
+fn email(address: &str, domain: &str) -> Result<(), String> {
+    match address.ends_with(domain) {
+        true => Ok(()),
+        false => Err(format!("must end in {}", domain)),
+    }
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Invite {
+    #[field(validate = email("@rocket.rs"))]
+    email: String,
+    #[field(validate = validate::email())]
+    reply_to: String,
+}
+
+#[test]
+fn user_validators_are_not_shadowed() {
+    let invite: Invite = strict("email=bob@rocket.rs&reply_to=bob@example.com").unwrap();
+    assert_eq!(invite.email, "bob@rocket.rs");
+
+    // `email` is the function above; `validate::email` is Rocket's.
+    let invite: Result<Invite, _> = strict("email=bob@example.com&reply_to=bob");
+    assert_eq!(errors(invite), vec![
+        invalid("email", "bob@example.com", "must end in @rocket.rs"),
+        invalid("reply_to", "bob", "must be an email address"),
+    ]);
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Search {
+    #[form(default = "*".to_string())]
+    query: String,
+    #[form(field = "page", default = 1)]
+    page_number: usize,
+    #[form(default = vec!["name".into()], field = "sort")]
+    sort_by: Vec<String>,
+    #[form(default = Some(HashMap::<String, usize>::new()))]
+    weights: Option<HashMap<String, usize>>,
+    exact: bool,
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct SavedSearch {
+    name: String,
+    search: Search,
+}
+
+#[test]
+fn field_defaults() {
+    let search: Search = strict("").unwrap();
+    assert_eq!(search, Search {
+        query: "*".into(),
+        page_number: 1,
+        sort_by: vec!["name".into()],
+        weights: Some(HashMap::new()),
+        exact: false,
+    });
+
+    let form_string = "query=rocket&page=2&sort=date&sort=stars&weights[a]=3&exact=on";
+    let search: Search = strict(form_string).unwrap();
+    assert_eq!(search, Search {
+        query: "rocket".into(),
+        page_number: 2,
+        sort_by: vec!["date".into(), "stars".into()],
+        weights: Some(vec![("a".to_string(), 3)].into_iter().collect()),
+        exact: true,
+    });
+
+    // Defaults don't apply to fields that are present but invalid.
+    let search: Result<Search, _> = strict("page=two");
+    assert_eq!(errors(search), vec![bad_value("page", "two")]);
+
+    // A missing structure whose fields all have defaults is defaulted.
+    let saved: SavedSearch = strict("name=mine").unwrap();
+    assert_eq!(saved.search, strict::<Search>("").unwrap());
+
+    let saved: SavedSearch = strict("name=mine&search.page=3").unwrap();
+    assert_eq!((saved.search.query.as_str(), saved.search.page_number), ("*", 3));
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+#[form(tag = "kind")]
+enum Payment {
+    Card {
+        number: String,
+        #[form(field = "cvv")]
+        code: u16,
+    },
+    #[form(value = "bank")]
+    Transfer {
+        iban: String,
+        #[form(default = "EUR".into())]
+        currency: String,
+    },
+    Cash,
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Checkout {
+    total: usize,
+    payment: Payment,
+}
+
+#[test]
+fn enums() {
+    let payment: Payment = strict("kind=card&number=4242&cvv=123").unwrap();
+    assert_eq!(payment, Payment::Card { number: "4242".into(), code: 123 });
+
+    let payment: Payment = strict("iban=DE00&kind=BANK").unwrap();
+    assert_eq!(payment, Payment::Transfer { iban: "DE00".into(), currency: "EUR".into() });
+
+    let payment: Payment = strict("kind=cash").unwrap();
+    assert_eq!(payment, Payment::Cash);
+
+    // The tag is URL decoded before it's matched.
+    let payment: Payment = strict("kind=%43ash").unwrap();
+    assert_eq!(payment, Payment::Cash);
+
+    // Fields of other variants are unexpected when strict.
+    let payment: Result<Payment, _> = strict("kind=cash&number=4242");
+    assert_eq!(errors(payment), vec![unknown("number", "4242")]);
+
+    let payment: Payment = lenient("kind=cash&number=4242").unwrap();
+    assert_eq!(payment, Payment::Cash);
+
+    // The tag is required and must name a variant.
+    let payment: Result<Payment, _> = strict("number=4242");
+    assert_eq!(errors(payment), vec![missing("kind")]);
+
+    let payment: Result<Payment, _> = strict("kind=check&number=4242");
+    assert_eq!(errors(payment), vec![bad_value("kind", "check")]);
+
+    let payment: Result<Payment, _> = strict("kind=card&number=4242&cvv=abc");
+    assert_eq!(errors(payment), vec![bad_value("cvv", "abc")]);
+
+    // Enums can be nested.
+    let checkout: Checkout = strict("total=10&payment.kind=card&payment.number=1&payment.cvv=2")
+        .unwrap();
+    assert_eq!(checkout.payment, Payment::Card { number: "1".into(), code: 2 });
+
+    let form_string = "total=10&payment[kind]=cash";
+    assert_eq!(strict::<Checkout>(form_string).unwrap().payment, Payment::Cash);
+
+    let checkout: Result<Checkout, _> = strict("total=10&payment.kind=card&payment.number=1");
+    assert_eq!(errors(checkout), vec![missing("payment.cvv")]);
+
+    let checkout: Result<Checkout, _> = strict("total=10&payment[number]=1");
+    assert_eq!(errors(checkout), vec![missing("payment.kind")]);
+}
@@ -0,0 +1,66 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::RawStr;
+use rocket::request::FromParam;
+
+#[derive(Debug, PartialEq, FromParam, UriDisplayPath)]
+enum Kind {
+    Rss,
+    #[param(value = "atom")]
+    Atom,
+    #[param(value = "json feed")]
+    Json,
+}
+
+#[derive(Debug, PartialEq, FromParam, UriDisplayPath)]
+struct Id(u32);
+
+#[derive(Debug, PartialEq, FromParam)]
+struct Slug<'r>(&'r RawStr);
+
+#[derive(Debug, PartialEq, FromParam)]
+struct Name(String);
+
+#[get("/feed/<kind>/<id>")]
+fn feed(kind: Kind, id: Id) -> String {
+    uri!(feed: kind, id).to_string()
+}
+
+fn parse<'a, T: FromParam<'a>>(param: &'a str) -> Result<T, T::Error> {
+    T::from_param(param.into())
+}
+
+#[test]
+fn from_param_enum() {
+    assert_eq!(parse::<Kind>("rss").unwrap(), Kind::Rss);
+    assert_eq!(parse::<Kind>("RSS").unwrap(), Kind::Rss);
+    assert_eq!(parse::<Kind>("Atom").unwrap(), Kind::Atom);
+    assert_eq!(parse::<Kind>("json%20feed").unwrap(), Kind::Json);
+
+    assert_eq!(parse::<Kind>("json").unwrap_err(), "json");
+    assert_eq!(parse::<Kind>("rss ").unwrap_err(), "rss ");
+    assert_eq!(parse::<Kind>("%FF").unwrap_err(), "%FF");
+}
+
+#[test]
+fn from_param_newtype() {
+    assert_eq!(parse::<Id>("10").unwrap(), Id(10));
+    assert_eq!(parse::<Id>("ten").unwrap_err(), "ten");
+    assert_eq!(parse::<Slug>("a%20b").unwrap(), Slug("a%20b".into()));
+    assert_eq!(parse::<Name>("a%20b").unwrap(), Name("a b".into()));
+}
+
+#[test]
+fn from_param_uri() {
+    assert_eq!(uri!(feed: Kind::Rss, Id(1)).to_string(), "/feed/Rss/1");
+    assert_eq!(uri!(feed: Kind::Json, &Id(2)).to_string(), "/feed/json%20feed/2");
+
+    use rocket::local::blocking::Client;
+
+    let client = Client::tracked(rocket::ignite().mount("/", routes![feed])).unwrap();
+    let response = client.get("/feed/JSON%20Feed/3").dispatch();
+    assert_eq!(response.into_string().unwrap(), "/feed/json%20feed/3");
+
+    let response = client.get("/feed/xml/3").dispatch();
+    assert_eq!(response.status(), rocket::http::Status::NotFound);
+}
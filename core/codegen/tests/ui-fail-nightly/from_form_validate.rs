@@ -0,0 +1 @@
+../ui-fail/from_form_validate.rs
\ No newline at end of file
@@ -0,0 +1,42 @@
+#[macro_use] extern crate rocket;
+
+#[derive(FromForm)]
+enum MissingTag {
+    A { a: String },
+    B { b: String },
+    C { c: String },
+}
+
+#[derive(FromForm)]
+#[form(tag = "kind")]
+enum TagConflict {
+    A { kind: String },
+    B { b: String },
+}
+
+#[derive(FromForm)]
+#[form(tag = "kind")]
+enum TupleVariant {
+    A { a: String },
+    B(String),
+}
+
+#[derive(FromForm)]
+#[form(tag = "kind")]
+enum DuplicateVariant {
+    A { a: String },
+    #[form(value = "a")]
+    B { b: String },
+}
+
+#[derive(FromForm)]
+#[form(tag = "kind")]
+enum SelfValidator {
+    A {
+        a: String,
+        #[field(validate = eq(&self.a))]
+        b: String,
+    },
+}
+
+fn main() { }
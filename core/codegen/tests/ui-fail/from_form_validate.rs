@@ -0,0 +1,22 @@
+#[macro_use] extern crate rocket;
+
+#[derive(FromForm)]
+struct UnknownParameter {
+    #[field(check = len(1..))]
+    name: String,
+    age: u8,
+}
+
+#[derive(FromForm)]
+struct NotACall {
+    #[field(validate = len)]
+    name: String,
+}
+
+#[derive(FromForm)]
+struct NotACall2 {
+    #[field(validate = 10)]
+    name: String,
+}
+
+fn main() { }
@@ -0,0 +1,21 @@
+#[macro_use] extern crate rocket;
+
+#[derive(FromParam)]
+enum WithFields {
+    A,
+    B(usize),
+    C,
+}
+
+#[derive(FromParam)]
+enum WithNamedFields {
+    A { a: usize },
+}
+
+#[derive(FromParam)]
+struct TwoFields(String, usize);
+
+#[derive(FromParam)]
+struct Lifetimes<'a, 'b>(&'a &'b str);
+
+fn main() { }
@@ -107,4 +107,9 @@ fn f3() {}
 #[route(120, "/")]
 fn f4() {}
 
+// Check that route concurrency limits are validated.
+
+#[get("/", concurrency = 0)]
+fn g0() {}
+
 fn main() {}
@@ -135,6 +135,13 @@ struct BamP<T>(T);
 #[derive(UriDisplayPath)]
 struct BopP(FooP);
 
+#[derive(UriDisplayPath)]
+enum KindP {
+    Rss,
+    #[param(value = "atom feed")]
+    Atom,
+}
+
 #[test]
 fn uri_display_path() {
     assert_uri_display_path!(FooP("hi"), "hi");
@@ -145,4 +152,6 @@ fn uri_display_path() {
     assert_uri_display_path!(BamP(12), "12");
     assert_uri_display_path!(BamP(BazP(&100)), "100");
     assert_uri_display_path!(BopP(FooP("bop foo")), "bop%20foo");
+    assert_uri_display_path!(KindP::Rss, "Rss");
+    assert_uri_display_path!(KindP::Atom, "atom%20feed");
 }
@@ -18,6 +18,7 @@
 #[doc(hidden)] pub use http::response::Builder as ResponseBuilder;
 #[doc(hidden)] pub use http::status::StatusCode;
 #[doc(hidden)] pub use http::uri::{Uri, Parts as UriParts};
+#[doc(hidden)] pub use http::version::Version;
 
 /// Reexported http header types.
 pub mod header {
@@ -31,6 +31,7 @@ mod docify;
 mod known_media_types;
 mod cookies;
 mod method;
+mod version;
 mod media_type;
 mod content_type;
 mod status;
@@ -39,6 +40,7 @@ mod accept;
 mod raw_str;
 mod parse;
 mod listener;
+mod proxy_protocol;
 
 /// Case-preserving, ASCII case-insensitive string types.
 ///
@@ -60,10 +62,15 @@ pub mod private {
         pub use crate::cookies::Key;
     }
 
-    pub use crate::listener::{Incoming, Listener, Connection, bind_tcp};
+    pub use crate::listener::{Incoming, Listener, Connection, RawCertificate, bind_tcp};
+    pub use crate::listener::HANDSHAKE_TIMEOUT;
+    pub use crate::listener::{Activity, InFlight, TimedListener, TimedConnection};
+    pub use crate::listener::{LimitedListener, LimitedConnection};
+    pub use crate::proxy_protocol::{ProxyProtocolListener, ProxiedStream};
 }
 
 pub use crate::method::Method;
+pub use crate::version::Version;
 pub use crate::content_type::ContentType;
 pub use crate::accept::{Accept, QMediaType};
 pub use crate::status::{Status, StatusClass};
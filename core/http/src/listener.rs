@@ -3,15 +3,21 @@ use std::future::Future;
 use std::io;
 use std::net::SocketAddr;
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
 use std::time::Duration;
 
 use hyper::server::accept::Accept;
 
 use log::{debug, error};
 
-use tokio::time::Sleep;
-use tokio::io::{AsyncRead, AsyncWrite};
+use futures::ready;
+use futures::future::FutureExt;
+use futures::stream::{FuturesUnordered, StreamExt};
+use parking_lot::Mutex;
+use tokio::sync::{AcquireError, OwnedSemaphorePermit, Semaphore};
+use tokio::time::{Instant, Sleep};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 use tokio::net::{TcpListener, TcpStream};
 
 // TODO.async: 'Listener' and 'Connection' provide common enough functionality
@@ -30,6 +36,27 @@ pub trait Listener {
 /// A 'Connection' represents an open connection to a client
 pub trait Connection: AsyncRead + AsyncWrite {
     fn remote_addr(&self) -> Option<SocketAddr>;
+
+    /// The DER-encoded X.509 certificate chain presented and verified by the
+    /// client, end-entity certificate first, if any.
+    fn peer_certificates(&self) -> Option<Vec<RawCertificate>> {
+        None
+    }
+
+    /// The server name the client requested via TLS SNI, if any.
+    fn server_name(&self) -> Option<String> {
+        None
+    }
+}
+
+/// A DER-encoded X.509 certificate.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RawCertificate(pub Vec<u8>);
+
+impl AsRef<[u8]> for RawCertificate {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
 }
 
 pin_project_lite::pin_project! {
@@ -171,3 +198,439 @@ impl Connection for TcpStream {
         self.peer_addr().ok()
     }
 }
+
+/// How long a connection's handshake, such as reading its PROXY header or
+/// negotiating TLS, may take before the connection is closed, unless a
+/// different timeout is configured.
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum number of handshakes in progress at once. Further connections
+/// wait in the OS's backlog until a handshake completes.
+const MAX_HANDSHAKES: usize = 256;
+
+type Handshake<C> = Pin<Box<dyn Future<Output = io::Result<C>> + Send>>;
+
+/// Connections accepted from a listener whose handshakes, such as reading a
+/// PROXY header or negotiating TLS, are completed concurrently so that a slow
+/// or silent client doesn't hold up those behind it.
+pub struct Handshakes<L, C> {
+    listener: L,
+    timeout: Duration,
+    pending: FuturesUnordered<Handshake<C>>,
+}
+
+impl<L: Listener, C: Send + 'static> Handshakes<L, C> {
+    /// Wraps `listener`, closing connections whose handshakes take longer
+    /// than `timeout`.
+    pub fn new(listener: L, timeout: Duration) -> Self {
+        Handshakes { listener, timeout, pending: FuturesUnordered::new() }
+    }
+
+    /// Return the actual address the inner listener bound to.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts pending connections, starting `handshake` on each, and returns
+    /// the next connection whose handshake completed. Handshakes that fail or
+    /// time out are reported as per-connection errors.
+    pub fn poll_accept<F, Fut>(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut handshake: F,
+    ) -> Poll<io::Result<C>>
+        where F: FnMut(L::Connection) -> Fut, Fut: Future<Output = io::Result<C>> + Send + 'static
+    {
+        while self.pending.len() < MAX_HANDSHAKES {
+            match self.listener.poll_accept(cx) {
+                Poll::Pending => break,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Ready(Ok(conn)) => {
+                    let fut = tokio::time::timeout(self.timeout, handshake(conn)).map(|result| {
+                        result.unwrap_or_else(|elapsed| Err(elapsed.into()))
+                            // Report a per-connection error so that accepting continues.
+                            .map_err(|e| io::Error::new(io::ErrorKind::ConnectionAborted, e))
+                    });
+
+                    self.pending.push(Box::pin(fut));
+                }
+            }
+        }
+
+        match self.pending.poll_next_unpin(cx) {
+            Poll::Ready(Some(result)) => Poll::Ready(result),
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Tracks the requests in flight on a connection so that the connection can be
+/// timed out while it's waiting for a request.
+#[derive(Clone)]
+pub struct Activity(Arc<Mutex<ActivityState>>);
+
+struct ActivityState {
+    in_flight: usize,
+    idle_since: Instant,
+    waker: Option<Waker>,
+}
+
+/// Marks a request as in flight on a connection until it is dropped.
+pub struct InFlight(Activity);
+
+impl Activity {
+    fn new() -> Self {
+        let state = ActivityState { in_flight: 0, idle_since: Instant::now(), waker: None };
+        Activity(Arc::new(Mutex::new(state)))
+    }
+
+    /// Marks a request as in flight until the returned guard is dropped.
+    pub fn begin(&self) -> InFlight {
+        self.0.lock().in_flight += 1;
+        InFlight(self.clone())
+    }
+
+    /// Returns when the connection became idle, or `None` if a request is in
+    /// flight, in which case the task in `cx` is woken when it becomes idle.
+    fn idle_since(&self, cx: &mut Context<'_>) -> Option<Instant> {
+        let mut state = self.0.lock();
+        if state.in_flight == 0 {
+            return Some(state.idle_since);
+        }
+
+        state.waker = Some(cx.waker().clone());
+        None
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        let mut state = (self.0).0.lock();
+        state.in_flight -= 1;
+        if state.in_flight == 0 {
+            state.idle_since = Instant::now();
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// A listener whose connections are closed when no request is in flight and
+/// the next request's headers aren't received within a timeout, measured from
+/// when the connection was opened or the last response was sent.
+pub struct TimedListener<L> {
+    listener: L,
+    timeout: Option<Duration>,
+}
+
+impl<L: Listener> TimedListener<L> {
+    /// Wraps `listener`. When `timeout` is `None`, connections never time out.
+    pub fn new(listener: L, timeout: Option<Duration>) -> Self {
+        TimedListener { listener, timeout }
+    }
+}
+
+impl<L: Listener> Listener for TimedListener<L> {
+    type Connection = TimedConnection<L::Connection>;
+
+    fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    fn poll_accept(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Self::Connection>> {
+        let timeout = self.timeout;
+        self.listener.poll_accept(cx).map_ok(|conn| TimedConnection {
+            conn,
+            activity: Activity::new(),
+            timer: timeout.map(|t| (t, Box::pin(tokio::time::sleep(t)))),
+        })
+    }
+}
+
+/// A connection accepted by a [`TimedListener`].
+pub struct TimedConnection<C> {
+    conn: C,
+    activity: Activity,
+    timer: Option<(Duration, Pin<Box<Sleep>>)>,
+}
+
+impl<C> TimedConnection<C> {
+    /// The activity of this connection. Requests must be marked as in flight
+    /// via [`Activity::begin()`] so that they aren't timed out.
+    pub fn activity(&self) -> &Activity {
+        &self.activity
+    }
+}
+
+impl<C: AsyncRead + Unpin> AsyncRead for TimedConnection<C> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if let Some((timeout, sleep)) = this.timer.as_mut() {
+            if let Some(idle_since) = this.activity.idle_since(cx) {
+                let deadline = idle_since + *timeout;
+                if sleep.deadline() != deadline {
+                    sleep.as_mut().reset(deadline);
+                }
+
+                if sleep.as_mut().poll(cx).is_ready() {
+                    let msg = "timed out waiting for request headers";
+                    return Poll::Ready(Err(io::Error::new(io::ErrorKind::TimedOut, msg)));
+                }
+            }
+        }
+
+        Pin::new(&mut this.conn).poll_read(cx, buf)
+    }
+}
+
+impl<C: AsyncWrite + Unpin> AsyncWrite for TimedConnection<C> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8]
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().conn).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().conn).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().conn).poll_shutdown(cx)
+    }
+}
+
+impl<C: Connection + Unpin> Connection for TimedConnection<C> {
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        self.conn.remote_addr()
+    }
+
+    fn peer_certificates(&self) -> Option<Vec<RawCertificate>> {
+        self.conn.peer_certificates()
+    }
+
+    fn server_name(&self) -> Option<String> {
+        self.conn.server_name()
+    }
+}
+
+type Acquiring = Pin<Box<dyn Future<Output = Result<OwnedSemaphorePermit, AcquireError>> + Send>>;
+
+/// A listener that stops accepting connections while a maximum number of the
+/// connections it accepted are open. Further connections wait in the OS's
+/// backlog until an open connection is closed.
+///
+/// To count connections whose handshakes are in progress, this listener must
+/// wrap the TCP listener directly, beneath any listener that performs them.
+pub struct LimitedListener<L> {
+    listener: L,
+    permits: Option<Arc<Semaphore>>,
+    acquiring: Option<Acquiring>,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl<L: Listener> LimitedListener<L> {
+    /// Wraps `listener`. When `limit` is `None`, connections are unlimited.
+    pub fn new(listener: L, limit: Option<usize>) -> Self {
+        let permits = limit.map(|n| Arc::new(Semaphore::new(n)));
+        LimitedListener { listener, permits, acquiring: None, permit: None }
+    }
+}
+
+impl<L: Listener> Listener for LimitedListener<L> {
+    type Connection = LimitedConnection<L::Connection>;
+
+    fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    fn poll_accept(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Self::Connection>> {
+        // Acquire a permit, waiting for a connection to close if necessary,
+        // before accepting the next connection.
+        if let Some(permits) = self.permits.as_ref().filter(|_| self.permit.is_none()) {
+            let acquiring = self.acquiring
+                .get_or_insert_with(|| Box::pin(permits.clone().acquire_owned()));
+
+            let permit = ready!(acquiring.as_mut().poll(cx)).expect("semaphore is never closed");
+            self.acquiring = None;
+            self.permit = Some(permit);
+        }
+
+        let conn = ready!(self.listener.poll_accept(cx))?;
+        Poll::Ready(Ok(LimitedConnection { conn, _permit: self.permit.take() }))
+    }
+}
+
+/// A connection accepted by a [`LimitedListener`], counted against its limit
+/// until it is dropped.
+pub struct LimitedConnection<C> {
+    conn: C,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl<C: AsyncRead + Unpin> AsyncRead for LimitedConnection<C> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().conn).poll_read(cx, buf)
+    }
+}
+
+impl<C: AsyncWrite + Unpin> AsyncWrite for LimitedConnection<C> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8]
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().conn).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().conn).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().conn).poll_shutdown(cx)
+    }
+}
+
+impl<C: Connection + Unpin> Connection for LimitedConnection<C> {
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        self.conn.remote_addr()
+    }
+
+    fn peer_certificates(&self) -> Option<Vec<RawCertificate>> {
+        self.conn.peer_certificates()
+    }
+
+    fn server_name(&self) -> Option<String> {
+        self.conn.server_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(fut)
+    }
+
+    /// Returns a connection accepted by a `TimedListener` with `timeout` and
+    /// the client end of the connection.
+    async fn connect(timeout: Option<Duration>) -> (TimedConnection<TcpStream>, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).await.unwrap();
+        let mut listener = TimedListener::new(listener, timeout);
+        let conn = futures::future::poll_fn(|cx| listener.poll_accept(cx)).await.unwrap();
+        (conn, client)
+    }
+
+    #[test]
+    fn test_header_timeout() {
+        block_on(async {
+            let (mut conn, mut client) = connect(Some(Duration::from_millis(50))).await;
+            client.write_all(b"GET").await.unwrap();
+            let mut buf = [0; 3];
+            conn.read_exact(&mut buf).await.unwrap();
+            let error = conn.read(&mut buf).await.unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+
+            // Connections with a request in flight don't time out. The timeout
+            // restarts once the request completes.
+            let (mut conn, _client) = connect(Some(Duration::from_millis(50))).await;
+            let in_flight = conn.activity().begin();
+            let read = tokio::time::timeout(Duration::from_millis(150), conn.read(&mut buf));
+            assert!(read.await.is_err());
+
+            drop(in_flight);
+            let error = conn.read(&mut buf).await.unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+
+            let (mut conn, _client) = connect(None).await;
+            let read = tokio::time::timeout(Duration::from_millis(150), conn.read(&mut buf));
+            assert!(read.await.is_err());
+        })
+    }
+
+    #[test]
+    fn test_handshake_timeout() {
+        block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let mut handshakes = Handshakes::new(listener, Duration::from_millis(50));
+            let _client = TcpStream::connect(addr).await.unwrap();
+
+            let never = |_: TcpStream| futures::future::pending::<io::Result<TcpStream>>();
+            let accept = futures::future::poll_fn(|cx| handshakes.poll_accept(cx, never));
+            let result = tokio::time::timeout(Duration::from_secs(1), accept).await;
+            let error = result.expect("handshake timed out").err().expect("handshake failed");
+            assert_eq!(error.kind(), io::ErrorKind::ConnectionAborted);
+        })
+    }
+
+    #[test]
+    fn test_connection_limit() {
+        block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let mut listener = LimitedListener::new(listener, Some(1));
+            let _clients = (TcpStream::connect(addr).await, TcpStream::connect(addr).await);
+
+            let first = futures::future::poll_fn(|cx| listener.poll_accept(cx)).await.unwrap();
+            let accept = futures::future::poll_fn(|cx| listener.poll_accept(cx));
+            assert!(tokio::time::timeout(Duration::from_millis(150), accept).await.is_err());
+
+            // The second connection is accepted once the first is closed.
+            drop(first);
+            let accept = futures::future::poll_fn(|cx| listener.poll_accept(cx));
+            let second = tokio::time::timeout(Duration::from_secs(1), accept).await;
+            assert!(second.expect("second connection was accepted").is_ok());
+        })
+    }
+
+    #[test]
+    fn test_connection_limit_counts_handshakes() {
+        block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let listener = LimitedListener::new(listener, Some(1));
+            let mut handshakes = Handshakes::new(listener, Duration::from_secs(5));
+
+            // The handshake completes once the client sends a byte.
+            let handshake = |mut conn: LimitedConnection<TcpStream>| async move {
+                let mut byte = [0u8; 1];
+                let result = conn.read_exact(&mut byte).await;
+                result.map(|_| conn)
+            };
+
+            let silent = TcpStream::connect(addr).await.unwrap();
+            let mut client = TcpStream::connect(addr).await.unwrap();
+            client.write_all(b"!").await.unwrap();
+
+            let accept = futures::future::poll_fn(|cx| handshakes.poll_accept(cx, handshake));
+            assert!(tokio::time::timeout(Duration::from_millis(150), accept).await.is_err());
+
+            // The second connection is accepted once the first's handshake fails.
+            drop(silent);
+            let accept = futures::future::poll_fn(|cx| handshakes.poll_accept(cx, handshake));
+            let result = tokio::time::timeout(Duration::from_secs(1), accept).await;
+            let error = result.expect("first handshake failed").err().expect("handshake failed");
+            assert_eq!(error.kind(), io::ErrorKind::ConnectionAborted);
+
+            let accept = futures::future::poll_fn(|cx| handshakes.poll_accept(cx, handshake));
+            let second = tokio::time::timeout(Duration::from_secs(1), accept).await;
+            assert!(second.expect("second connection was accepted").is_ok());
+        })
+    }
+}
@@ -0,0 +1,303 @@
+//! Decoding of PROXY protocol v1 and v2 headers.
+//!
+//! A load balancer speaking the PROXY protocol prefixes each connection with a
+//! header describing the original connection's source and destination. See
+//! <https://www.haproxy.org/download/2.3/doc/proxy-protocol.txt>.
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf};
+
+use crate::listener::{Connection, Listener, RawCertificate, Handshakes};
+
+/// The signature that begins every v1 header.
+const V1_PREFIX: &[u8] = b"PROXY ";
+
+/// The signature that begins every v2 header.
+const V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+
+/// The maximum length of a v1 header, including the trailing CRLF.
+const V1_MAX_LEN: usize = 107;
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid PROXY header: {}", msg))
+}
+
+/// Reads and decodes a PROXY protocol v1 or v2 header from `stream`, reading
+/// no further than the end of the header. Returns the source address of the
+/// proxied connection, or `None` if the proxy reported no address, as it does
+/// for health checks and unknown or non-IP transports.
+pub async fn read_header<S: AsyncRead + Unpin>(stream: &mut S) -> io::Result<Option<SocketAddr>> {
+    let mut prefix = [0u8; 6];
+    stream.read_exact(&mut prefix).await?;
+    if prefix == V1_PREFIX {
+        let mut line = prefix.to_vec();
+        while !line.ends_with(b"\r\n") {
+            if line.len() >= V1_MAX_LEN {
+                return Err(invalid("v1 header is too long"));
+            }
+
+            line.push(stream.read_u8().await?);
+        }
+
+        return parse_v1(&line[V1_PREFIX.len()..(line.len() - 2)]);
+    }
+
+    let mut header = [0u8; 16];
+    header[..6].copy_from_slice(&prefix);
+    stream.read_exact(&mut header[6..]).await?;
+    if &header[..12] != V2_SIGNATURE {
+        return Err(invalid("missing signature"));
+    }
+
+    let len = u16::from_be_bytes([header[14], header[15]]) as usize;
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload).await?;
+    parse_v2(header[12], header[13], &payload)
+}
+
+/// Parses the space-separated fields of a v1 header.
+fn parse_v1(line: &[u8]) -> io::Result<Option<SocketAddr>> {
+    let line = std::str::from_utf8(line).map_err(|_| invalid("v1 header isn't ASCII"))?;
+    let mut fields = line.split(' ');
+    let is_v6 = match fields.next() {
+        Some("TCP4") => false,
+        Some("TCP6") => true,
+        Some("UNKNOWN") => return Ok(None),
+        _ => return Err(invalid("unknown v1 protocol")),
+    };
+
+    let mut next = || fields.next().ok_or_else(|| invalid("missing v1 field"));
+    let (src, _dst, src_port, _dst_port) = (next()?, next()?, next()?, next()?);
+    let ip: IpAddr = match is_v6 {
+        false => src.parse::<Ipv4Addr>().map_err(|_| invalid("bad IPv4 address"))?.into(),
+        true => src.parse::<Ipv6Addr>().map_err(|_| invalid("bad IPv6 address"))?.into(),
+    };
+
+    let port = src_port.parse::<u16>().map_err(|_| invalid("bad v1 port"))?;
+    Ok(Some(SocketAddr::new(ip, port)))
+}
+
+/// Parses a v2 header given its version/command byte, its address family and
+/// transport byte, and its payload, which may end with ignored TLVs.
+fn parse_v2(ver_cmd: u8, family: u8, payload: &[u8]) -> io::Result<Option<SocketAddr>> {
+    if ver_cmd >> 4 != 2 {
+        return Err(invalid("unsupported version"));
+    }
+
+    match ver_cmd & 0x0F {
+        // LOCAL: the proxy's own connection, such as a health check.
+        0x0 => return Ok(None),
+        0x1 => { /* PROXY */ }
+        _ => return Err(invalid("unknown v2 command")),
+    }
+
+    let port = |bytes: &[u8]| u16::from_be_bytes([bytes[0], bytes[1]]);
+    match family >> 4 {
+        // AF_INET: 4-byte source and destination addresses, then ports.
+        0x1 if payload.len() >= 12 => {
+            let ip = Ipv4Addr::new(payload[0], payload[1], payload[2], payload[3]);
+            Ok(Some(SocketAddr::new(ip.into(), port(&payload[8..]))))
+        }
+        // AF_INET6: 16-byte source and destination addresses, then ports.
+        0x2 if payload.len() >= 36 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(&payload[..16]);
+            Ok(Some(SocketAddr::new(Ipv6Addr::from(octets).into(), port(&payload[32..]))))
+        }
+        0x1 | 0x2 => Err(invalid("v2 address block is too short")),
+        // AF_UNSPEC or AF_UNIX: no usable IP address.
+        _ => Ok(None),
+    }
+}
+
+/// Reads the PROXY header from `stream`, returning a [`ProxiedStream`] that
+/// reports the source address in the header as its remote address. Errors if
+/// the header is missing or malformed.
+pub async fn accept<S: AsyncRead + Unpin>(mut stream: S) -> io::Result<ProxiedStream<S>> {
+    let remote = read_header(&mut stream).await?;
+    Ok(ProxiedStream { stream, remote })
+}
+
+/// A stream whose remote address was reported in a PROXY protocol header.
+pub struct ProxiedStream<S> {
+    stream: S,
+    remote: Option<SocketAddr>,
+}
+
+impl<S> ProxiedStream<S> {
+    /// Wraps `stream`, reporting its own remote address. Used when the PROXY
+    /// protocol is disabled.
+    pub fn direct(stream: S) -> Self {
+        ProxiedStream { stream, remote: None }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for ProxiedStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for ProxiedStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8]
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+    }
+}
+
+impl<S: Connection + Unpin> Connection for ProxiedStream<S> {
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        self.remote.or_else(|| self.stream.remote_addr())
+    }
+
+    fn peer_certificates(&self) -> Option<Vec<RawCertificate>> {
+        self.stream.peer_certificates()
+    }
+
+    fn server_name(&self) -> Option<String> {
+        self.stream.server_name()
+    }
+}
+
+/// A listener whose connections must begin with a PROXY protocol header.
+pub struct ProxyProtocolListener<L: Listener> {
+    handshakes: Handshakes<L, ProxiedStream<L::Connection>>,
+}
+
+impl<L: Listener> ProxyProtocolListener<L> where L::Connection: Send + 'static {
+    /// Wraps `listener` so that every accepted connection's PROXY header is
+    /// decoded before the connection is yielded. Connections whose headers
+    /// aren't received within `timeout` are closed.
+    pub fn new(listener: L, timeout: Duration) -> Self {
+        ProxyProtocolListener { handshakes: Handshakes::new(listener, timeout) }
+    }
+}
+
+impl<L: Listener> Listener for ProxyProtocolListener<L>
+    where L::Connection: Unpin + Send + 'static
+{
+    type Connection = ProxiedStream<L::Connection>;
+
+    fn local_addr(&self) -> Option<SocketAddr> {
+        self.handshakes.local_addr()
+    }
+
+    fn poll_accept(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Self::Connection>> {
+        self.handshakes.poll_accept(cx, accept)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::listener::HANDSHAKE_TIMEOUT;
+
+    fn read(bytes: &[u8]) -> io::Result<Option<SocketAddr>> {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let mut reader = bytes;
+        let result = runtime.block_on(read_header(&mut reader));
+        if result.is_ok() {
+            assert_eq!(reader, b"rest", "read past the end of the header");
+        }
+
+        result
+    }
+
+    fn addr(s: &str) -> Option<SocketAddr> {
+        Some(s.parse().unwrap())
+    }
+
+    #[test]
+    fn test_v1() {
+        assert_eq!(read(b"PROXY TCP4 1.2.3.4 5.6.7.8 1000 80\r\nrest").unwrap(),
+            addr("1.2.3.4:1000"));
+
+        assert_eq!(read(b"PROXY TCP6 2001:db8::1 ::1 1000 443\r\nrest").unwrap(),
+            addr("[2001:db8::1]:1000"));
+
+        assert_eq!(read(b"PROXY UNKNOWN\r\nrest").unwrap(), None);
+        assert_eq!(read(b"PROXY UNKNOWN ffff::1 ::1 1 2\r\nrest").unwrap(), None);
+
+        assert!(read(b"PROXY TCP4 1.2.3.4 5.6.7.8 1000\r\nrest").is_err());
+        assert!(read(b"PROXY TCP4 2001:db8::1 ::1 1000 80\r\nrest").is_err());
+        assert!(read(b"PROXY TCP4 1.2.3.4 5.6.7.8 100000 80\r\nrest").is_err());
+        assert!(read(b"PROXY SCTP 1.2.3.4 5.6.7.8 1000 80\r\nrest").is_err());
+        assert!(read(&[&b"PROXY UNKNOWN "[..], &[b'a'; 128]].concat()).is_err());
+        assert!(read(b"GET / HTTP/1.1\r\n\r\n").is_err());
+    }
+
+    #[test]
+    fn test_v2() {
+        let v2 = |cmd: u8, family: u8, payload: &[u8]| {
+            let len = (payload.len() as u16).to_be_bytes();
+            [V2_SIGNATURE, &[cmd, family], &len, payload, b"rest"].concat()
+        };
+
+        let v4 = [1, 2, 3, 4, 5, 6, 7, 8, 0x03, 0xE8, 0, 80];
+        assert_eq!(read(&v2(0x21, 0x11, &v4)).unwrap(), addr("1.2.3.4:1000"));
+
+        // Trailing TLVs are ignored.
+        let tlvs = [&v4[..], &[0x04, 0x00, 0x01, 0xFF]].concat();
+        assert_eq!(read(&v2(0x21, 0x11, &tlvs)).unwrap(), addr("1.2.3.4:1000"));
+
+        let mut v6 = vec![0x20, 0x01, 0x0d, 0xb8];
+        v6.extend_from_slice(&[0; 11]);
+        v6.push(1);
+        v6.extend_from_slice(&[0; 16]);
+        v6.extend_from_slice(&[0x03, 0xE8, 0x01, 0xBB]);
+        assert_eq!(read(&v2(0x21, 0x21, &v6)).unwrap(), addr("[2001:db8::1]:1000"));
+
+        assert_eq!(read(&v2(0x20, 0x11, &v4)).unwrap(), None);
+        assert_eq!(read(&v2(0x21, 0x00, &[])).unwrap(), None);
+        assert_eq!(read(&v2(0x21, 0x31, &[0; 216])).unwrap(), None);
+
+        assert!(read(&v2(0x11, 0x11, &v4)).is_err());
+        assert!(read(&v2(0x22, 0x11, &v4)).is_err());
+        assert!(read(&v2(0x21, 0x11, &v4[..8])).is_err());
+        assert!(read(&v2(0x21, 0x21, &v4)).is_err());
+    }
+
+    #[test]
+    fn test_silent_client_does_not_block() {
+        use tokio::io::AsyncWriteExt;
+        use tokio::net::TcpStream;
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let local = listener.local_addr().unwrap();
+            let mut listener = ProxyProtocolListener::new(listener, HANDSHAKE_TIMEOUT);
+
+            let _silent = TcpStream::connect(local).await.unwrap();
+            let mut client = TcpStream::connect(local).await.unwrap();
+            client.write_all(b"PROXY TCP4 1.2.3.4 5.6.7.8 1000 80\r\n").await.unwrap();
+
+            let accept = futures::future::poll_fn(|cx| listener.poll_accept(cx));
+            let stream = tokio::time::timeout(Duration::from_secs(1), accept).await
+                .expect("silent client blocked accept")
+                .expect("accepted connection");
+
+            assert_eq!(stream.remote_addr(), addr("1.2.3.4:1000"));
+        });
+    }
+}
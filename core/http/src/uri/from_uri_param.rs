@@ -72,7 +72,9 @@ use crate::uri::{self, UriPart, UriDisplay};
 ///
 ///    * `String`, `i8`, `i16`, `i32`, `i64`, `i128`, `isize`, `u8`, `u16`,
 ///      `u32`, `u64`, `u128`, `usize`, `f32`, `f64`, `bool`, `IpAddr`,
-///      `Ipv4Addr`, `Ipv6Addr`, `&str`, `&RawStr`, `Cow<str>`
+///      `Ipv4Addr`, `Ipv6Addr`, `IpNet`, `Ipv4Net`, `Ipv6Net`,
+///      `time::Date`, `time::Time`, `time::PrimitiveDateTime`,
+///      `std::time::Duration`, `time::Duration`, `&str`, `&RawStr`, `Cow<str>`
 ///
 /// The following types have _identity_ implementations _only in [`Path`]_:
 ///
@@ -210,6 +212,7 @@ pub trait FromUriParam<P: UriPart, T> {
 }
 
 use std::{borrow::Cow, net::{IpAddr, Ipv4Addr, Ipv6Addr}};
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
 
 #[doc(hidden)]
 #[macro_export(local_inner_macros)]
@@ -301,7 +304,10 @@ impl_from_uri_param_identity! {
     i8, i16, i32, i64, i128, isize,
     u8, u16, u32, u64, u128, usize,
     f32, f64, bool,
-    IpAddr, Ipv4Addr, Ipv6Addr
+    IpAddr, Ipv4Addr, Ipv6Addr,
+    IpNet, Ipv4Net, Ipv6Net,
+    time::Date, time::Time, time::PrimitiveDateTime,
+    std::time::Duration, time::Duration
 }
 
 impl_from_uri_param_identity! {
@@ -123,6 +123,23 @@ use crate::uri::{Uri, UriPart, Path, Query, Formatter};
 ///
 ///     The string is percent encoded.
 ///
+///   * **`IpNet`, `Ipv4Net`, `Ipv6Net`**
+///
+///     The network is written in CIDR notation, as in `10.0.0.0/8`, and
+///     percent encoded.
+///
+///   * **`time::Date`, `time::Time`, `time::PrimitiveDateTime`**
+///
+///     The value is written in the format of an HTML `date`, `time`, or
+///     `datetime-local` input, respectively: `2021-03-04`, `10:30`, and
+///     `2021-03-04T10:30`. Seconds and fractions of a second are written only
+///     when they are nonzero, as in `10:30:15.5`.
+///
+///   * **`std::time::Duration`, `time::Duration`**
+///
+///     The duration is written as a number of seconds, with a fractional part
+///     only when it is nonzero, as in `90` or `1.5`.
+///
 ///   * **`&T`, `&mut T`** _where_ **`T: UriDisplay`**
 ///
 ///     Uses the implementation of `UriDisplay` for `T`.
@@ -350,9 +367,97 @@ impl_with_display! {
     IpAddr, Ipv4Addr, Ipv6Addr
 }
 
+/// Writes the fractional part of a second, if any, as in `.5`.
+fn write_fraction<P: UriPart>(f: &mut Formatter<'_, P>, nanos: u32) -> fmt::Result {
+    use std::fmt::Write;
+
+    if nanos == 0 {
+        return Ok(());
+    }
+
+    let fraction = format!("{:09}", nanos);
+    write!(f, ".{}", fraction.trim_end_matches('0'))
+}
+
+/// Writes the date as `YYYY-MM-DD`, the format of an HTML `date` input.
+impl<P: UriPart> UriDisplay<P> for time::Date {
+    fn fmt(&self, f: &mut Formatter<'_, P>) -> fmt::Result {
+        use std::fmt::Write;
+        write!(f, "{:04}-{:02}-{:02}", self.year(), self.month(), self.day())
+    }
+}
+
+/// Writes the time as `HH:MM[:SS[.FFF]]`, the format of an HTML `time` input.
+/// Seconds are written only if they are nonzero.
+impl<P: UriPart> UriDisplay<P> for time::Time {
+    fn fmt(&self, f: &mut Formatter<'_, P>) -> fmt::Result {
+        use std::fmt::Write;
+
+        write!(f, "{:02}:{:02}", self.hour(), self.minute())?;
+        if self.second() != 0 || self.nanosecond() != 0 {
+            write!(f, ":{:02}", self.second())?;
+        }
+
+        write_fraction(f, self.nanosecond())
+    }
+}
+
+/// Writes the date and time as `YYYY-MM-DDTHH:MM[:SS[.FFF]]`, the format of an
+/// HTML `datetime-local` input.
+impl<P: UriPart> UriDisplay<P> for time::PrimitiveDateTime {
+    fn fmt(&self, f: &mut Formatter<'_, P>) -> fmt::Result {
+        use std::fmt::Write;
+
+        UriDisplay::fmt(&self.date(), f)?;
+        f.write_char('T')?;
+        UriDisplay::fmt(&self.time(), f)
+    }
+}
+
+/// Writes the duration as a number of seconds, as in `90` or `1.5`.
+impl<P: UriPart> UriDisplay<P> for std::time::Duration {
+    fn fmt(&self, f: &mut Formatter<'_, P>) -> fmt::Result {
+        use std::fmt::Write;
+
+        write!(f, "{}", self.as_secs())?;
+        write_fraction(f, self.subsec_nanos())
+    }
+}
+
+/// Writes the duration as a number of seconds, as in `-90` or `1.5`.
+impl<P: UriPart> UriDisplay<P> for time::Duration {
+    fn fmt(&self, f: &mut Formatter<'_, P>) -> fmt::Result {
+        use std::fmt::Write;
+
+        let (secs, nanos) = (self.whole_seconds(), self.subsec_nanoseconds());
+        if secs == 0 && nanos < 0 {
+            f.write_char('-')?;
+        }
+
+        write!(f, "{}", secs)?;
+        write_fraction(f, nanos.abs() as u32)
+    }
+}
+
 // These are second level implementations: they all defer to an existing
 // implementation.
 
+macro_rules! impl_with_encoded_display {
+    ($($T:ty),+) => {$(
+        /// Percent-encodes the `Display` implementation.
+        impl<P: UriPart> UriDisplay<P> for $T  {
+            #[inline(always)]
+            fn fmt(&self, f: &mut Formatter<'_, P>) -> fmt::Result {
+                self.to_string().as_str().fmt(f)
+            }
+        }
+    )+}
+}
+
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
+
+impl_with_encoded_display!(IpNet, Ipv4Net, Ipv6Net);
+
 /// Percent-encodes the raw string. Defers to `str`.
 impl<P: UriPart> UriDisplay<P> for RawStr {
     #[inline(always)]
@@ -542,6 +647,33 @@ mod uri_display_tests {
         assert_display!(<Path, path::PathBuf> &"hi there", "hi%20there");
     }
 
+    #[test]
+    fn time_and_networks() {
+        use time::{Date, Time, PrimitiveDateTime, Duration};
+
+        let date = Date::try_from_ymd(2021, 3, 4).unwrap();
+        let time = Time::try_from_hms(9, 5, 0).unwrap();
+        let precise = Time::try_from_hms_milli(9, 5, 7, 250).unwrap();
+
+        assert_display!(<Path, Date> date, "2021-03-04");
+        assert_display!(<Query, Date> &date, "2021-03-04");
+        assert_display!(<Path, Time> time, "09:05");
+        assert_display!(<Path, Time> precise, "09:05:07.25");
+        assert_display!(<Path, PrimitiveDateTime> PrimitiveDateTime::new(date, time),
+            "2021-03-04T09:05");
+        assert_display!(<Query, Option<PrimitiveDateTime>> Some(date.with_time(precise)),
+            "2021-03-04T09:05:07.25");
+
+        assert_display!(<Path, std::time::Duration> std::time::Duration::from_secs(90), "90");
+        assert_display!(<Path, std::time::Duration> std::time::Duration::from_millis(1500), "1.5");
+        assert_display!(<Query, Duration> Duration::milliseconds(-1500), "-1.5");
+        assert_display!(<Query, Duration> Duration::milliseconds(-500), "-0.5");
+
+        let net: ipnet::Ipv4Net = "10.0.0.0/8".parse().unwrap();
+        assert_display!(<Path, ipnet::Ipv4Net> net, "10.0.0.0%2F8");
+        assert_display!(<Query, ipnet::IpNet> ipnet::IpNet::from(net), "10.0.0.0%2F8");
+    }
+
     struct Wrapper<T>(T);
 
     impl<A, T: FromUriParam<Query, A>> FromUriParam<Query, A> for Wrapper<T> {
@@ -0,0 +1,75 @@
+use std::fmt;
+
+use self::Version::*;
+
+/// Representation of HTTP protocol versions.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Version {
+    Http09,
+    Http10,
+    Http11,
+    Http2,
+    Http3,
+}
+
+impl Version {
+    /// WARNING: This is unstable! Do not use this method outside of Rocket!
+    #[doc(hidden)]
+    pub fn from_hyp(version: http::version::Version) -> Option<Version> {
+        match version {
+            http::version::Version::HTTP_09 => Some(Http09),
+            http::version::Version::HTTP_10 => Some(Http10),
+            http::version::Version::HTTP_11 => Some(Http11),
+            http::version::Version::HTTP_2 => Some(Http2),
+            http::version::Version::HTTP_3 => Some(Http3),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `self` is a multiplexing version of HTTP, that is,
+    /// HTTP/2 or later.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Version;
+    ///
+    /// assert!(!Version::Http11.is_multiplexed());
+    /// assert!(Version::Http2.is_multiplexed());
+    /// ```
+    #[inline]
+    pub fn is_multiplexed(self) -> bool {
+        self >= Http2
+    }
+
+    /// Returns the string representation of `self` as it appears in an
+    /// HTTP/1 request line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Version;
+    ///
+    /// assert_eq!(Version::Http11.as_str(), "HTTP/1.1");
+    /// assert_eq!(Version::Http2.as_str(), "HTTP/2");
+    /// ```
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Http09 => "HTTP/0.9",
+            Http10 => "HTTP/1.0",
+            Http11 => "HTTP/1.1",
+            Http2 => "HTTP/2",
+            Http3 => "HTTP/3",
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    #[inline(always)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
@@ -23,6 +23,10 @@ pub struct StaticRouteInfo {
     pub handler: StaticHandler,
     /// The route's rank, if any.
     pub rank: Option<isize>,
+    /// The route's handler timeout in seconds, if any.
+    pub timeout: Option<u32>,
+    /// The route's concurrency limit, if any.
+    pub concurrency: Option<usize>,
 }
 
 /// Information generated by the `catch` attribute during codegen.
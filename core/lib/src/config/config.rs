@@ -1,12 +1,15 @@
 use std::net::{IpAddr, Ipv4Addr};
 
+use ipnet::IpNet;
+
 use figment::{Figment, Profile, Provider, Metadata, error::Result};
 use figment::providers::{Serialized, Env, Toml, Format};
 use figment::value::{Map, Dict};
 use serde::{Deserialize, Serialize};
 use yansi::Paint;
 
-use crate::config::{SecretKey, TlsConfig, LogLevel};
+use crate::config::{SecretKey, TlsConfig, Http2Config, Timeouts, LogLevel, LogFormat};
+use crate::config::ProxyHeader;
 use crate::data::Limits;
 
 /// Rocket server configuration.
@@ -53,23 +56,65 @@ use crate::data::Limits;
 ///     the "default" meta-profile.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Config {
-    /// IP address to serve on. **(default: `127.0.0.1`)**
+    /// IP address to serve on. Once launched, this is the address the server
+    /// actually bound to. **(default: `127.0.0.1`)**
     pub address: IpAddr,
-    /// Port to serve on. **(default: `8000`)**
+    /// Port to serve on. Once launched, this is the port the server actually
+    /// bound to, which may differ if `0` was configured. **(default: `8000`)**
     pub port: u16,
     /// Number of future-executing threads. **(default: `num cores`)**
     pub workers: usize,
     /// Keep-alive timeout in seconds; disabled when `0`. **(default: `5`)**
     pub keep_alive: u32,
+    /// Maximum number of open connections, if any, including those whose
+    /// handshakes are in progress. While the limit is reached, no new
+    /// connections are accepted. Must be nonzero. **(default: `None`)**
+    #[serde(default, deserialize_with = "deserialize_limit")]
+    pub max_connections: Option<usize>,
+    /// Maximum number of requests handled at once, if any. Requests beyond the
+    /// limit are rejected with a `503 Service Unavailable`. Must be nonzero.
+    /// **(default: `None`)**
+    #[serde(default, deserialize_with = "deserialize_limit")]
+    pub max_concurrent_requests: Option<usize>,
     /// Max level to log. **(default: _debug_ `normal` / _release_ `critical`)**
     pub log_level: LogLevel,
+    /// Format of log messages: `pretty` or `json`. **(default: `pretty`)**
+    pub log_format: LogFormat,
     /// Whether to use colors and emoji when logging. **(default: `true`)**
     #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
     pub cli_colors: bool,
     /// The secret key for signing and encrypting. **(default: `0`)**
     pub secret_key: SecretKey,
+    /// Networks of proxies trusted to report the client's address, protocol,
+    /// and host via the `proxy_header`. Single addresses may be given without a
+    /// prefix length. **(default: `[]`)**
+    ///
+    /// See [`Request::client_ip()`](crate::Request::client_ip()) for details.
+    #[serde(deserialize_with = "deserialize_ip_nets")]
+    pub trusted_proxies: Vec<IpNet>,
+    /// The header in which trusted proxies report the client's address,
+    /// protocol, and host. Any other forwarding header is ignored.
+    /// **(default: `x-forwarded`)**
+    pub proxy_header: ProxyHeader,
+    /// Whether every connection begins with a PROXY protocol v1 or v2 header
+    /// whose source address becomes the connection's remote address. Only
+    /// enable this behind a proxy that sends the header, as connections
+    /// without one are closed. **(default: `false`)**
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub proxy_protocol: bool,
+    /// Header from which a request's ID is accepted, if it's present and
+    /// valid, and in which it's echoed in responses.
+    /// **(default: `X-Request-Id`)**
+    ///
+    /// See [`RequestId`](crate::request::RequestId) for details.
+    #[serde(deserialize_with = "deserialize_header_name")]
+    pub request_id_header: String,
     /// The TLS configuration, if any. **(default: `None`)**
     pub tls: Option<TlsConfig>,
+    /// HTTP/2 configuration. **(default: [`Http2Config::default()`])**
+    pub http2: Http2Config,
+    /// Request timeouts. **(default: [`Timeouts::default()`])**
+    pub timeouts: Timeouts,
     /// Streaming read size limits. **(default: [`Limits::default()`])**
     pub limits: Limits,
     /// Whether `ctrl-c` initiates a server shutdown. **(default: `true`)**
@@ -136,10 +181,19 @@ impl Config {
             port: 8000,
             workers: num_cpus::get(),
             keep_alive: 5,
+            max_connections: None,
+            max_concurrent_requests: None,
             log_level: LogLevel::Normal,
+            log_format: LogFormat::Pretty,
             cli_colors: true,
             secret_key: SecretKey::zero(),
+            trusted_proxies: vec![],
+            proxy_header: ProxyHeader::XForwarded,
+            proxy_protocol: false,
+            request_id_header: "X-Request-Id".into(),
             tls: None,
+            http2: Http2Config::default(),
+            timeouts: Timeouts::default(),
             limits: Limits::default(),
             ctrlc: true,
         }
@@ -230,7 +284,7 @@ impl Config {
         #[cfg(all(feature = "secrets", not(test), not(rocket_unsafe_secret_key)))]
         if !config.secret_key.is_provided() {
             if figment.profile() != Self::DEBUG_PROFILE {
-                crate::logger::try_init(LogLevel::Debug, true, false);
+                crate::logger::try_init(LogLevel::Debug, LogFormat::Pretty, true, false);
                 error!("secrets enabled in non-`debug` without `secret_key`");
                 info_!("disable `secrets` feature or configure a `secret_key`");
                 panic!("aborting due to configuration error(s)")
@@ -271,6 +325,7 @@ impl Config {
         launch_info_!("port: {}", Paint::default(&self.port).bold());
         launch_info_!("workers: {}", Paint::default(self.workers).bold());
         launch_info_!("log level: {}", Paint::default(self.log_level).bold());
+        launch_info_!("log format: {}", Paint::default(self.log_format).bold());
         launch_info_!("secret key: {:?}", Paint::default(&self.secret_key).bold());
         launch_info_!("limits: {}", Paint::default(&self.limits).bold());
         launch_info_!("cli colors: {}", Paint::default(&self.cli_colors).bold());
@@ -282,11 +337,31 @@ impl Config {
             launch_info_!("keep-alive: {}", Paint::default("disabled").bold());
         }
 
+        let limit = |max: Option<usize>| max.map_or("unlimited".into(), |n| n.to_string());
+        launch_info_!("max connections: {}", Paint::default(limit(self.max_connections)).bold());
+        launch_info_!("max concurrent requests: {}",
+            Paint::default(limit(self.max_concurrent_requests)).bold());
+
+        match self.trusted_proxies.is_empty() {
+            true => launch_info_!("trusted proxies: {}", Paint::default("none").bold()),
+            false => {
+                let proxies: Vec<_> = self.trusted_proxies.iter().map(|n| n.to_string()).collect();
+                launch_info_!("trusted proxies: {}", Paint::default(proxies.join(", ")).bold());
+            }
+        }
+
+        launch_info_!("proxy header: {}", Paint::default(self.proxy_header).bold());
+        launch_info_!("proxy protocol: {}", Paint::default(self.proxy_protocol).bold());
+        launch_info_!("request id header: {}", Paint::default(&self.request_id_header).bold());
+
         match self.tls_enabled() {
             true => launch_info_!("tls: {}", Paint::default("enabled").bold()),
             false => launch_info_!("tls: {}", Paint::default("disabled").bold()),
         }
 
+        launch_info_!("http/2: {}", Paint::default(&self.http2).bold());
+        launch_info_!("timeouts: {}", Paint::default(&self.timeouts).bold());
+
         #[cfg(all(feature = "secrets", not(test), not(rocket_unsafe_secret_key)))]
         if !self.secret_key.is_provided() {
             warn!("secrets enabled without a configured `secret_key`");
@@ -350,7 +425,7 @@ impl Provider for Config {
 pub fn pretty_print_error(error: figment::Error) {
     use figment::error::{Kind, OneOf};
 
-    crate::logger::try_init(LogLevel::Debug, true, false);
+    crate::logger::try_init(LogLevel::Debug, LogFormat::Pretty, true, false);
 
     for e in error {
         fn w<T: std::fmt::Display>(v: T) -> Paint<T> { Paint::white(v) }
@@ -408,3 +483,46 @@ pub fn pretty_print_error(error: figment::Error) {
         }
     }
 }
+
+/// Deserializes an optional limit, which must be nonzero if present.
+fn deserialize_limit<'de, D>(de: D) -> std::result::Result<Option<usize>, D::Error>
+    where D: serde::Deserializer<'de>
+{
+    use serde::de::{Error, Unexpected};
+
+    match Option::<usize>::deserialize(de)? {
+        Some(0) => Err(D::Error::invalid_value(Unexpected::Unsigned(0), &"a nonzero limit")),
+        limit => Ok(limit),
+    }
+}
+
+/// Deserializes a string that is a valid HTTP header name.
+fn deserialize_header_name<'de, D>(de: D) -> std::result::Result<String, D::Error>
+    where D: serde::Deserializer<'de>
+{
+    use serde::de::{Error, Unexpected};
+    use crate::http::hyper::HeaderName;
+
+    let name = String::deserialize(de)?;
+    match HeaderName::from_bytes(name.as_bytes()) {
+        Ok(_) => Ok(name),
+        Err(_) => Err(D::Error::invalid_value(Unexpected::Str(&name), &"an HTTP header name")),
+    }
+}
+
+/// Deserializes a sequence of IP networks in CIDR notation or bare IP
+/// addresses, the latter as single-address networks.
+fn deserialize_ip_nets<'de, D>(de: D) -> std::result::Result<Vec<IpNet>, D::Error>
+    where D: serde::Deserializer<'de>
+{
+    use serde::de::{Error, Unexpected};
+
+    Vec::<String>::deserialize(de)?
+        .iter()
+        .map(|s| s.parse::<IpNet>()
+            .or_else(|_| s.parse::<IpAddr>().map(IpNet::from))
+            .map_err(|_| {
+                D::Error::invalid_value(Unexpected::Str(s), &"an IP address or network")
+            }))
+        .collect()
+}
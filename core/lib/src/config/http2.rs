@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+/// HTTP/2 configuration.
+///
+/// When enabled, HTTP/2 is negotiated via ALPN (`h2`) on TLS connections.
+/// Plaintext connections are HTTP/1 only unless `h2c` is set, in which case
+/// clients may additionally speak HTTP/2 with prior knowledge.
+///
+/// The `max_concurrent_streams`, `initial_stream_window_size`, and
+/// `initial_connection_window_size` parameters are passed through to the
+/// HTTP/2 implementation. When unset, the implementation's defaults apply.
+///
+/// ```rust
+/// # use rocket::figment::Figment;
+/// let figment = Figment::from(rocket::Config::default())
+///     .merge(("http2.h2c", true))
+///     .merge(("http2.max_concurrent_streams", 128));
+///
+/// let config = rocket::Config::from(figment);
+/// assert!(config.http2.enabled);
+/// assert!(config.http2.h2c);
+/// assert_eq!(config.http2.max_concurrent_streams, Some(128));
+/// ```
+#[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+pub struct Http2Config {
+    /// Whether HTTP/2 is enabled at all. **(default: `true`)**
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub enabled: bool,
+    /// Whether to accept prior-knowledge HTTP/2 over plaintext connections.
+    /// **(default: `false`)**
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub h2c: bool,
+    /// Maximum number of concurrent streams per connection.
+    /// **(default: `None`)**
+    pub max_concurrent_streams: Option<u32>,
+    /// Initial flow-control window size, in bytes, for each stream.
+    /// **(default: `None`)**
+    pub initial_stream_window_size: Option<u32>,
+    /// Initial flow-control window size, in bytes, for each connection.
+    /// **(default: `None`)**
+    pub initial_connection_window_size: Option<u32>,
+    /// Whether to use an adaptive flow-control window based on BDP. When
+    /// enabled, the configured window sizes are ignored.
+    /// **(default: `false`)**
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub adaptive_window: bool,
+}
+
+impl Default for Http2Config {
+    fn default() -> Self {
+        Http2Config {
+            enabled: true,
+            h2c: false,
+            max_concurrent_streams: None,
+            initial_stream_window_size: None,
+            initial_connection_window_size: None,
+            adaptive_window: false,
+        }
+    }
+}
+
+impl Http2Config {
+    /// The ALPN protocols to advertise on TLS connections, in order of
+    /// preference.
+    #[cfg(feature = "tls")]
+    pub(crate) fn alpn_protocols(&self) -> Vec<Vec<u8>> {
+        match self.enabled {
+            true => vec![b"h2".to_vec(), b"http/1.1".to_vec()],
+            false => vec![b"http/1.1".to_vec()],
+        }
+    }
+
+    /// Whether the server must only speak HTTP/1, given whether connections
+    /// are over TLS.
+    pub(crate) fn http1_only(&self, tls: bool) -> bool {
+        !self.enabled || (!tls && !self.h2c)
+    }
+}
+
+impl std::fmt::Display for Http2Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.enabled {
+            return write!(f, "disabled");
+        }
+
+        write!(f, "enabled")?;
+        if self.h2c {
+            write!(f, ", h2c")?;
+        }
+
+        if let Some(n) = self.max_concurrent_streams {
+            write!(f, ", max streams = {}", n)?;
+        }
+
+        if self.adaptive_window {
+            write!(f, ", adaptive window")?;
+        } else {
+            if let Some(n) = self.initial_stream_window_size {
+                write!(f, ", stream window = {}", n)?;
+            }
+
+            if let Some(n) = self.initial_connection_window_size {
+                write!(f, ", connection window = {}", n)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -112,21 +112,27 @@
 mod secret_key;
 mod config;
 mod tls;
+mod http2;
+mod timeouts;
 
 #[doc(hidden)] pub use config::pretty_print_error;
 
 pub use config::Config;
-pub use crate::logger::LogLevel;
+pub use crate::logger::{LogLevel, LogFormat};
 pub use secret_key::SecretKey;
-pub use tls::TlsConfig;
+pub use tls::{TlsConfig, MutualTls, SniCertificate, CipherSuite, TlsVersion};
+pub use http2::Http2Config;
+pub use timeouts::Timeouts;
+pub use crate::request::forwarded::ProxyHeader;
+#[doc(inline)] pub use ipnet::IpNet;
 
 #[cfg(test)]
 mod tests {
     use std::net::Ipv4Addr;
     use figment::Figment;
 
-    use crate::config::{Config, TlsConfig};
-    use crate::logger::LogLevel;
+    use crate::config::{Config, TlsConfig, Http2Config, CipherSuite, TlsVersion};
+    use crate::logger::{LogLevel, LogFormat};
     use crate::data::{Limits, ToByteUnit};
 
     #[test]
@@ -182,6 +188,7 @@ mod tests {
                 workers = 20
                 keep_alive = 10
                 log_level = "off"
+                log_format = "json"
                 cli_colors = 0
             "#)?;
 
@@ -192,6 +199,7 @@ mod tests {
                 workers: 20,
                 keep_alive: 10,
                 log_level: LogLevel::Off,
+                log_format: LogFormat::Json,
                 cli_colors: false,
                 ..Config::default()
             });
@@ -282,6 +290,135 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_http2_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("Rocket.toml", r#"
+                [global.http2]
+                h2c = true
+                max_concurrent_streams = 64
+                initial_stream_window_size = 1048576
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config, Config {
+                http2: Http2Config {
+                    h2c: true,
+                    max_concurrent_streams: Some(64),
+                    initial_stream_window_size: Some(1048576),
+                    ..Http2Config::default()
+                },
+                ..Config::default()
+            });
+
+            assert!(!config.http2.http1_only(false));
+            assert!(!config.http2.http1_only(true));
+
+            jail.set_env("ROCKET_HTTP2", r#"{enabled=false}"#);
+            let config = Config::from(Config::figment());
+            assert!(!config.http2.enabled);
+            assert!(config.http2.http1_only(false));
+            assert!(config.http2.http1_only(true));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_request_id_header() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("ROCKET_REQUEST_ID_HEADER", "X-Trace-Id");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.request_id_header, "X-Trace-Id");
+
+            jail.set_env("ROCKET_REQUEST_ID_HEADER", "X Trace Id");
+            let config: Result<Config, _> = Config::figment().extract();
+            assert!(config.is_err());
+
+            jail.set_env("ROCKET_REQUEST_ID_HEADER", "");
+            let config: Result<Config, _> = Config::figment().extract();
+            assert!(config.is_err());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_limits_are_nonzero() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("ROCKET_MAX_CONNECTIONS", 16);
+            jail.set_env("ROCKET_MAX_CONCURRENT_REQUESTS", 4);
+            let config = Config::from(Config::figment());
+            assert_eq!(config.max_connections, Some(16));
+            assert_eq!(config.max_concurrent_requests, Some(4));
+
+            jail.set_env("ROCKET_MAX_CONNECTIONS", 0);
+            let config: Result<Config, _> = Config::figment().extract();
+            assert!(config.is_err());
+
+            jail.set_env("ROCKET_MAX_CONNECTIONS", 16);
+            jail.set_env("ROCKET_MAX_CONCURRENT_REQUESTS", 0);
+            let config: Result<Config, _> = Config::figment().extract();
+            assert!(config.is_err());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_tls_ciphers_and_versions() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("Rocket.toml", r#"
+                [global.tls]
+                certs = "/ssl/cert.pem"
+                key = "/ssl/key.pem"
+                ciphers = [
+                    "TLS_AES_256_GCM_SHA384",
+                    "TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384",
+                ]
+                min_version = "1.2"
+                max_version = "1.3"
+                prefer_server_cipher_order = true
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config, Config {
+                tls: Some(TlsConfig::from_paths("/ssl/cert.pem", "/ssl/key.pem")
+                    .with_ciphers(vec![
+                        CipherSuite::TLS_AES_256_GCM_SHA384,
+                        CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
+                    ])
+                    .with_min_version(TlsVersion::V1_2)
+                    .with_max_version(TlsVersion::V1_3)
+                    .with_preferred_server_cipher_order(true)),
+                ..Config::default()
+            });
+
+            jail.create_file("Rocket.toml", r#"
+                [global.tls]
+                certs = "/ssl/cert.pem"
+                key = "/ssl/key.pem"
+                min_version = "1.3"
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            let tls = config.tls.unwrap();
+            assert_eq!(tls.min_version(), Some(TlsVersion::V1_3));
+            assert!(tls.ciphers().eq(CipherSuite::DEFAULT_SET.iter().copied()));
+
+            jail.set_env("ROCKET_TLS", r#"{certs="a",key="b",min_version="1.0"}"#);
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            jail.set_env("ROCKET_TLS", r#"{certs="a",key="b",max_version="1.1"}"#);
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            jail.set_env("ROCKET_TLS", r#"{certs="a",key="b",ciphers=["TLS_RSA_NOPE"]}"#);
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_profiles_merge() {
         figment::Jail::expect_with(|jail| {
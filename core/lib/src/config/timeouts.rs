@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Route;
+use crate::http::private::HANDSHAKE_TIMEOUT;
+
+/// Timeouts for receiving and handling requests, in seconds.
+///
+/// Each timeout is disabled when set to `0`, as all are by default.
+///
+///   * **`header`**: how long a connection may wait for a request's headers,
+///     measured from when the connection was opened or the previous response
+///     was sent. Connections that exceed it, including idle keep-alive
+///     connections, are closed. It also limits how long a new connection's
+///     TLS handshake and PROXY protocol header may take, which is otherwise
+///     limited to 10 seconds.
+///   * **`body`**: how long a read of a request's body may wait for more data.
+///     A stalled body fails the request with a `408 Request Timeout`.
+///   * **`handler`**: how long a route's handler, including its guards, may
+///     take. A handler that exceeds it fails the request with a `503 Service
+///     Unavailable`. This can be overridden per route via the `timeout` route
+///     attribute parameter or [`Route::timeout`](crate::Route::timeout).
+///
+/// Failed requests are handled by the catcher for the respective status.
+///
+/// ```rust
+/// # use rocket::figment::Figment;
+/// let figment = Figment::from(rocket::Config::default())
+///     .merge(("timeouts.header", 10))
+///     .merge(("timeouts.handler", 60));
+///
+/// let config = rocket::Config::from(figment);
+/// assert_eq!(config.timeouts.header, 10);
+/// assert_eq!(config.timeouts.body, 0);
+/// assert_eq!(config.timeouts.handler, 60);
+/// ```
+#[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+pub struct Timeouts {
+    /// Seconds to wait for a request's headers. **(default: `0`)**
+    pub header: u32,
+    /// Seconds a read of a request's body may wait for data.
+    /// **(default: `0`)**
+    pub body: u32,
+    /// Seconds a route's handler may take. **(default: `0`)**
+    pub handler: u32,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Timeouts { header: 0, body: 0, handler: 0 }
+    }
+}
+
+/// Converts a timeout in seconds to a `Duration`, or `None` if it is disabled.
+fn duration(secs: u32) -> Option<Duration> {
+    match secs {
+        0 => None,
+        n => Some(Duration::from_secs(n as u64)),
+    }
+}
+
+impl Timeouts {
+    /// The header timeout, if enabled.
+    pub(crate) fn header_duration(&self) -> Option<Duration> {
+        duration(self.header)
+    }
+
+    /// The timeout for a new connection's TLS handshake and PROXY header: the
+    /// header timeout, if enabled, or a default.
+    pub(crate) fn handshake_duration(&self) -> Duration {
+        self.header_duration().unwrap_or(HANDSHAKE_TIMEOUT)
+    }
+
+    /// The body timeout, if enabled.
+    pub(crate) fn body_duration(&self) -> Option<Duration> {
+        duration(self.body)
+    }
+
+    /// The handler timeout for `route`, if enabled.
+    pub(crate) fn handler_duration(&self, route: &Route) -> Option<Duration> {
+        duration(route.timeout.unwrap_or(self.handler))
+    }
+}
+
+impl std::fmt::Display for Timeouts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut write = |name, secs| match secs {
+            0 => write!(f, "{} = disabled", name),
+            n => write!(f, "{} = {}s", name, n),
+        };
+
+        write("header", self.header)?;
+        write(", body", self.body)?;
+        write(", handler", self.handler)
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use figment::value::magic::{Either, RelativePathBuf};
 use serde::{Deserialize, Serialize};
 
@@ -5,7 +7,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Both `certs` and `key` can be configured as a path or as raw bytes. `certs`
 /// must be a DER-encoded X.509 TLS certificate chain, while `key` must be a
-/// DER-encoded ASN.1 key in either PKCS#8 or PKCS#1 format.
+/// DER-encoded ASN.1 key in PKCS#8, PKCS#1, or SEC1 format.
 ///
 /// The following example illustrates manual configuration:
 ///
@@ -23,13 +25,237 @@ use serde::{Deserialize, Serialize};
 ///
 /// When a path is configured in a file source, such as `Rocket.toml`, relative
 /// paths are interpreted as being relative to the source file's directory.
+///
+/// RSA, ECDSA (P-256 and P-384), and Ed25519 keys are supported. RSA keys may
+/// be in PKCS#1 or PKCS#8 format, ECDSA keys in SEC1 or PKCS#8 format, and
+/// Ed25519 keys in PKCS#8 format.
+///
+/// # Cipher Suites and Protocol Versions
+///
+/// The enabled cipher suites, in order of preference, are configured via
+/// `ciphers`, which defaults to [`CipherSuite::DEFAULT_SET`]. The enabled
+/// protocol versions are bounded by `min_version` and `max_version`, which
+/// default to TLS 1.2 and TLS 1.3, respectively. When
+/// `prefer_server_cipher_order` is `true`, the server's cipher suite
+/// preference is used instead of the client's.
+///
+/// ```rust
+/// # use rocket::figment::Figment;
+/// use rocket::config::{CipherSuite, TlsVersion};
+///
+/// let figment = Figment::from(rocket::Config::default())
+///     .merge(("tls.certs", "/ssl/certs.pem"))
+///     .merge(("tls.key", "/ssl/key.pem"))
+///     .merge(("tls.ciphers", ["TLS_AES_256_GCM_SHA384", "TLS_AES_128_GCM_SHA256"]))
+///     .merge(("tls.min_version", "1.3"))
+///     .merge(("tls.prefer_server_cipher_order", true));
+///
+/// let config = rocket::Config::from(figment);
+/// let tls_config = config.tls.as_ref().unwrap();
+/// assert_eq!(tls_config.ciphers().collect::<Vec<_>>(), [
+///     CipherSuite::TLS_AES_256_GCM_SHA384,
+///     CipherSuite::TLS_AES_128_GCM_SHA256,
+/// ]);
+///
+/// assert_eq!(tls_config.min_version(), Some(TlsVersion::V1_3));
+/// assert!(tls_config.prefer_server_cipher_order());
+/// ```
+///
+/// Combinations that leave no usable cipher suite, such as enabling only TLS
+/// 1.3 cipher suites while limiting `max_version` to TLS 1.2, or enabling only
+/// RSA cipher suites in TLS 1.2 with an ECDSA key, are reported as errors at
+/// launch.
+///
+/// # Multiple Certificates (SNI)
+///
+/// Additional certificate chains and keys can be served for specific server
+/// names requested by clients via TLS Server Name Indication (SNI). These are
+/// configured via the `sni` key, a map from server names to
+/// [`SniCertificate`]s. A name may be a wildcard matching any single label,
+/// such as `*.rocket.rs`. The top-level `certs` and `key` serve as the default
+/// for clients that request no name or a name without a matching certificate.
+/// The name a client requested is available via [`Request::server_name()`].
+///
+/// [`Request::server_name()`]: crate::Request::server_name()
+///
+/// ```rust
+/// # use rocket::figment::{Figment, providers::{Format, Toml}};
+/// let toml = Toml::string(r#"
+///     [tls]
+///     certs = "/ssl/default_certs.pem"
+///     key = "/ssl/default_key.pem"
+///
+///     [tls.sni."api.rocket.rs"]
+///     certs = "/ssl/api_certs.pem"
+///     key = "/ssl/api_key.pem"
+///
+///     [tls.sni."*.rocket.rs"]
+///     certs = "/ssl/wildcard_certs.pem"
+///     key = "/ssl/wildcard_key.pem"
+/// "#);
+///
+/// let figment = Figment::from(rocket::Config::default()).merge(toml);
+/// let config = rocket::Config::from(figment);
+/// let tls_config = config.tls.as_ref().unwrap();
+/// let api = tls_config.sni("api.rocket.rs").unwrap();
+/// assert_eq!(api.certs().unwrap_left(), std::path::Path::new("/ssl/api_certs.pem"));
+/// assert_eq!(tls_config.sni_names().count(), 2);
+/// ```
+///
+/// # Reloading Certificates
+///
+/// The certificate chain and private key can be reloaded without restarting
+/// the server or dropping established connections. When `watch` is `true`,
+/// certificate and key files are polled for changes and reloaded once they
+/// stop changing. On Unix, when `reload_on_sighup` is `true`, a `SIGHUP`
+/// triggers a reload. A reload can also be requested programmatically via
+/// [`Rocket::tls_reloader()`](crate::Rocket::tls_reloader()). If the new
+/// certificate chain or key is invalid, an error is logged and the previous
+/// certificate remains in use.
+///
+/// ```rust
+/// # use rocket::figment::Figment;
+/// let figment = Figment::from(rocket::Config::default())
+///     .merge(("tls.certs", "/ssl/certs.pem"))
+///     .merge(("tls.key", "/ssl/key.pem"))
+///     .merge(("tls.watch", true))
+///     .merge(("tls.reload_on_sighup", true));
+///
+/// let config = rocket::Config::from(figment);
+/// let tls_config = config.tls.as_ref().unwrap();
+/// assert!(tls_config.watch());
+/// assert!(tls_config.reload_on_sighup());
+/// ```
+///
+/// # Mutual TLS
+///
+/// Client certificate verification, or _mutual TLS_, is configured via the
+/// optional `mutual` key. See [`MutualTls`] for details.
 #[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
 pub struct TlsConfig {
     /// Path or raw bytes for the DER-encoded X.509 TLS certificate chain.
     pub(crate) certs: Either<RelativePathBuf, Vec<u8>>,
-    /// Path or raw bytes to DER-encoded ASN.1 key in either PKCS#8 or PKCS#1
+    /// Path or raw bytes to DER-encoded ASN.1 key in PKCS#8, PKCS#1, or SEC1
     /// format.
     pub(crate) key: Either<RelativePathBuf, Vec<u8>>,
+    /// The enabled cipher suites, in order of preference.
+    #[serde(default = "CipherSuite::default_set")]
+    pub(crate) ciphers: Vec<CipherSuite>,
+    /// The minimum enabled protocol version, if any.
+    #[serde(default)]
+    pub(crate) min_version: Option<TlsVersion>,
+    /// The maximum enabled protocol version, if any.
+    #[serde(default)]
+    pub(crate) max_version: Option<TlsVersion>,
+    /// Whether to prefer the server's cipher suite order.
+    #[serde(default)]
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub(crate) prefer_server_cipher_order: bool,
+    /// Certificate chains and keys for specific SNI server names.
+    #[serde(default)]
+    pub(crate) sni: BTreeMap<String, SniCertificate>,
+    /// Whether to reload the certificate chain and key when their files change.
+    #[serde(default)]
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub(crate) watch: bool,
+    /// Whether to reload the certificate chain and key on `SIGHUP`.
+    #[serde(default)]
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub(crate) reload_on_sighup: bool,
+    /// Mutual TLS configuration, if any.
+    #[serde(default)]
+    pub(crate) mutual: Option<MutualTls>,
+}
+
+/// A certificate chain and private key served to clients that request a
+/// specific server name via SNI.
+///
+/// See [`TlsConfig`] for details on configuring SNI certificates. Both `certs`
+/// and `key` can be configured as a path or as raw bytes in the same formats
+/// as [`TlsConfig`]'s.
+#[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+pub struct SniCertificate {
+    /// Path or raw bytes for the DER-encoded X.509 TLS certificate chain.
+    pub(crate) certs: Either<RelativePathBuf, Vec<u8>>,
+    /// Path or raw bytes to DER-encoded ASN.1 key in PKCS#8, PKCS#1, or SEC1
+    /// format.
+    pub(crate) key: Either<RelativePathBuf, Vec<u8>>,
+}
+
+/// A supported TLS cipher suite.
+///
+/// The first three variants are TLS 1.3 cipher suites; the rest are TLS 1.2
+/// cipher suites. TLS 1.2 cipher suites are additionally restricted to keys of
+/// the type in their name: `ECDSA` suites require an ECDSA or Ed25519 key while
+/// `RSA` suites require an RSA key.
+#[allow(non_camel_case_types)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Deserialize, Serialize)]
+pub enum CipherSuite {
+    /// The TLS 1.3 `TLS_CHACHA20_POLY1305_SHA256` cipher suite.
+    TLS_CHACHA20_POLY1305_SHA256,
+    /// The TLS 1.3 `TLS_AES_256_GCM_SHA384` cipher suite.
+    TLS_AES_256_GCM_SHA384,
+    /// The TLS 1.3 `TLS_AES_128_GCM_SHA256` cipher suite.
+    TLS_AES_128_GCM_SHA256,
+    /// The TLS 1.2 `TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256` cipher suite.
+    TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256,
+    /// The TLS 1.2 `TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256` cipher suite.
+    TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
+    /// The TLS 1.2 `TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384` cipher suite.
+    TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
+    /// The TLS 1.2 `TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384` cipher suite.
+    TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384,
+    /// The TLS 1.2 `TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256` cipher suite.
+    TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+    /// The TLS 1.2 `TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256` cipher suite.
+    TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+}
+
+/// A TLS protocol version.
+///
+/// Versions are configured as strings: `"1.2"` or `"1.3"`. TLS 1.0 and TLS
+/// 1.1 are not supported; configuring either is a configuration error.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Hash, Deserialize, Serialize)]
+pub enum TlsVersion {
+    /// TLS 1.2.
+    #[serde(rename = "1.2")]
+    V1_2,
+    /// TLS 1.3.
+    #[serde(rename = "1.3")]
+    V1_3,
+}
+
+/// Mutual TLS configuration: a CA certificate bundle and whether client
+/// certificates are mandatory.
+///
+/// When configured, clients are asked to present a certificate which is
+/// verified against the certificates in `ca_certs`. If `mandatory` is `true`,
+/// connections from clients that do not present a valid certificate are
+/// rejected during the TLS handshake. Otherwise, such clients may connect but
+/// the [`mtls::Certificate`](crate::mtls::Certificate) request guard will
+/// forward.
+///
+/// ```rust
+/// # use rocket::figment::Figment;
+/// let figment = Figment::from(rocket::Config::default())
+///     .merge(("tls.certs", "/ssl/certs.pem"))
+///     .merge(("tls.key", "/ssl/key.pem"))
+///     .merge(("tls.mutual.ca_certs", "/ssl/ca_certs.pem"))
+///     .merge(("tls.mutual.mandatory", true));
+///
+/// let config = rocket::Config::from(figment);
+/// let mutual = config.tls.as_ref().unwrap().mutual().unwrap();
+/// assert!(mutual.ca_certs().is_left());
+/// assert!(mutual.mandatory);
+/// ```
+#[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+pub struct MutualTls {
+    /// Path or raw bytes for the PEM-encoded X.509 CA certificate bundle.
+    pub(crate) ca_certs: Either<RelativePathBuf, Vec<u8>>,
+    /// Whether a valid client certificate is required. **(default: `false`)**
+    #[serde(default)]
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub mandatory: bool,
 }
 
 impl TlsConfig {
@@ -49,7 +275,15 @@ impl TlsConfig {
     {
         TlsConfig {
             certs: Either::Left(certs.as_ref().to_path_buf().into()),
-            key: Either::Left(key.as_ref().to_path_buf().into())
+            key: Either::Left(key.as_ref().to_path_buf().into()),
+            ciphers: CipherSuite::default_set(),
+            min_version: None,
+            max_version: None,
+            prefer_server_cipher_order: false,
+            sni: BTreeMap::new(),
+            watch: false,
+            reload_on_sighup: false,
+            mutual: None,
         }
     }
 
@@ -70,10 +304,236 @@ impl TlsConfig {
     pub fn from_bytes(certs: &[u8], key: &[u8]) -> Self {
         TlsConfig {
             certs: Either::Right(certs.to_vec().into()),
-            key: Either::Right(key.to_vec().into())
+            key: Either::Right(key.to_vec().into()),
+            ciphers: CipherSuite::default_set(),
+            min_version: None,
+            max_version: None,
+            prefer_server_cipher_order: false,
+            sni: BTreeMap::new(),
+            watch: false,
+            reload_on_sighup: false,
+            mutual: None,
         }
     }
 
+    /// Sets the enabled cipher suites, in order of preference, to `ciphers`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{TlsConfig, CipherSuite};
+    ///
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+    ///     .with_ciphers(CipherSuite::TLS_V13_SET.iter().copied());
+    ///
+    /// assert_eq!(tls_config.ciphers().count(), 3);
+    /// ```
+    pub fn with_ciphers<I>(mut self, ciphers: I) -> Self
+        where I: IntoIterator<Item = CipherSuite>
+    {
+        self.ciphers = ciphers.into_iter().collect();
+        self
+    }
+
+    /// Sets the minimum enabled protocol version to `version`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{TlsConfig, TlsVersion};
+    ///
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+    ///     .with_min_version(TlsVersion::V1_3);
+    ///
+    /// assert_eq!(tls_config.min_version(), Some(TlsVersion::V1_3));
+    /// ```
+    pub fn with_min_version(mut self, version: TlsVersion) -> Self {
+        self.min_version = Some(version);
+        self
+    }
+
+    /// Sets the maximum enabled protocol version to `version`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{TlsConfig, TlsVersion};
+    ///
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+    ///     .with_max_version(TlsVersion::V1_2);
+    ///
+    /// assert_eq!(tls_config.max_version(), Some(TlsVersion::V1_2));
+    /// ```
+    pub fn with_max_version(mut self, version: TlsVersion) -> Self {
+        self.max_version = Some(version);
+        self
+    }
+
+    /// Sets whether the server's cipher suite order is preferred over the
+    /// client's.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::TlsConfig;
+    ///
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+    ///     .with_preferred_server_cipher_order(true);
+    ///
+    /// assert!(tls_config.prefer_server_cipher_order());
+    /// ```
+    pub fn with_preferred_server_cipher_order(mut self, prefer_server_order: bool) -> Self {
+        self.prefer_server_cipher_order = prefer_server_order;
+        self
+    }
+
+    /// Returns an iterator over the enabled cipher suites in order of
+    /// preference.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{TlsConfig, CipherSuite};
+    ///
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem");
+    /// assert!(tls_config.ciphers().eq(CipherSuite::DEFAULT_SET.iter().copied()));
+    /// ```
+    pub fn ciphers(&self) -> impl Iterator<Item = CipherSuite> + '_ {
+        self.ciphers.iter().copied()
+    }
+
+    /// Returns the configured minimum protocol version, if any.
+    pub fn min_version(&self) -> Option<TlsVersion> {
+        self.min_version
+    }
+
+    /// Returns the configured maximum protocol version, if any.
+    pub fn max_version(&self) -> Option<TlsVersion> {
+        self.max_version
+    }
+
+    /// Returns whether the server's cipher suite order is preferred over the
+    /// client's.
+    pub fn prefer_server_cipher_order(&self) -> bool {
+        self.prefer_server_cipher_order
+    }
+
+    /// Adds `certificate` as the certificate to serve to clients that request
+    /// the server name `name` via SNI, replacing any existing certificate for
+    /// `name`. `name` may be a wildcard matching any single label, such as
+    /// `*.rocket.rs`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{TlsConfig, SniCertificate};
+    ///
+    /// let api = SniCertificate::from_paths("/ssl/api_certs.pem", "/ssl/api_key.pem");
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+    ///     .with_sni("api.rocket.rs", api);
+    ///
+    /// assert!(tls_config.sni("api.rocket.rs").is_some());
+    /// assert!(tls_config.sni("www.rocket.rs").is_none());
+    /// ```
+    pub fn with_sni<N: Into<String>>(mut self, name: N, certificate: SniCertificate) -> Self {
+        self.sni.insert(name.into().to_ascii_lowercase(), certificate);
+        self
+    }
+
+    /// Returns the certificate configured for the SNI server name `name`, if
+    /// any. Wildcards are not expanded: `name` must match a configured name
+    /// exactly, ignoring case.
+    pub fn sni(&self, name: &str) -> Option<&SniCertificate> {
+        self.sni.iter()
+            .find(|(sni_name, _)| sni_name.eq_ignore_ascii_case(name))
+            .map(|(_, certificate)| certificate)
+    }
+
+    /// Returns an iterator over the configured SNI server names.
+    pub fn sni_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.sni.keys().map(|name| name.as_str())
+    }
+
+    /// Sets whether the certificate chain and key are reloaded when their
+    /// files change. Only certificates and keys configured as paths are
+    /// watched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::TlsConfig;
+    ///
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+    ///     .with_watch(true);
+    ///
+    /// assert!(tls_config.watch());
+    /// ```
+    pub fn with_watch(mut self, watch: bool) -> Self {
+        self.watch = watch;
+        self
+    }
+
+    /// Sets whether the certificate chain and key are reloaded when the
+    /// process receives a `SIGHUP`. Has no effect on non-Unix platforms.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::TlsConfig;
+    ///
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+    ///     .with_reload_on_sighup(true);
+    ///
+    /// assert!(tls_config.reload_on_sighup());
+    /// ```
+    pub fn with_reload_on_sighup(mut self, reload: bool) -> Self {
+        self.reload_on_sighup = reload;
+        self
+    }
+
+    /// Returns whether the certificate chain and key are reloaded when their
+    /// files change.
+    pub fn watch(&self) -> bool {
+        self.watch
+    }
+
+    /// Returns whether the certificate chain and key are reloaded on `SIGHUP`.
+    pub fn reload_on_sighup(&self) -> bool {
+        self.reload_on_sighup
+    }
+
+    /// Sets the mutual TLS configuration to `mutual`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{TlsConfig, MutualTls};
+    ///
+    /// let mutual = MutualTls::from_path("/ssl/ca_certs.pem").mandatory(true);
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem")
+    ///     .with_mutual(mutual);
+    ///
+    /// assert!(tls_config.mutual().unwrap().mandatory);
+    /// ```
+    pub fn with_mutual(mut self, mutual: MutualTls) -> Self {
+        self.mutual = Some(mutual);
+        self
+    }
+
+    /// Returns the mutual TLS configuration, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::TlsConfig;
+    ///
+    /// let tls_config = TlsConfig::from_paths("/ssl/certs.pem", "/ssl/key.pem");
+    /// assert!(tls_config.mutual().is_none());
+    /// ```
+    pub fn mutual(&self) -> Option<&MutualTls> {
+        self.mutual.as_ref()
+    }
+
     /// Returns the value of the `certs` parameter.
     ///
     /// # Example
@@ -120,12 +580,206 @@ impl TlsConfig {
     }
 }
 
+impl SniCertificate {
+    /// Constructs an `SniCertificate` from paths to a certificate chain and
+    /// private key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::SniCertificate;
+    ///
+    /// let api = SniCertificate::from_paths("/ssl/api_certs.pem", "/ssl/api_key.pem");
+    /// ```
+    pub fn from_paths<C, K>(certs: C, key: K) -> Self
+        where C: AsRef<std::path::Path>, K: AsRef<std::path::Path>
+    {
+        SniCertificate {
+            certs: Either::Left(certs.as_ref().to_path_buf().into()),
+            key: Either::Left(key.as_ref().to_path_buf().into()),
+        }
+    }
+
+    /// Constructs an `SniCertificate` from byte buffers containing a
+    /// certificate chain and private key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::SniCertificate;
+    ///
+    /// # let certs_buf = &[];
+    /// # let key_buf = &[];
+    /// let api = SniCertificate::from_bytes(certs_buf, key_buf);
+    /// ```
+    pub fn from_bytes(certs: &[u8], key: &[u8]) -> Self {
+        SniCertificate {
+            certs: Either::Right(certs.to_vec().into()),
+            key: Either::Right(key.to_vec().into()),
+        }
+    }
+
+    /// Returns the value of the `certs` parameter.
+    pub fn certs(&self) -> either::Either<std::path::PathBuf, &[u8]> {
+        match &self.certs {
+            Either::Left(path) => either::Either::Left(path.relative()),
+            Either::Right(bytes) => either::Either::Right(&bytes),
+        }
+    }
+
+    /// Returns the value of the `key` parameter.
+    pub fn key(&self) -> either::Either<std::path::PathBuf, &[u8]> {
+        match &self.key {
+            Either::Left(path) => either::Either::Left(path.relative()),
+            Either::Right(bytes) => either::Either::Right(&bytes),
+        }
+    }
+}
+
+impl CipherSuite {
+    /// The default set and order of cipher suites: TLS 1.3 cipher suites
+    /// followed by TLS 1.2 cipher suites, with ChaCha20 preferred.
+    pub const DEFAULT_SET: [CipherSuite; 9] = [
+        CipherSuite::TLS_CHACHA20_POLY1305_SHA256,
+        CipherSuite::TLS_AES_256_GCM_SHA384,
+        CipherSuite::TLS_AES_128_GCM_SHA256,
+        CipherSuite::TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256,
+        CipherSuite::TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
+        CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
+        CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+        CipherSuite::TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384,
+        CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+    ];
+
+    /// The TLS 1.3 cipher suites.
+    pub const TLS_V13_SET: [CipherSuite; 3] = [
+        CipherSuite::TLS_CHACHA20_POLY1305_SHA256,
+        CipherSuite::TLS_AES_256_GCM_SHA384,
+        CipherSuite::TLS_AES_128_GCM_SHA256,
+    ];
+
+    /// The TLS 1.2 cipher suites.
+    pub const TLS_V12_SET: [CipherSuite; 6] = [
+        CipherSuite::TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256,
+        CipherSuite::TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
+        CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
+        CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+        CipherSuite::TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384,
+        CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+    ];
+
+    fn default_set() -> Vec<CipherSuite> {
+        CipherSuite::DEFAULT_SET.to_vec()
+    }
+
+    /// Returns the protocol version `self` is used with.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{CipherSuite, TlsVersion};
+    ///
+    /// let suite = CipherSuite::TLS_AES_128_GCM_SHA256;
+    /// assert_eq!(suite.version(), TlsVersion::V1_3);
+    /// ```
+    pub fn version(self) -> TlsVersion {
+        match CipherSuite::TLS_V13_SET.contains(&self) {
+            true => TlsVersion::V1_3,
+            false => TlsVersion::V1_2,
+        }
+    }
+}
+
+impl std::fmt::Display for TlsVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TlsVersion::V1_2 => write!(f, "TLS 1.2"),
+            TlsVersion::V1_3 => write!(f, "TLS 1.3"),
+        }
+    }
+}
+
+impl MutualTls {
+    /// Constructs a `MutualTls` configuration from a path to a PEM-encoded CA
+    /// certificate bundle. Client certificates are not mandatory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::MutualTls;
+    ///
+    /// let mutual = MutualTls::from_path("/ssl/ca_certs.pem");
+    /// assert!(!mutual.mandatory);
+    /// ```
+    pub fn from_path<C: AsRef<std::path::Path>>(ca_certs: C) -> Self {
+        MutualTls {
+            ca_certs: Either::Left(ca_certs.as_ref().to_path_buf().into()),
+            mandatory: false,
+        }
+    }
+
+    /// Constructs a `MutualTls` configuration from a byte buffer containing a
+    /// PEM-encoded CA certificate bundle. Client certificates are not
+    /// mandatory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::MutualTls;
+    ///
+    /// # let ca_certs_buf = &[];
+    /// let mutual = MutualTls::from_bytes(ca_certs_buf);
+    /// ```
+    pub fn from_bytes(ca_certs: &[u8]) -> Self {
+        MutualTls {
+            ca_certs: Either::Right(ca_certs.to_vec().into()),
+            mandatory: false,
+        }
+    }
+
+    /// Sets whether a valid client certificate is required to connect.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::MutualTls;
+    ///
+    /// let mutual = MutualTls::from_path("/ssl/ca_certs.pem").mandatory(true);
+    /// assert!(mutual.mandatory);
+    /// ```
+    pub fn mandatory(mut self, mandatory: bool) -> Self {
+        self.mandatory = mandatory;
+        self
+    }
+
+    /// Returns the value of the `ca_certs` parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    /// use rocket::config::MutualTls;
+    ///
+    /// let mutual = MutualTls::from_path("/ssl/ca_certs.pem");
+    /// assert_eq!(mutual.ca_certs().unwrap_left(), Path::new("/ssl/ca_certs.pem"));
+    /// ```
+    pub fn ca_certs(&self) -> either::Either<std::path::PathBuf, &[u8]> {
+        match &self.ca_certs {
+            Either::Left(path) => either::Either::Left(path.relative()),
+            Either::Right(bytes) => either::Either::Right(&bytes),
+        }
+    }
+}
+
 #[cfg(feature = "tls")]
 type Reader = Box<dyn std::io::BufRead + Sync + Send>;
 
 #[cfg(feature = "tls")]
 impl TlsConfig {
-    pub(crate) fn to_readers(&self) -> std::io::Result<(Reader, Reader)> {
+    pub(crate) fn to_native_config(
+        &self,
+        alpn_protocols: Vec<Vec<u8>>,
+    ) -> std::io::Result<crate::http::tls::Config<Reader>> {
         use std::{io::{self, Error}, fs};
         use yansi::Paint;
 
@@ -144,6 +798,119 @@ impl TlsConfig {
             }
         }
 
-        Ok((to_reader(&self.certs)?, to_reader(&self.key)?))
+        let versions = self.enabled_versions()?;
+        let ciphersuites = self.ciphers()
+            .filter(|suite| versions.contains(&suite.version()))
+            .map(|suite| suite.to_native())
+            .collect::<Vec<_>>();
+
+        if ciphersuites.is_empty() {
+            let versions: Vec<_> = versions.iter().map(|v| v.to_string()).collect();
+            let msg = format!("none of the enabled cipher suites support {}",
+                versions.join(" or "));
+
+            return Err(Error::new(io::ErrorKind::InvalidInput, msg));
+        }
+
+        let ca_certs = match self.mutual {
+            Some(ref mutual) => Some(to_reader(&mutual.ca_certs)?),
+            None => None,
+        };
+
+        let sni = self.sni.iter()
+            .map(|(name, sni)| Ok(crate::http::tls::SniCertificate {
+                name: name.clone(),
+                cert_chain: to_reader(&sni.certs)?,
+                private_key: to_reader(&sni.key)?,
+            }))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Ok(crate::http::tls::Config {
+            cert_chain: to_reader(&self.certs)?,
+            private_key: to_reader(&self.key)?,
+            sni,
+            ca_certs,
+            mandatory_mtls: self.mutual.as_ref().map_or(false, |m| m.mandatory),
+            alpn_protocols,
+            versions: versions.iter().map(|v| v.to_native()).collect(),
+            ciphersuites,
+            prefer_server_order: self.prefer_server_cipher_order,
+        })
+    }
+
+    /// Returns the modification times of the certificate chain and key files,
+    /// including those for SNI names, for those configured as paths.
+    pub(crate) fn modified(&self) -> Vec<Option<std::time::SystemTime>> {
+        let sni = self.sni.values().flat_map(|sni| vec![&sni.certs, &sni.key]);
+        vec![&self.certs, &self.key].into_iter()
+            .chain(sni)
+            .filter_map(|value| match value {
+                Either::Left(path) => Some(path.relative()),
+                Either::Right(_) => None,
+            })
+            .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+            .collect()
+    }
+
+    /// Returns the supported versions in `[min_version, max_version]`.
+    fn enabled_versions(&self) -> std::io::Result<Vec<TlsVersion>> {
+        use std::io::{Error, ErrorKind::InvalidInput};
+
+        let min = self.min_version.unwrap_or(TlsVersion::V1_2);
+        let max = self.max_version.unwrap_or(TlsVersion::V1_3);
+        if min > max {
+            let msg = format!("`min_version` ({}) exceeds `max_version` ({})", min, max);
+            return Err(Error::new(InvalidInput, msg));
+        }
+
+        let versions = [TlsVersion::V1_2, TlsVersion::V1_3].iter()
+            .copied()
+            .filter(|v| (min..=max).contains(v))
+            .collect();
+
+        Ok(versions)
+    }
+}
+
+#[cfg(feature = "tls")]
+impl CipherSuite {
+    fn to_native(self) -> &'static crate::http::tls::rustls::SupportedCipherSuite {
+        use crate::http::tls::rustls::ciphersuite::*;
+
+        match self {
+            CipherSuite::TLS_CHACHA20_POLY1305_SHA256 => &TLS13_CHACHA20_POLY1305_SHA256,
+            CipherSuite::TLS_AES_256_GCM_SHA384 => &TLS13_AES_256_GCM_SHA384,
+            CipherSuite::TLS_AES_128_GCM_SHA256 => &TLS13_AES_128_GCM_SHA256,
+            CipherSuite::TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256 => {
+                &TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256
+            }
+            CipherSuite::TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256 => {
+                &TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256
+            }
+            CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384 => {
+                &TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384
+            }
+            CipherSuite::TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384 => {
+                &TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384
+            }
+            CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256 => {
+                &TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256
+            }
+            CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256 => {
+                &TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tls")]
+impl TlsVersion {
+    fn to_native(self) -> crate::http::tls::rustls::ProtocolVersion {
+        use crate::http::tls::rustls::ProtocolVersion;
+
+        match self {
+            TlsVersion::V1_2 => ProtocolVersion::TLSv1_2,
+            TlsVersion::V1_3 => ProtocolVersion::TLSv1_3,
+        }
     }
 }
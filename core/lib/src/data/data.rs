@@ -1,4 +1,7 @@
 use std::io::Cursor;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use crate::http::hyper;
 use crate::ext::AsyncReadBody;
@@ -45,17 +48,21 @@ pub struct Data {
     buffer: Vec<u8>,
     is_complete: bool,
     stream: AsyncReadBody,
+    timed_out: Arc<AtomicBool>,
 }
 
 impl Data {
-    pub(crate) async fn from_hyp(body: hyper::Body) -> Data {
-        // TODO.async: This used to also set the read timeout to 5 seconds.
-        // Such a short read timeout is likely no longer necessary, but some
-        // kind of idle timeout should be implemented.
+    /// Creates a `Data` from a hyper body. Reads that wait longer than
+    /// `timeout`, if any, for more of the body fail.
+    pub(crate) async fn from_hyp(body: hyper::Body, timeout: Option<Duration>) -> Data {
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let stream = match timeout {
+            Some(t) => AsyncReadBody::with_idle_timeout(body, t, timed_out.clone()),
+            None => AsyncReadBody::from(body),
+        };
 
-        let stream = AsyncReadBody::from(body);
         let buffer = Vec::with_capacity(PEEK_BYTES / 8);
-        Data { buffer, stream, is_complete: false }
+        Data { buffer, stream, is_complete: false, timed_out }
     }
 
     /// This creates a `data` object from a local data source `data`.
@@ -65,9 +72,17 @@ impl Data {
             buffer: data,
             stream: AsyncReadBody::empty(),
             is_complete: true,
+            timed_out: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Returns a function that reports whether a read of this body has timed
+    /// out, even after `self` has been consumed.
+    pub(crate) fn timed_out(&self) -> impl Fn() -> bool {
+        let timed_out = self.timed_out.clone();
+        move || timed_out.load(Ordering::Acquire)
+    }
+
     /// Returns the raw data stream, limited to `limit` bytes.
     ///
     /// The stream contains all of the data in the body of the request,
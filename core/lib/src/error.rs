@@ -76,6 +76,8 @@ pub enum ErrorKind {
     Bind(io::Error),
     /// An I/O error occurred during launch.
     Io(io::Error),
+    /// The TLS configuration is invalid or could not be loaded.
+    Tls(io::Error),
     /// An I/O error occurred in the runtime.
     Runtime(Box<dyn std::error::Error + Send + Sync>),
     /// Route collisions were detected.
@@ -137,6 +139,7 @@ impl fmt::Display for ErrorKind {
         match self {
             ErrorKind::Bind(e) => write!(f, "binding failed: {}", e),
             ErrorKind::Io(e) => write!(f, "I/O error: {}", e),
+            ErrorKind::Tls(e) => write!(f, "TLS configuration error: {}", e),
             ErrorKind::Collision(_) => write!(f, "route collisions detected"),
             ErrorKind::FailedFairings(_) => write!(f, "a launch fairing failed"),
             ErrorKind::Runtime(e) => write!(f, "runtime error: {}", e)
@@ -177,6 +180,11 @@ impl Drop for Error {
                 info_!("{}", e);
                 panic!("aborting due to i/o error");
             }
+            ErrorKind::Tls(ref e) => {
+                error!("Rocket failed to launch due to an invalid TLS configuration.");
+                info_!("{}", e);
+                panic!("aborting due to TLS configuration error");
+            }
             ErrorKind::Collision(ref collisions) => {
                 error!("Rocket failed to launch due to the following routing collisions:");
                 for &(ref a, ref b) in collisions {
@@ -1,9 +1,14 @@
 use std::io::{self, Cursor};
 use std::pin::Pin;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::task::{Poll, Context};
+use std::time::Duration;
 
 use futures::{ready, stream::Stream};
 use tokio::io::{AsyncRead, ReadBuf};
+use tokio::time::{Instant, Sleep};
 
 use crate::http::hyper::{self, Bytes, HttpBody};
 
@@ -50,6 +55,15 @@ impl<T: AsyncRead> AsyncReadExt for T { }
 pub struct AsyncReadBody {
     inner: hyper::Body,
     state: State,
+    idle_timeout: Option<IdleTimeout>,
+}
+
+/// Fails reads that wait longer than `duration` for more data.
+struct IdleTimeout {
+    duration: Duration,
+    sleep: Pin<Box<Sleep>>,
+    waiting: bool,
+    expired: Arc<AtomicBool>,
 }
 
 enum State {
@@ -60,13 +74,41 @@ enum State {
 
 impl AsyncReadBody {
     pub fn empty() -> Self {
-        Self { inner: hyper::Body::empty(), state: State::Done }
+        Self { inner: hyper::Body::empty(), state: State::Done, idle_timeout: None }
+    }
+
+    /// Fails reads of `body` that wait longer than `duration` for more data
+    /// with an error of kind `TimedOut`, setting `expired` when one does.
+    pub fn with_idle_timeout(
+        body: hyper::Body,
+        duration: Duration,
+        expired: Arc<AtomicBool>,
+    ) -> Self {
+        let sleep = Box::pin(tokio::time::sleep(duration));
+        let idle_timeout = IdleTimeout { duration, sleep, waiting: false, expired };
+        Self { inner: body, state: State::Pending, idle_timeout: Some(idle_timeout) }
     }
 }
 
 impl From<hyper::Body> for AsyncReadBody {
     fn from(body: hyper::Body) -> Self {
-        Self { inner: body, state: State::Pending }
+        Self { inner: body, state: State::Pending, idle_timeout: None }
+    }
+}
+
+impl IdleTimeout {
+    /// Polls the timer for a read that is waiting for data, starting it if
+    /// the read just began waiting.
+    fn poll_expired(&mut self, cx: &mut Context<'_>) -> Poll<io::Error> {
+        if !self.waiting {
+            self.waiting = true;
+            self.sleep.as_mut().reset(Instant::now() + self.duration);
+        }
+
+        ready!(self.sleep.as_mut().poll(cx));
+        self.expired.store(true, Ordering::Release);
+        let msg = "timed out waiting for request body data";
+        Poll::Ready(io::Error::new(io::ErrorKind::TimedOut, msg))
     }
 }
 
@@ -79,7 +121,19 @@ impl AsyncRead for AsyncReadBody {
         loop {
             match self.state {
                 State::Pending => {
-                    match ready!(Pin::new(&mut self.inner).poll_data(cx)) {
+                    let data = match Pin::new(&mut self.inner).poll_data(cx) {
+                        Poll::Ready(data) => data,
+                        Poll::Pending => match self.idle_timeout.as_mut() {
+                            Some(timeout) => return timeout.poll_expired(cx).map(Err),
+                            None => return Poll::Pending,
+                        }
+                    };
+
+                    if let Some(timeout) = self.idle_timeout.as_mut() {
+                        timeout.waiting = false;
+                    }
+
+                    match data {
                         Some(Ok(bytes)) => {
                             self.state = State::Partial(Cursor::new(bytes));
                         }
@@ -101,3 +155,28 @@ impl AsyncRead for AsyncReadBody {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    #[test]
+    fn test_body_idle_timeout() {
+        crate::async_test(async {
+            let (mut sender, body) = hyper::Body::channel();
+            let expired = Arc::new(AtomicBool::new(false));
+            let timeout = Duration::from_millis(50);
+            let mut body = AsyncReadBody::with_idle_timeout(body, timeout, expired.clone());
+
+            let mut buf = [0; 2];
+            sender.send_data(Bytes::from_static(b"hi")).await.unwrap();
+            body.read_exact(&mut buf).await.unwrap();
+            assert!(!expired.load(Ordering::Acquire));
+
+            let error = body.read(&mut buf).await.unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+            assert!(expired.load(Ordering::Acquire));
+        })
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{Rocket, Request, Response, Data};
 use crate::fairing::{Fairing, Kind};
 use crate::logger::PaintExt;
+use crate::trace::Span;
 
 use yansi::Paint;
 
@@ -61,14 +62,18 @@ impl Fairings {
     #[inline(always)]
     pub async fn handle_request(&self, req: &mut Request<'_>, data: &mut Data) {
         for &i in &self.request {
-            self.all_fairings[i].on_request(req, data).await;
+            let fairing = &self.all_fairings[i];
+            let span = Span::fairing(fairing.info().name, "request");
+            span.instrument(fairing.on_request(req, data)).await;
         }
     }
 
     #[inline(always)]
     pub async fn handle_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
         for &i in &self.response {
-            self.all_fairings[i].on_response(request, response).await;
+            let fairing = &self.all_fairings[i];
+            let span = Span::fairing(fairing.info().name, "response");
+            span.instrument(fairing.on_response(request, response)).await;
         }
     }
 
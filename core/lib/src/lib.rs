@@ -62,10 +62,13 @@
 //!
 //! ## Features
 //!
-//! There are two optional, disabled-by-default features:
+//! There are five optional, disabled-by-default features:
 //!
 //!   * **secrets:** Enables support for [private cookies].
 //!   * **tls:** Enables support for [TLS].
+//!   * **mtls:** Enables support for [mutual TLS]. Implies `tls`.
+//!   * **tracing:** Runs connections and requests in [`tracing`](trace) spans.
+//!   * **regex:** Enables the [`pattern`](request::validate) form validator.
 //!
 //! The features can be enabled in `Rocket.toml`:
 //!
@@ -76,6 +79,7 @@
 //!
 //! [private cookies]: https://rocket.rs/master/guide/requests/#private-cookies
 //! [TLS]: https://rocket.rs/master/guide/configuration/#tls
+//! [mutual TLS]: https://rocket.rs/master/guide/configuration/#mutual-tls
 //!
 //! ## Configuration
 //!
@@ -120,6 +124,11 @@ pub mod handler;
 pub mod fairing;
 pub mod error;
 pub mod catcher;
+pub mod trace;
+
+#[cfg(feature = "mtls")]
+#[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
+pub mod mtls;
 
 // Reexport of HTTP everything.
 pub mod http {
@@ -133,6 +142,7 @@ pub mod http {
 }
 
 mod shutdown;
+#[cfg(feature = "tls")] mod reload;
 mod router;
 mod rocket;
 mod server;
@@ -149,6 +159,9 @@ pub use crate::router::Route;
 pub use crate::request::{Request, State};
 pub use crate::rocket::Rocket;
 pub use crate::shutdown::Shutdown;
+#[cfg(feature = "tls")]
+#[cfg_attr(nightly, doc(cfg(feature = "tls")))]
+pub use crate::reload::TlsReloader;
 
 /// Alias to [`Rocket::ignite()`] Creates a new instance of `Rocket`.
 pub fn ignite() -> Rocket {
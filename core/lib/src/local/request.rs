@@ -93,6 +93,25 @@ macro_rules! pub_request_impl {
         self
     }
 
+    /// Set the TLS SNI server name of this request, as if the client had
+    /// requested `name` during a TLS handshake.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    #[doc = $import]
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let req = request.server_name("api.rocket.rs");
+    /// # });
+    /// ```
+    #[inline]
+    pub fn server_name(mut self, name: &str) -> Self {
+        self._request_mut().set_server_name(name);
+        self
+    }
+
     /// Add a cookie to this request.
     ///
     /// # Examples
@@ -166,6 +185,38 @@ macro_rules! pub_request_impl {
         self
     }
 
+    /// Sets the client certificate chain presented by this request to the
+    /// PEM-encoded certificates read from `reader`, as if the client had
+    /// presented them during a mutual TLS handshake. The certificates are not
+    /// verified.
+    ///
+    /// If `reader` does not contain valid PEM-encoded certificates, the
+    /// request proceeds without a client certificate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    #[doc = $import]
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let pem = std::io::Cursor::new("-----BEGIN CERTIFICATE----- ...");
+    /// let req = request.identity(pem);
+    /// # });
+    /// ```
+    #[cfg(feature = "mtls")]
+    #[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
+    #[inline]
+    pub fn identity<C: std::io::Read>(mut self, reader: C) -> Self {
+        let mut reader = std::io::BufReader::new(reader);
+        let certs = crate::http::tls::parse_certificates(&mut reader).ok()
+            .filter(|certs| !certs.is_empty())
+            .map(std::sync::Arc::new);
+
+        self._request_mut().connection.peer_certs = certs;
+        self
+    }
+
     /// Set the body (data) of the request.
     ///
     /// # Examples
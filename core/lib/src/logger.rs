@@ -1,14 +1,19 @@
 //! Rocket's logging infrastructure.
 
 use std::fmt;
+use std::future::Future;
 use std::str::FromStr;
+use std::time::Instant;
 
 use log;
 use yansi::Paint;
+use parking_lot::Mutex;
 use serde::{de, Serialize, Serializer, Deserialize, Deserializer};
 
+use crate::http::Status;
+
 #[derive(Debug)]
-struct RocketLogger(LogLevel);
+struct RocketLogger(LogLevel, LogFormat);
 
 /// Defines the maximum level of log messages to show.
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -66,6 +71,67 @@ impl fmt::Display for LogLevel {
     }
 }
 
+/// Defines the format of log messages.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LogFormat {
+    /// Colored, indented, human-readable lines: `"pretty"`.
+    Pretty,
+    /// One JSON object per line: `"json"`.
+    ///
+    /// Each object has `level`, `target`, and `message` fields. Configuration
+    /// settings logged at launch also have `setting` and `value` fields.
+    /// Messages logged while handling a request also have the request's
+    /// `request_id`, `method`, and `uri`, the `route` name once a named route
+    /// has matched, and the response's `status` and the `duration_ms` since the
+    /// request arrived once a response has been produced.
+    Json,
+}
+
+impl LogFormat {
+    fn as_str(&self) -> &str {
+        match self {
+            LogFormat::Pretty => "pretty",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let format = match &*s.to_ascii_lowercase() {
+            "pretty" => LogFormat::Pretty,
+            "json" => LogFormat::Json,
+            _ => return Err("a log format (pretty, json)")
+        };
+
+        Ok(format)
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Serialize for LogFormat {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for LogFormat {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(de)?;
+        LogFormat::from_str(&string).map_err(|_| de::Error::invalid_value(
+            de::Unexpected::Str(&string),
+            &figment::error::OneOf( &["pretty", "json"])
+        ))
+    }
+}
+
 impl Serialize for LogLevel {
     fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
         ser.serialize_str(self.as_str())
@@ -122,6 +188,10 @@ impl log::Log for RocketLogger {
             return;
         }
 
+        if self.1 == LogFormat::Json {
+            return log_json(record);
+        }
+
         // In Rocket, we abuse targets with suffix "_" to indicate indentation.
         let is_launch = record.target().starts_with("launch");
         if record.target().ends_with('_') {
@@ -130,6 +200,9 @@ impl log::Log for RocketLogger {
             }
         }
 
+        // Tag messages logged while handling a request with its ID.
+        let _ = REQUEST.try_with(|request| print!("{} ", Paint::default(&request.id).dimmed()));
+
         match record.level() {
             log::Level::Info => println!("{}", Paint::blue(record.args()).wrap()),
             log::Level::Trace => println!("{}", Paint::magenta(record.args()).wrap()),
@@ -163,7 +236,121 @@ impl log::Log for RocketLogger {
     }
 }
 
-pub(crate) fn try_init(level: LogLevel, colors: bool, verbose: bool) -> bool {
+tokio::task_local! {
+    static REQUEST: RequestContext;
+}
+
+/// The request being handled by the current task. Its ID is included in pretty
+/// logs, and all of it in JSON logs.
+pub(crate) struct RequestContext {
+    id: String,
+    method: String,
+    uri: String,
+    start: Instant,
+    route: Mutex<Option<&'static str>>,
+    status: Mutex<Option<u16>>,
+}
+
+impl RequestContext {
+    pub(crate) fn new(id: String, method: &str, uri: String) -> Self {
+        let (route, status, start) = (Mutex::new(None), Mutex::new(None), Instant::now());
+        RequestContext { id, method: method.into(), uri, start, route, status }
+    }
+}
+
+/// Runs `fut` with `context` as the current task's request.
+pub(crate) async fn scoped<F: Future>(context: RequestContext, fut: F) -> F::Output {
+    REQUEST.scope(context, fut).await
+}
+
+/// Records the name of the route handling the current task's request.
+pub(crate) fn set_route(name: Option<&'static str>) {
+    let _ = REQUEST.try_with(|request| *request.route.lock() = name);
+}
+
+/// Records the status of the response to the current task's request.
+pub(crate) fn set_status(status: Status) {
+    let _ = REQUEST.try_with(|request| *request.status.lock() = Some(status.code));
+}
+
+/// A log record as printed in JSON logs.
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    level: &'a str,
+    target: &'a str,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    setting: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<&'a str>,
+    #[serde(flatten)]
+    request: Option<JsonRequest<'a>>,
+}
+
+/// The request a JSON log record was logged while handling.
+#[derive(Serialize)]
+struct JsonRequest<'a> {
+    request_id: &'a str,
+    method: &'a str,
+    uri: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    route: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_ms: Option<f64>,
+}
+
+impl<'a> From<&'a RequestContext> for JsonRequest<'a> {
+    fn from(request: &'a RequestContext) -> Self {
+        let status = *request.status.lock();
+        let duration_ms = status.map(|_| {
+            let ms = request.start.elapsed().as_secs_f64() * 1000.0;
+            (ms * 1000.0).round() / 1000.0
+        });
+
+        JsonRequest {
+            request_id: &request.id,
+            method: &request.method,
+            uri: &request.uri,
+            route: *request.route.lock(),
+            status,
+            duration_ms,
+        }
+    }
+}
+
+/// Formats `record`, logged while handling `request`, if any, as a single-line
+/// JSON object.
+fn json_line(record: &log::Record<'_>, request: Option<&RequestContext>) -> String {
+    // In Rocket, targets ending in "_" only indicate indentation.
+    let target = match record.target().trim_end_matches('_') {
+        "" => record.module_path().unwrap_or("rocket"),
+        target => target,
+    };
+
+    // Launch settings are logged as `setting: value`; structure them as such.
+    let message = record.args().to_string();
+    let (setting, value) = match (record.target(), message.find(": ")) {
+        ("launch_", Some(i)) => (Some(&message[..i]), Some(&message[(i + 2)..])),
+        _ => (None, None),
+    };
+
+    let level = record.level().as_str().to_ascii_lowercase();
+    let request = request.map(JsonRequest::from);
+    let record = JsonRecord { level: &level, target, message: &message, setting, value, request };
+    serde_json::to_string(&record).unwrap_or_default()
+}
+
+/// Prints `record` as a single-line JSON object.
+fn log_json(record: &log::Record<'_>) {
+    let line = REQUEST.try_with(|request| json_line(record, Some(request)))
+        .unwrap_or_else(|_| json_line(record, None));
+
+    println!("{}", line);
+}
+
+pub(crate) fn try_init(level: LogLevel, format: LogFormat, colors: bool, verbose: bool) -> bool {
     if level == LogLevel::Off {
         return false;
     }
@@ -171,11 +358,12 @@ pub(crate) fn try_init(level: LogLevel, colors: bool, verbose: bool) -> bool {
     if !atty::is(atty::Stream::Stdout)
         || (cfg!(windows) && !Paint::enable_windows_ascii())
         || !colors
+        || format == LogFormat::Json
     {
         Paint::disable();
     }
 
-    if let Err(e) = log::set_boxed_logger(Box::new(RocketLogger(level))) {
+    if let Err(e) = log::set_boxed_logger(Box::new(RocketLogger(level, format))) {
         if verbose {
             eprintln!("Logger failed to initialize: {}", e);
         }
@@ -201,7 +389,7 @@ impl PaintExt for Paint<&str> {
 
 #[doc(hidden)]
 pub fn init(level: LogLevel) -> bool {
-    try_init(level, true, true)
+    try_init(level, LogFormat::Pretty, true, true)
 }
 
 // Expose logging macros as (hidden) funcions for use by core/contrib codegen.
@@ -216,3 +404,51 @@ external_log_function!(error: error);
 external_log_function!(error_: error_);
 external_log_function!(warn: warn);
 external_log_function!(warn_: warn_);
+
+#[cfg(test)]
+mod tests {
+    use super::{json_line, RequestContext, set_route, set_status, REQUEST};
+    use crate::http::Status;
+
+    fn line(target: &str, message: &str, request: Option<&RequestContext>) -> String {
+        json_line(&log::Record::builder()
+            .level(log::Level::Info)
+            .target(target)
+            .module_path(Some("rocket::server"))
+            .args(format_args!("{}", message))
+            .build(), request)
+    }
+
+    #[test]
+    fn test_json_line() {
+        assert_eq!(line("_", r#"a "quoted" \ path"#, None),
+            r#"{"level":"info","target":"rocket::server","message":"a \"quoted\" \\ path"}"#);
+
+        assert_eq!(line("rocket", "line\nbreak\u{7}", None),
+            r#"{"level":"info","target":"rocket","message":"line\nbreak\u0007"}"#);
+
+        assert_eq!(line("launch_", "address: 127.0.0.1", None),
+            r#"{"level":"info","target":"launch","message":"address: 127.0.0.1","#.to_owned()
+            + r#""setting":"address","value":"127.0.0.1"}"#);
+
+        let request = RequestContext::new("abc".into(), "GET", "/a?b".into());
+        assert_eq!(line("_", "Matched: GET /a", Some(&request)),
+            r#"{"level":"info","target":"rocket::server","message":"Matched: GET /a","#.to_owned()
+            + r#""request_id":"abc","method":"GET","uri":"/a?b"}"#);
+    }
+
+    #[test]
+    fn test_json_line_route_and_status() {
+        let request = RequestContext::new("abc".into(), "GET", "/a".into());
+        let json = crate::async_test(REQUEST.scope(request, async {
+            set_route(Some("index"));
+            set_status(Status::Ok);
+            REQUEST.with(|request| line("_", "Response succeeded.", Some(request)))
+        }));
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["route"], "index");
+        assert_eq!(value["status"], 200);
+        assert!(value["duration_ms"].as_f64().unwrap() >= 0.0);
+    }
+}
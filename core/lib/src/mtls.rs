@@ -0,0 +1,218 @@
+//! Support for mutual TLS client certificates.
+//!
+//! When mutual TLS is configured via [`MutualTls`](crate::config::MutualTls),
+//! clients are asked to present a certificate during the TLS handshake which
+//! is verified against the configured CA certificates. The [`Certificate`]
+//! request guard retrieves the verified end-entity certificate, allowing
+//! handlers to inspect its subject, subject alternative names, and serial.
+//!
+//! This module is only available when the `mtls` feature is enabled.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::mtls::Certificate;
+//!
+//! #[get("/auth")]
+//! fn auth(cert: Certificate<'_>) -> String {
+//!     format!("Hello, {}! (serial {})", cert.subject(), cert.serial())
+//! }
+//!
+//! #[get("/auth", rank = 2)]
+//! fn unauth() -> &'static str {
+//!     "Please present a client certificate."
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::request::{FromRequest, Outcome, Request};
+use crate::http::Status;
+use crate::http::private::RawCertificate;
+
+/// Re-export of the `x509-parser` crate used to parse certificates.
+pub use x509_parser as x509;
+
+use x509::certificate::X509Certificate;
+use x509::extensions::GeneralName;
+use x509::x509::X509Name;
+
+/// A request guard for the verified client certificate.
+///
+/// The guard succeeds when the client presented a certificate that was
+/// verified against the configured CA certificates. It forwards when no client
+/// certificate was presented, which can only occur when mutual TLS is not
+/// [`mandatory`](crate::config::MutualTls::mandatory()), and fails with a status
+/// of `401 Unauthorized` if the presented certificate could not be parsed.
+///
+/// The full chain presented by the client is available via
+/// [`Certificate::chain()`].
+pub struct Certificate<'a> {
+    x509: X509Certificate<'a>,
+    chain: &'a [RawCertificate],
+}
+
+/// An error encountered while retrieving the client [`Certificate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The certificate could not be parsed as X.509.
+    Parse(String),
+    /// The certificate contained trailing data.
+    Trailing(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "failed to parse client certificate: {}", e),
+            Error::Trailing(n) => write!(f, "client certificate has {} trailing bytes", n),
+        }
+    }
+}
+
+impl std::error::Error for Error { }
+
+impl<'a> Certificate<'a> {
+    fn parse(chain: &'a [RawCertificate]) -> Option<Result<Self, Error>> {
+        let end_entity = chain.first()?;
+        let result = match x509::parse_x509_certificate(&end_entity.0) {
+            Ok((rem, _)) if !rem.is_empty() => Err(Error::Trailing(rem.len())),
+            Ok((_, x509)) => Ok(Certificate { x509, chain }),
+            Err(e) => Err(Error::Parse(e.to_string())),
+        };
+
+        Some(result)
+    }
+
+    /// Returns the subject of the certificate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::mtls::Certificate;
+    ///
+    /// #[get("/")]
+    /// fn common_name(cert: Certificate<'_>) -> Option<String> {
+    ///     let cn = cert.subject().iter_common_name().next()?;
+    ///     cn.as_str().ok().map(|s| s.to_string())
+    /// }
+    /// ```
+    pub fn subject(&self) -> &X509Name<'_> {
+        self.x509.subject()
+    }
+
+    /// Returns the issuer of the certificate.
+    pub fn issuer(&self) -> &X509Name<'_> {
+        self.x509.issuer()
+    }
+
+    /// Returns the serial number of the certificate as a colon-separated,
+    /// lowercase hexadecimal string, e.g., `10:01`.
+    pub fn serial(&self) -> String {
+        self.x509.raw_serial_as_string()
+    }
+
+    /// Returns the raw, big-endian bytes of the certificate's serial number.
+    pub fn raw_serial(&self) -> &'a [u8] {
+        self.x509.raw_serial()
+    }
+
+    /// Returns an iterator over the subject alternative names (SANs) in the
+    /// certificate. If the certificate has no subject alternative name
+    /// extension, the iterator is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::mtls::{Certificate, x509::extensions::GeneralName};
+    ///
+    /// #[get("/")]
+    /// fn dns_names(cert: Certificate<'_>) -> String {
+    ///     cert.subject_alt_names()
+    ///         .filter_map(|name| match name {
+    ///             GeneralName::DNSName(dns) => Some(*dns),
+    ///             _ => None
+    ///         })
+    ///         .collect::<Vec<_>>()
+    ///         .join(", ")
+    /// }
+    /// ```
+    pub fn subject_alt_names(&self) -> impl Iterator<Item = &GeneralName<'_>> + '_ {
+        self.x509.tbs_certificate.subject_alternative_name()
+            .ok()
+            .flatten()
+            .into_iter()
+            .flat_map(|ext| ext.value.general_names.iter())
+    }
+
+    /// Returns `true` if the certificate contains a DNS subject alternative
+    /// name equal to `name`, compared case-insensitively.
+    pub fn has_dns_name(&self, name: &str) -> bool {
+        self.subject_alt_names().any(|san| match san {
+            GeneralName::DNSName(dns) => dns.eq_ignore_ascii_case(name),
+            _ => false
+        })
+    }
+
+    /// Returns the parsed end-entity certificate.
+    pub fn as_x509(&self) -> &X509Certificate<'a> {
+        &self.x509
+    }
+
+    /// Returns the DER-encoded bytes of the end-entity certificate.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        &self.chain[0].0
+    }
+
+    /// Returns an iterator over the DER-encoded bytes of each certificate in
+    /// the full chain presented by the client, end-entity certificate first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::mtls::Certificate;
+    ///
+    /// #[get("/")]
+    /// fn chain_length(cert: Certificate<'_>) -> String {
+    ///     cert.chain().count().to_string()
+    /// }
+    /// ```
+    pub fn chain(&self) -> impl Iterator<Item = &'a [u8]> + 'a {
+        self.chain.iter().map(|cert| cert.as_ref())
+    }
+}
+
+impl fmt::Debug for Certificate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Certificate")
+            .field("subject", &self.subject().to_string())
+            .field("issuer", &self.issuer().to_string())
+            .field("serial", &self.serial())
+            .finish()
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Certificate<'a> {
+    type Error = Error;
+
+    async fn from_request(request: &'a Request<'r>) -> Outcome<Self, Self::Error> {
+        let chain = match request.connection.peer_certs {
+            Some(ref chain) => chain.as_slice(),
+            None => return Outcome::Forward(()),
+        };
+
+        match Certificate::parse(chain) {
+            Some(Ok(cert)) => Outcome::Success(cert),
+            Some(Err(e)) => {
+                error_!("{}", e);
+                Outcome::Failure((Status::Unauthorized, e))
+            }
+            None => Outcome::Forward(()),
+        }
+    }
+}
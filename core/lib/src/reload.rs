@@ -0,0 +1,320 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::config::TlsConfig;
+use crate::http::tls::ReloadableResolver;
+
+/// How often certificate and key files are polled for changes when `watch` is
+/// enabled.
+pub(crate) const WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A handle to reload the TLS certificate chain and private key of a running
+/// server.
+///
+/// A handle is retrieved via [`Rocket::tls_reloader()`]. Calling
+/// [`TlsReloader::reload()`] rereads the configured certificate chain and
+/// private key. New connections use the new certificate; established
+/// connections are unaffected. If the new certificate chain or key is invalid,
+/// an error is logged and the previous certificate remains in use.
+///
+/// Reloads can also be triggered by file changes and, on Unix, by `SIGHUP`;
+/// see [`TlsConfig`] for details.
+///
+/// [`Rocket::tls_reloader()`]: crate::Rocket::tls_reloader()
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// use rocket::{State, TlsReloader};
+///
+/// #[post("/reload")]
+/// fn reload(reloader: State<'_, TlsReloader>) -> &'static str {
+///     reloader.reload();
+///     "Reloading certificates..."
+/// }
+///
+/// #[rocket::main]
+/// async fn main() {
+///     let rocket = rocket::ignite().mount("/", routes![reload]);
+///     let reloader = rocket.tls_reloader();
+///     let _ = rocket.manage(reloader).launch().await;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TlsReloader(pub(crate) mpsc::Sender<()>);
+
+impl TlsReloader {
+    /// Requests that the TLS certificate chain and private key be reloaded.
+    /// This function returns immediately; the reload happens asynchronously.
+    /// Requests made while the server isn't running TLS are ignored.
+    #[inline]
+    pub fn reload(&self) {
+        // If a reload is already pending, it will pick up the latest files, so
+        // another reload would be redundant. If the server isn't running TLS,
+        // the channel is closed at launch. Either way, there's nothing to do.
+        if self.0.try_send(()).is_ok() {
+            info!("TLS certificate reload requested.");
+        }
+    }
+}
+
+#[cfg(unix)]
+type Hangup = tokio::signal::unix::Signal;
+
+#[cfg(not(unix))]
+type Hangup = std::convert::Infallible;
+
+/// Returns a `SIGHUP` listener if `config` enables reloading on `SIGHUP`.
+#[cfg(unix)]
+fn hangup_signal(config: &TlsConfig) -> Option<Hangup> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    if !config.reload_on_sighup {
+        return None;
+    }
+
+    match signal(SignalKind::hangup()) {
+        Ok(signal) => Some(signal),
+        Err(e) => {
+            warn!("Failed to enable TLS certificate reloading on `SIGHUP`.");
+            info_!("Error: {}", e);
+            None
+        }
+    }
+}
+
+/// Returns a `SIGHUP` listener if `config` enables reloading on `SIGHUP`.
+#[cfg(not(unix))]
+fn hangup_signal(config: &TlsConfig) -> Option<Hangup> {
+    if config.reload_on_sighup {
+        warn!("TLS certificate reloading on `SIGHUP` is only supported on Unix.");
+    }
+
+    None
+}
+
+/// Resolves when a `SIGHUP` is received. Never resolves when `signal` is
+/// `None`.
+async fn hangup(signal: &mut Option<Hangup>) {
+    match signal {
+        #[cfg(unix)]
+        Some(signal) => { signal.recv().await; }
+        #[cfg(not(unix))]
+        Some(never) => match *never { },
+        None => futures::future::pending().await,
+    }
+}
+
+/// Reloads the certificate chains and keys configured in `config` into
+/// `resolver` whenever a reload is requested via `requests`, when the files
+/// change, as polled every `watch_interval`, if `watch` is enabled, or on
+/// `SIGHUP` if `reload_on_sighup` is enabled. Returns once `shutdown` resolves
+/// or its sender is dropped.
+pub(crate) async fn reload_tls(
+    config: TlsConfig,
+    resolver: Arc<ReloadableResolver>,
+    mut requests: mpsc::Receiver<()>,
+    watch_interval: Duration,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    let mut signal = hangup_signal(&config);
+    let watch = config.watch && !config.modified().is_empty();
+    if config.watch && !watch {
+        warn!("TLS `watch` is enabled, but no certificate or key is a file.");
+    }
+
+    // A change is only reloaded once the files have stopped changing for a
+    // full interval so that partially rotated files are never loaded.
+    let mut interval = tokio::time::interval(watch_interval);
+    let (mut seen, mut loaded) = (config.modified(), config.modified());
+
+    loop {
+        let reason = tokio::select! {
+            _ = &mut shutdown => break,
+            Some(()) = requests.recv() => "reload requested",
+            _ = hangup(&mut signal) => "received SIGHUP",
+            _ = interval.tick(), if watch => {
+                let modified = config.modified();
+                if modified != seen {
+                    seen = modified;
+                    continue;
+                } else if modified == loaded {
+                    continue;
+                }
+
+                "files changed"
+            }
+        };
+
+        loaded = config.modified();
+        let result = config.to_native_config(vec![])
+            .and_then(|mut native| resolver.reload(&mut native));
+
+        match result {
+            Ok(()) => info!("Reloaded TLS certificates ({}).", reason),
+            Err(e) => {
+                error!("Failed to reload TLS certificates ({}).", reason);
+                info_!("{}", e);
+                info_!("The previous certificates remain in use.");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::sync::{mpsc, oneshot};
+
+    use crate::config::TlsConfig;
+    use crate::http::tls::{server_config, parse_certificates, ReloadableResolver};
+    use super::reload_tls;
+
+    macro_rules! private {
+        ($file:literal) => (
+            &include_bytes!(concat!("../../../examples/tls/private/", $file))[..]
+        )
+    }
+
+    const RSA: (&[u8], &[u8]) = (private!("cert.pem"), private!("key.pem"));
+    const ED25519: (&[u8], &[u8]) = (private!("ed25519_cert.pem"), private!("ed25519_key.pem"));
+
+    /// A directory holding a certificate chain and key, removed on drop.
+    struct Files(PathBuf);
+
+    impl Files {
+        fn new(name: &str, (cert, key): (&[u8], &[u8])) -> Files {
+            let dir = std::env::temp_dir()
+                .join(format!("rocket-reload-{}-{}", name, std::process::id()));
+
+            std::fs::create_dir_all(&dir).expect("create temporary directory");
+            let files = Files(dir);
+            files.install((cert, key));
+            files
+        }
+
+        fn install(&self, (cert, key): (&[u8], &[u8])) {
+            std::fs::write(self.0.join("cert.pem"), cert).expect("write certificate");
+            std::fs::write(self.0.join("key.pem"), key).expect("write key");
+        }
+
+        fn config(&self) -> TlsConfig {
+            TlsConfig::from_paths(self.0.join("cert.pem"), self.0.join("key.pem"))
+        }
+    }
+
+    impl Drop for Files {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn resolver(config: &TlsConfig) -> Arc<ReloadableResolver> {
+        let native = config.to_native_config(vec![]).expect("valid TLS config");
+        server_config(native).expect("valid certificates").1
+    }
+
+    /// Waits for `resolver` to present the leaf certificate in `cert`.
+    async fn presents(resolver: &ReloadableResolver, cert: &[u8]) {
+        let mut reader = std::io::Cursor::new(cert);
+        let expected = parse_certificates(&mut reader).expect("valid cert").remove(0);
+        let wait = async {
+            while resolver.certificate_chain(None)[0].0 != expected.0 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        };
+
+        tokio::time::timeout(Duration::from_secs(5), wait).await
+            .expect("certificate was not reloaded");
+    }
+
+    #[test]
+    fn test_reload_on_request() {
+        crate::async_test(async {
+            let files = Files::new("request", RSA);
+            let mut rocket = crate::ignite();
+            let requests = rocket.tls_reload_receiver.take().unwrap();
+            let reloader = rocket.tls_reloader();
+
+            let config = files.config();
+            let resolver = resolver(&config);
+            let (stop, shutdown) = oneshot::channel();
+            let interval = Duration::from_secs(60);
+            let task = reload_tls(config, resolver.clone(), requests, interval, shutdown);
+            let task = tokio::spawn(task);
+
+            files.install(ED25519);
+            reloader.reload();
+            reloader.reload();
+            presents(&resolver, ED25519.0).await;
+
+            files.install(RSA);
+            reloader.reload();
+            presents(&resolver, RSA.0).await;
+
+            stop.send(()).unwrap();
+            tokio::time::timeout(Duration::from_secs(5), task).await
+                .expect("reload task stopped").unwrap();
+        })
+    }
+
+    #[test]
+    fn test_reload_on_watch() {
+        crate::async_test(async {
+            let files = Files::new("watch", RSA);
+            let config = files.config().with_watch(true);
+            let resolver = resolver(&config);
+            let (_requests, receiver) = mpsc::channel(1);
+            let (stop, shutdown) = oneshot::channel();
+            let interval = Duration::from_millis(10);
+            let task = reload_tls(config, resolver.clone(), receiver, interval, shutdown);
+            let task = tokio::spawn(task);
+
+            files.install(ED25519);
+            presents(&resolver, ED25519.0).await;
+
+            files.install(RSA);
+            presents(&resolver, RSA.0).await;
+
+            drop(stop);
+            tokio::time::timeout(Duration::from_secs(5), task).await
+                .expect("reload task stopped").unwrap();
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_reload_on_sighup() {
+        crate::async_test(async {
+            let files = Files::new("sighup", RSA);
+            let config = files.config().with_reload_on_sighup(true);
+            let resolver = resolver(&config);
+            let (requests, receiver) = mpsc::channel(1);
+            let (_stop, shutdown) = oneshot::channel();
+            let interval = Duration::from_secs(60);
+            let task = reload_tls(config, resolver.clone(), receiver, interval, shutdown);
+            tokio::spawn(task);
+
+            // Wait for a requested reload so that the `SIGHUP` handler, which
+            // is installed before any reload, is known to be installed.
+            files.install(ED25519);
+            requests.send(()).await.unwrap();
+            presents(&resolver, ED25519.0).await;
+
+            files.install(RSA);
+            let status = std::process::Command::new("kill")
+                .args(&["-HUP", &std::process::id().to_string()])
+                .status()
+                .expect("run `kill`");
+
+            assert!(status.success());
+            presents(&resolver, RSA.0).await;
+        })
+    }
+}
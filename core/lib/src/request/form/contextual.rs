@@ -0,0 +1,155 @@
+use std::convert::Infallible;
+
+use crate::request::{FromForm, FromFormField, FormItems, FormItem};
+use crate::request::{FormFieldError, FormParseErrors};
+use crate::request::form::form_items::split_key;
+
+/// A form value that never fails to parse, along with the context needed to
+/// re-render the form when it is invalid.
+///
+/// `Contextual<T>` implements [`FromForm`] for every `T` that derives
+/// `FromForm`. Parsing always succeeds: when `T` parses, `value` is `Some`;
+/// when it doesn't, `value` is `None`. In both cases, `context` holds the
+/// submitted values of every field, the fields that parsed, and every error
+/// that occurred, so a handler can re-render the form with the user's input and
+/// an error message next to each invalid field.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{Form, Contextual};
+///
+/// #[derive(FromForm)]
+/// struct Signup {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// #[post("/signup", data = "<form>")]
+/// fn signup(form: Form<Contextual<'_, Signup>>) -> String {
+///     if let Some(ref signup) = form.value {
+///         return format!("Welcome, {}!", signup.name);
+///     }
+///
+///     let ctxt = &form.context;
+///     let name: String = ctxt.field("name").unwrap_or_default();
+///     let errors: Vec<_> = ctxt.errors().iter().map(|e| e.to_string()).collect();
+///     format!("Please fix your submission for {}: {}", name, errors.join(", "))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Contextual<'f, T> {
+    /// The parsed value, if the form parsed successfully.
+    pub value: Option<T>,
+    /// The form's submitted values and errors.
+    pub context: FormContext<'f>,
+}
+
+/// The submitted values and errors of a form, as collected by [`Contextual`].
+#[derive(Debug, Clone)]
+pub struct FormContext<'f> {
+    items: Vec<FormItem<'f>>,
+    errors: FormParseErrors<'f>,
+    strict: bool,
+}
+
+impl<'f> FormContext<'f> {
+    /// Returns every error that occurred while parsing the form. This is empty
+    /// if the form parsed successfully.
+    #[inline(always)]
+    pub fn errors(&self) -> &FormParseErrors<'f> {
+        &self.errors
+    }
+
+    /// Returns an iterator over the errors for the field at `path`, such as
+    /// `address.city` or `items[0].name`.
+    pub fn field_errors<'a>(&'a self, path: &'a str) -> impl Iterator<Item = &'a FormFieldError<'f>> {
+        self.errors.field(path)
+    }
+
+    /// Parses the field at `path`, such as `age`, `address`, or `items[0]`, as
+    /// a `T`, returning `None` if the field is missing and `T` has no default
+    /// or if it fails to parse. Use this to retrieve the fields that parsed
+    /// from a form that didn't.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::{FromForm, FormItems, Contextual};
+    ///
+    /// #[derive(FromForm)]
+    /// struct Signup {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// let mut items = FormItems::from("name=Bob&age=old");
+    /// let form = Contextual::<Signup>::from_form(&mut items, true).unwrap();
+    /// assert!(form.value.is_none());
+    /// assert_eq!(form.context.field::<String>("name"), Some("Bob".into()));
+    /// assert_eq!(form.context.field::<u8>("age"), None);
+    /// ```
+    pub fn field<T: FromFormField<'f>>(&self, path: &str) -> Option<T> {
+        let mut segments = vec![];
+        let mut rest = path;
+        while !rest.is_empty() {
+            let (segment, next) = split_key(rest);
+            segments.push(segment);
+            rest = next;
+        }
+
+        let items: Vec<_> = self.items.iter()
+            .filter_map(|item| {
+                let mut item = *item;
+                for segment in &segments {
+                    let (head, rest) = item.split_key();
+                    if head.url_decode_lossy() != *segment {
+                        return None;
+                    }
+
+                    item = rest;
+                }
+
+                Some(item)
+            })
+            .collect();
+
+        if items.is_empty() {
+            return T::default();
+        }
+
+        T::from_form_field(&mut FormItems::from(items), self.strict).ok()
+    }
+
+    /// Returns the URL decoded value last submitted for the field at `path`,
+    /// if any.
+    pub fn field_value(&self, path: &str) -> Option<String> {
+        self.field_values(path).last()
+    }
+
+    /// Returns an iterator over the URL decoded values submitted for the field
+    /// at `path`, in submission order.
+    pub fn field_values<'a>(&'a self, path: &'a str) -> impl Iterator<Item = String> + 'a {
+        self.items.iter()
+            .filter(move |item| item.full_key().url_decode_lossy() == path)
+            .map(|item| item.value.url_decode_lossy())
+    }
+}
+
+impl<'f, T> FromForm<'f> for Contextual<'f, T>
+    where T: FromForm<'f, Error = FormParseErrors<'f>>
+{
+    type Error = Infallible;
+
+    fn from_form(items: &mut FormItems<'f>, strict: bool) -> Result<Self, Infallible> {
+        let items: Vec<_> = items.collect();
+        let (value, errors) = match T::from_form(&mut FormItems::from(items.clone()), strict) {
+            Ok(value) => (Some(value), FormParseErrors::new()),
+            Err(errors) => (None, errors),
+        };
+
+        Ok(Contextual { value, context: FormContext { items, errors, strict } })
+    }
+}
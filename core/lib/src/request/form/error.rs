@@ -1,14 +1,57 @@
-use std::io;
+use std::{io, fmt};
+use std::borrow::Cow;
+use std::ops::Deref;
+
 use crate::http::RawStr;
+use crate::request::FormItem;
 
-/// Error returned by the [`FromForm`](crate::request::FromForm) derive on form
-/// parsing errors.
+/// The kind of a [`FormFieldError`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FormErrorKind {
+    /// The field's value failed to parse or validate.
+    BadValue,
+    /// The parse was strict and the field appeared in the incoming form string
+    /// but was unexpected.
+    ///
+    /// This error cannot occur when parsing is lenient.
+    Unknown,
+    /// The field was expected but is missing in the incoming form.
+    Missing,
+    /// The field's value parsed but failed a validator. The message describes
+    /// the failure, as in "length must be at least 8".
+    ///
+    /// See [`validate`](crate::request::validate) for details on validation.
+    Invalid(Cow<'static, str>),
+}
+
+impl fmt::Display for FormErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormErrorKind::BadValue => "invalid value".fmt(f),
+            FormErrorKind::Unknown => "unexpected field".fmt(f),
+            FormErrorKind::Missing => "missing field".fmt(f),
+            FormErrorKind::Invalid(message) => message.fmt(f),
+        }
+    }
+}
+
+/// A single form parsing error.
 ///
-/// If multiple errors occur while parsing a form, the first error in the
-/// following precedence, from highest to lowest, is returned:
+/// This is the error type the [`FromForm`](crate::request::FromForm) derive
+/// returned prior to collecting every error in a form. It remains available for
+/// manual `FromForm` implementations. To report such an error alongside those
+/// of derived implementations, convert it into a [`FormFieldError`] or
+/// [`FormParseErrors`] with `From`:
 ///
-///   * `BadValue` or `Unknown` in incoming form string field order
-///   * `Missing` in lexical field order
+/// ```rust
+/// use rocket::request::{FormParseError, FormFieldError, FormErrorKind};
+///
+/// let error = FormFieldError::from(FormParseError::BadValue("age".into(), "old".into()));
+/// assert_eq!(error, FormFieldError::new("age", Some("old".into()), FormErrorKind::BadValue));
+///
+/// let error = FormFieldError::from(FormParseError::Missing("name".into()));
+/// assert_eq!(error, FormFieldError::missing("name"));
+/// ```
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum FormParseError<'f> {
     /// The field named `.0` with value `.1` failed to parse or validate.
@@ -22,6 +65,179 @@ pub enum FormParseError<'f> {
     Missing(&'f RawStr),
 }
 
+/// An error in a single field of a form.
+///
+/// The `path` of a field is its key as submitted, URL decoded, such as
+/// `address.city` or `items[0].name`. The path of a missing field is built
+/// from the keys of its submitted siblings.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::request::{FormFieldError, FormErrorKind};
+///
+/// let kind = FormErrorKind::BadValue;
+/// let error = FormFieldError::new("items[0].quantity", Some("ten".into()), kind);
+/// assert_eq!(error.to_string(), "items[0].quantity: invalid value");
+///
+/// let error = FormFieldError::missing("address.city");
+/// assert_eq!(error.to_string(), "address.city: missing field");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FormFieldError<'f> {
+    /// The path of the field.
+    pub path: String,
+    /// The field's value, if it was present.
+    pub value: Option<&'f RawStr>,
+    /// The kind of error.
+    pub kind: FormErrorKind,
+}
+
+impl<'f> FormFieldError<'f> {
+    /// Creates a new error of kind `kind` for the field at `path` with value
+    /// `value`.
+    pub fn new<P>(path: P, value: Option<&'f RawStr>, kind: FormErrorKind) -> Self
+        where P: Into<String>
+    {
+        FormFieldError { path: path.into(), value, kind }
+    }
+
+    /// Creates a new error of kind `kind` for the field submitted as `item`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{FormItems, FormFieldError, FormErrorKind};
+    ///
+    /// let item = FormItems::from("items%5B0%5D=x").next().unwrap();
+    /// let error = FormFieldError::for_item(&item, FormErrorKind::Unknown);
+    /// assert_eq!(error.path, "items[0]");
+    /// assert_eq!(error.value, Some("x".into()));
+    /// ```
+    pub fn for_item(item: &FormItem<'f>, kind: FormErrorKind) -> Self {
+        FormFieldError::new(item.full_key().url_decode_lossy(), Some(item.value), kind)
+    }
+
+    /// Creates a new [`FormErrorKind::Missing`] error for the field at `path`.
+    pub fn missing<P: Into<String>>(path: P) -> Self {
+        FormFieldError::new(path, None, FormErrorKind::Missing)
+    }
+}
+
+impl<'f> From<FormParseError<'f>> for FormFieldError<'f> {
+    fn from(error: FormParseError<'f>) -> Self {
+        match error {
+            FormParseError::BadValue(name, value) => {
+                FormFieldError::new(name.url_decode_lossy(), Some(value), FormErrorKind::BadValue)
+            }
+            FormParseError::Unknown(name, value) => {
+                FormFieldError::new(name.url_decode_lossy(), Some(value), FormErrorKind::Unknown)
+            }
+            FormParseError::Missing(name) => FormFieldError::missing(name.url_decode_lossy()),
+        }
+    }
+}
+
+impl fmt::Display for FormFieldError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+/// Error returned by the [`FromForm`](crate::request::FromForm) derive on form
+/// parsing errors: every [`FormFieldError`] that occurred while parsing.
+///
+/// Errors are ordered by the fields they occurred in, in lexical field order,
+/// followed by errors for unexpected fields in incoming form string order.
+/// Errors in a nested field are reported with the nested field's path.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{FromForm, FormItems, FormFieldError, FormErrorKind};
+///
+/// #[derive(FromForm)]
+/// struct Person {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// let errors = Person::from_form(&mut FormItems::from("age=old"), true).err().unwrap();
+/// assert_eq!(errors.len(), 2);
+/// assert_eq!(errors[0], FormFieldError::missing("name"));
+/// assert_eq!(errors[1].kind, FormErrorKind::BadValue);
+/// assert_eq!(errors.field("age").next().unwrap().value, Some("old".into()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FormParseErrors<'f>(Vec<FormFieldError<'f>>);
+
+impl<'f> FormParseErrors<'f> {
+    /// Creates an empty list of errors.
+    pub fn new() -> Self {
+        FormParseErrors(vec![])
+    }
+
+    /// Appends `error` to the list.
+    pub fn push(&mut self, error: FormFieldError<'f>) {
+        self.0.push(error);
+    }
+
+    /// Returns an iterator over the errors for the field at `path`.
+    pub fn field<'a>(&'a self, path: &'a str) -> impl Iterator<Item = &'a FormFieldError<'f>> {
+        self.0.iter().filter(move |error| error.path == path)
+    }
+}
+
+impl<'f> Deref for FormParseErrors<'f> {
+    type Target = [FormFieldError<'f>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'f> From<FormFieldError<'f>> for FormParseErrors<'f> {
+    fn from(error: FormFieldError<'f>) -> Self {
+        FormParseErrors(vec![error])
+    }
+}
+
+impl<'f> From<FormParseError<'f>> for FormParseErrors<'f> {
+    fn from(error: FormParseError<'f>) -> Self {
+        FormParseErrors(vec![error.into()])
+    }
+}
+
+impl<'f> Extend<FormFieldError<'f>> for FormParseErrors<'f> {
+    fn extend<I: IntoIterator<Item = FormFieldError<'f>>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl<'f> IntoIterator for FormParseErrors<'f> {
+    type Item = FormFieldError<'f>;
+    type IntoIter = std::vec::IntoIter<FormFieldError<'f>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl fmt::Display for FormParseErrors<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str("; ")?;
+            }
+
+            error.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Error returned by the [`FromTransformedData`](crate::data::FromTransformedData) implementations of
 /// [`Form`](crate::request::Form) and [`LenientForm`](crate::request::LenientForm).
 #[derive(Debug)]
@@ -70,4 +286,4 @@ pub enum FormDataError<'f, E> {
 /// }
 /// # fn main() {}
 /// ```
-pub type FormError<'f> = FormDataError<'f, FormParseError<'f>>;
+pub type FormError<'f> = FormDataError<'f, FormParseErrors<'f>>;
@@ -113,6 +113,11 @@ pub enum FormItems<'f> {
     Cooked {
         items: &'f [FormItem<'f>],
         next_index: usize
+    },
+    #[doc(hidden)]
+    Owned {
+        items: Vec<FormItem<'f>>,
+        next_index: usize
     }
 }
 
@@ -209,6 +214,125 @@ impl<'f> FormItem<'f> {
     pub fn explode(&self) -> (&'f RawStr, &'f RawStr, &'f RawStr) {
         (self.raw, self.key, self.value)
     }
+
+    /// Returns the item's key as it appears in `raw`.
+    ///
+    /// This is usually the same as `key`. When a form is parsed into nested
+    /// structures, however, the items passed to each nested field's
+    /// [`FromFormField`](crate::request::FromFormField) implementation have
+    /// keys relative to that field, while `full_key()` remains the key that
+    /// was submitted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::FormItem;
+    ///
+    /// let item = FormItem {
+    ///     raw: "address.city=Paris".into(),
+    ///     key: "city".into(),
+    ///     value: "Paris".into(),
+    /// };
+    ///
+    /// assert_eq!(item.full_key(), "address.city");
+    /// ```
+    #[inline]
+    pub fn full_key(&self) -> &'f RawStr {
+        match self.raw.find('=') {
+            Some(i) => self.raw[..i].into(),
+            None => self.raw,
+        }
+    }
+
+    /// Returns the part of [`full_key()`](FormItem::full_key()) that precedes
+    /// `key`: the path of the field that the item is nested in.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::FormItem;
+    ///
+    /// let item = FormItem {
+    ///     raw: "address.city=Paris".into(),
+    ///     key: "city".into(),
+    ///     value: "Paris".into(),
+    /// };
+    ///
+    /// assert_eq!(item.prefix(), "address.");
+    /// ```
+    #[inline]
+    pub fn prefix(&self) -> &'f RawStr {
+        self.full_key().strip_suffix(self.key.as_str()).unwrap_or("").into()
+    }
+
+    /// Splits the first segment off of the item's key, returning the segment
+    /// and the item with its key relative to that segment.
+    ///
+    /// Keys are split on `.` and on `[` `]` brackets, which may be percent
+    /// encoded as `%5B` and `%5D`. A key without either is returned whole along
+    /// with an item whose key is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::FormItems;
+    ///
+    /// let item = FormItems::from("items[0].name=Rocket").next().unwrap();
+    ///
+    /// let (field, item) = item.split_key();
+    /// assert_eq!(field, "items");
+    /// assert_eq!(item.key, "[0].name");
+    ///
+    /// let (index, item) = item.split_key();
+    /// assert_eq!(index, "0");
+    /// assert_eq!(item.key, "name");
+    ///
+    /// let (name, item) = item.split_key();
+    /// assert_eq!(name, "name");
+    /// assert_eq!(item.key, "");
+    /// assert_eq!(item.full_key(), "items[0].name");
+    /// ```
+    pub fn split_key(&self) -> (&'f RawStr, FormItem<'f>) {
+        let (head, rest) = split_key(self.key.as_str());
+        (head.into(), FormItem { key: rest.into(), ..*self })
+    }
+}
+
+/// Returns the length of the (possibly percent-encoded) opening bracket, if
+/// `open`, or closing bracket, otherwise, at the start of `bytes`, if any.
+fn bracket(bytes: &[u8], open: bool) -> Option<usize> {
+    let (literal, encoded) = if open { (b'[', b'B') } else { (b']', b'D') };
+    match bytes {
+        [c, ..] if *c == literal => Some(1),
+        [b'%', b'5', c, ..] if c.to_ascii_uppercase() == encoded => Some(3),
+        _ => None
+    }
+}
+
+pub(crate) fn split_key(key: &str) -> (&str, &str) {
+    let bytes = key.as_bytes();
+    if let Some(open) = bracket(bytes, true) {
+        let close = (open..bytes.len())
+            .find_map(|i| bracket(&bytes[i..], false).map(|len| (i, len)));
+
+        return match close {
+            Some((i, len)) => {
+                let rest = &key[(i + len)..];
+                (&key[open..i], rest.strip_prefix('.').unwrap_or(rest))
+            }
+            None => (key, "")
+        };
+    }
+
+    for i in 0..bytes.len() {
+        if bytes[i] == b'.' {
+            return (&key[..i], &key[(i + 1)..]);
+        } else if bracket(&bytes[i..], true).is_some() {
+            return (&key[..i], &key[i..]);
+        }
+    }
+
+    (key, "")
 }
 
 impl FormItems<'_> {
@@ -246,6 +370,7 @@ impl FormItems<'_> {
         match self {
             FormItems::Raw { string, next_index } => *next_index >= string.len(),
             FormItems::Cooked { items, next_index } => *next_index >= items.len(),
+            FormItems::Owned { items, next_index } => *next_index >= items.len(),
         }
     }
 
@@ -293,6 +418,7 @@ impl FormItems<'_> {
         match self {
             FormItems::Raw { string, ref mut next_index } => *next_index = string.len(),
             FormItems::Cooked { items, ref mut next_index } => *next_index = items.len(),
+            FormItems::Owned { items, ref mut next_index } => *next_index = items.len(),
         }
     }
 }
@@ -318,6 +444,13 @@ impl<'f> From<&'f [FormItem<'f>]> for FormItems<'f> {
     }
 }
 
+impl<'f> From<Vec<FormItem<'f>>> for FormItems<'f> {
+    #[inline(always)]
+    fn from(items: Vec<FormItem<'f>>) -> FormItems<'f> {
+        FormItems::Owned { items, next_index: 0 }
+    }
+}
+
 fn raw<'f>(string: &mut &'f RawStr, index: &mut usize) -> Option<FormItem<'f>> {
     loop {
         let start = *index;
@@ -368,6 +501,15 @@ impl<'f> Iterator for FormItems<'f> {
                     None
                 }
             }
+            FormItems::Owned { items, ref mut next_index } => {
+                if *next_index < items.len() {
+                    let item = items[*next_index];
+                    *next_index += 1;
+                    Some(item)
+                } else {
+                    None
+                }
+            }
         }
     }
 }
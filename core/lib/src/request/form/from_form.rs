@@ -7,10 +7,12 @@ use crate::request::FormItems;
 ///
 /// This trait can be automatically derived. When deriving `FromForm`, every
 /// field in the structure must implement
-/// [`FromFormValue`](crate::request::FromFormValue). Rocket validates each field in
-/// the structure by calling its `FromFormValue` implementation. You may wish to
-/// implement `FromFormValue` for your own types for custom, automatic
-/// validation.
+/// [`FromFormField`](crate::request::FromFormField), which is implemented for
+/// every type that implements [`FromFormValue`](crate::request::FromFormValue),
+/// for other structures deriving `FromForm`, and for vectors and maps of these.
+/// Rocket validates each field in the structure by calling its `FromFormField`
+/// implementation. You may wish to implement `FromFormValue` for your own types
+/// for custom, automatic validation.
 ///
 /// ```rust
 /// # #![allow(deprecated, dead_code, unused_attributes)]
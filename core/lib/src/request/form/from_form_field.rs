@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::http::RawStr;
+use crate::request::{FormItems, FormItem, FromFormValue};
+use crate::request::{FormFieldError, FormParseErrors, FormErrorKind};
+
+/// Trait to parse a typed value from the form items that make up a field.
+///
+/// Fields in structs deriving [`FromForm`](crate::request::FromForm) are
+/// required to implement this trait. A field may be a single value, as in
+/// `name=Bob`, or may be made up of many items whose keys nest inside of the
+/// field's name, as in `address.city=Paris` or `tags[0]=a`. Rocket implements
+/// `FromFormField` for:
+///
+///   * **`T` _where_ `T: FromFormValue`**
+///
+///     Parses the value of an item named exactly as the field, as in
+///     `name=Bob`. If the item is repeated, every value must parse, and the
+///     last value is used. If the field is missing, the value is
+///     [`FromFormValue::default()`].
+///
+///   * **Structures deriving `FromForm`**
+///
+///     Parses the structure from items whose keys are the field name followed
+///     by a `.` or a bracketed key, as in `address.city=Paris` or
+///     `address[city]=Paris`. If the field is missing, the value is the
+///     structure parsed from an empty form, if every one of its fields has a
+///     default.
+///
+///   * **`Vec<T>` _where_ `T: FromFormField`**
+///
+///     Parses one `T` for each distinct index, as in `items[0].name=a` or
+///     `items.0.name=a`, and one `T` for each repetition of an item without an
+///     index, as in `tags=a&tags=b` or `tags[]=a&tags[]=b`. Elements with an
+///     index are ordered by it, followed by elements without one in the order
+///     in which they appear in the form. If the field is missing, the vector
+///     is empty.
+///
+///   * **`HashMap<K, V>` _where_ `K: FromFormValue + Eq + Hash`, `V:
+///     FromFormField`**
+///
+///     Parses one `V` for each distinct key, as in `scores[alice]=10` or
+///     `scores.alice=10`, parsing the key as a `K`. If the field is missing,
+///     the map is empty.
+///
+/// Additionally, a field of type `Option<T>` in a derived structure is `None`
+/// if the field is missing or its value fails to parse, and `Some` otherwise.
+/// Errors in fields nested in it, as in `billing.city`, fail the form. The
+/// default of any field in a derived structure can be overridden with
+/// `#[form(default = expr)]`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::collections::HashMap;
+///
+/// #[derive(FromForm)]
+/// struct Address {
+///     street: String,
+///     city: String,
+/// }
+///
+/// #[derive(FromForm)]
+/// struct Item {
+///     name: String,
+///     quantity: usize,
+/// }
+///
+/// #[derive(FromForm)]
+/// struct Order {
+///     address: Address,
+///     billing: Option<Address>,
+///     items: Vec<Item>,
+///     tags: Vec<String>,
+///     notes: HashMap<String, String>,
+/// }
+/// ```
+///
+/// An `Order` can be parsed from a form such as:
+///
+/// ```text
+/// address.street=Main+St&address.city=Paris
+///     &items[0].name=Apple&items[0].quantity=3
+///     &items[1].name=Pear&items[1].quantity=1
+///     &tags=fruit&tags=fresh&notes[door]=blue
+/// ```
+///
+/// # Implementing
+///
+/// The items passed to [`from_form_field()`](FromFormField::from_form_field())
+/// have keys _relative_ to the field: an item `address.city=Paris` is passed
+/// to the `address` field's implementation with a key of `city`. Use
+/// [`FormItem::split_key()`] to split the first segment off of a relative key
+/// and [`FormItem::full_key()`] to retrieve the key as it was submitted.
+pub trait FromFormField<'f>: Sized {
+    /// Parses an instance of `Self` from the form items that make up the field.
+    /// There is at least one such item.
+    ///
+    /// Extra form fields are allowed when `strict` is `false` and disallowed
+    /// when `strict` is `true`.
+    ///
+    /// # Errors
+    ///
+    /// If `Self` cannot be parsed, returns every error that occurred, each
+    /// with the full path of the offending field.
+    fn from_form_field(items: &mut FormItems<'f>, strict: bool) -> Result<Self, FormParseErrors<'f>>;
+
+    /// Returns a default value to be used when the field does not exist. If
+    /// this returns `None`, then the field is required. The default
+    /// implementation simply returns `None`.
+    #[inline(always)]
+    fn default() -> Option<Self> {
+        None
+    }
+}
+
+/// Returns the path of the field `name` nested in the field with the item
+/// prefix `prefix`.
+#[doc(hidden)]
+pub fn field_path(prefix: &RawStr, name: &str) -> String {
+    let prefix = prefix.url_decode_lossy();
+    match prefix.trim_end_matches('.') {
+        "" => name.into(),
+        prefix if name.is_empty() => prefix.into(),
+        prefix => format!("{}.{}", prefix, name),
+    }
+}
+
+/// Returns the values to report in an error for the field made up of `items`:
+/// every submitted value if the field isn't nested, or a single `None` if it is
+/// or if the field is missing.
+#[doc(hidden)]
+pub fn field_values<'f>(items: &[FormItem<'f>]) -> Vec<Option<&'f RawStr>> {
+    if items.is_empty() || items.iter().any(|item| !item.key.is_empty()) {
+        return vec![None];
+    }
+
+    items.iter().map(|item| Some(item.value)).collect()
+}
+
+impl<'f, T: FromFormValue<'f>> FromFormField<'f> for T {
+    fn from_form_field(items: &mut FormItems<'f>, strict: bool) -> Result<Self, FormParseErrors<'f>> {
+        let (mut value, mut prefix, mut errors) = (None, None, FormParseErrors::new());
+        for item in items {
+            if !item.key.is_empty() {
+                if strict {
+                    errors.push(FormFieldError::for_item(&item, FormErrorKind::Unknown));
+                }
+
+                prefix = Some(item.prefix());
+                continue;
+            }
+
+            match T::from_form_value(item.value) {
+                Ok(v) => value = Some(v),
+                Err(_) => errors.push(FormFieldError::for_item(&item, FormErrorKind::BadValue)),
+            }
+        }
+
+        match value.or_else(<T as FromFormValue<'f>>::default) {
+            Some(value) if errors.is_empty() => Ok(value),
+            Some(_) => Err(errors),
+            None => {
+                let path = field_path(prefix.unwrap_or_else(|| "".into()), "");
+                errors.push(FormFieldError::missing(path));
+                Err(errors)
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn default() -> Option<Self> {
+        <T as FromFormValue<'f>>::default()
+    }
+}
+
+/// Groups `items` by the first segment of their keys. Items with an empty
+/// first segment are each placed in their own group.
+fn group<'f>(items: &mut FormItems<'f>) -> Vec<(&'f str, Vec<FormItem<'f>>)> {
+    let mut groups: Vec<(&'f str, Vec<FormItem<'f>>)> = vec![];
+    let mut indices = HashMap::new();
+    for item in items {
+        let (key, item) = item.split_key();
+        if key.is_empty() {
+            groups.push((key.as_str(), vec![item]));
+        } else if let Some(&i) = indices.get(key.as_str()) {
+            groups[i].1.push(item);
+        } else {
+            indices.insert(key.as_str(), groups.len());
+            groups.push((key.as_str(), vec![item]));
+        }
+    }
+
+    groups
+}
+
+impl<'f, T: FromFormField<'f>> FromFormField<'f> for Vec<T> {
+    fn from_form_field(items: &mut FormItems<'f>, strict: bool) -> Result<Self, FormParseErrors<'f>> {
+        // Order indexed elements by index, then unindexed ones as they appear.
+        let mut groups = group(items);
+        groups.sort_by_key(|(key, _)| match key.parse::<usize>() {
+            Ok(index) => (false, index),
+            Err(_) => (true, 0),
+        });
+
+        let (mut values, mut errors) = (vec![], FormParseErrors::new());
+        for (_, items) in groups {
+            match T::from_form_field(&mut FormItems::from(items), strict) {
+                Ok(value) => values.push(value),
+                Err(e) => errors.extend(e),
+            }
+        }
+
+        if errors.is_empty() { Ok(values) } else { Err(errors) }
+    }
+
+    #[inline(always)]
+    fn default() -> Option<Self> {
+        Some(vec![])
+    }
+}
+
+impl<'f, K, V> FromFormField<'f> for HashMap<K, V>
+    where K: FromFormValue<'f> + Eq + Hash, V: FromFormField<'f>
+{
+    fn from_form_field(items: &mut FormItems<'f>, strict: bool) -> Result<Self, FormParseErrors<'f>> {
+        let (mut map, mut errors) = (HashMap::new(), FormParseErrors::new());
+        for (key, items) in group(items) {
+            let item = items[0];
+            if key.is_empty() {
+                if strict {
+                    errors.push(FormFieldError::for_item(&item, FormErrorKind::Unknown));
+                }
+
+                continue;
+            }
+
+            let key = match K::from_form_value(key.into()) {
+                Ok(key) => key,
+                Err(_) => {
+                    let path = item.full_key().url_decode_lossy();
+                    errors.push(FormFieldError::new(path, Some(key.into()), FormErrorKind::BadValue));
+                    continue;
+                }
+            };
+
+            match V::from_form_field(&mut FormItems::from(items), strict) {
+                Ok(value) => { map.insert(key, value); }
+                Err(e) => errors.extend(e),
+            }
+        }
+
+        if errors.is_empty() { Ok(map) } else { Err(errors) }
+    }
+
+    #[inline(always)]
+    fn default() -> Option<Self> {
+        Some(HashMap::new())
+    }
+}
+
+/// Parses the field named `name` of type `T` made up of `items`, used by
+/// `FromForm` derived implementations. `prefix` is the item prefix of the
+/// field's siblings. Errors, including a missing field without a default, are
+/// pushed to `errors`.
+#[doc(hidden)]
+pub fn parse_field<'f, T>(
+    items: Vec<FormItem<'f>>,
+    strict: bool,
+    prefix: &RawStr,
+    name: &str,
+    errors: &mut FormParseErrors<'f>,
+) -> Option<T>
+    where T: FromFormField<'f>
+{
+    if items.is_empty() {
+        let value = T::default();
+        if value.is_none() {
+            errors.push(FormFieldError::missing(field_path(prefix, name)));
+        }
+
+        return value;
+    }
+
+    T::from_form_field(&mut FormItems::from(items), strict)
+        .map_err(|e| errors.extend(e))
+        .ok()
+}
+
+/// Parses the field of type `Option<T>` made up of `items`, used by
+/// `FromForm` derived implementations. Errors in nested fields are pushed to
+/// `errors`.
+#[doc(hidden)]
+pub fn parse_optional_field<'f, T>(
+    items: Vec<FormItem<'f>>,
+    strict: bool,
+    errors: &mut FormParseErrors<'f>,
+) -> Option<Option<T>>
+    where T: FromFormField<'f>
+{
+    if items.is_empty() {
+        return Some(None);
+    }
+
+    // A value that fails to parse is `None`, but a nested field is reported.
+    let nested = items.iter().any(|item| !item.key.is_empty());
+    match T::from_form_field(&mut FormItems::from(items), strict) {
+        Ok(value) => Some(Some(value)),
+        Err(e) if nested => {
+            errors.extend(e);
+            None
+        }
+        Err(_) => Some(None),
+    }
+}
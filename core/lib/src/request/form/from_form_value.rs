@@ -1,10 +1,15 @@
 use std::str::FromStr;
+use std::convert::TryFrom;
+use std::ops::RangeBounds;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6, SocketAddr};
 use std::num::{
     NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
     NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
 };
 
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
+use time::{Date, Time, PrimitiveDateTime};
+
 use crate::http::RawStr;
 
 /// Trait to parse a typed value from a form value.
@@ -12,7 +17,9 @@ use crate::http::RawStr;
 /// This trait is used by Rocket's code generation in two places:
 ///
 ///   1. Fields in structs deriving [`FromForm`](crate::request::FromForm) are
-///      required to implement this trait.
+///      required to implement
+///      [`FromFormField`](crate::request::FromFormField), which every type
+///      implementing this trait does.
 ///   2. Types of dynamic query parameters (`?<param>`) are required to
 ///      implement this trait.
 ///
@@ -93,6 +100,32 @@ use crate::http::RawStr;
 ///     type returns successfully. Otherwise, the raw form value is returned as
 ///     the `Err` value.
 ///
+///   * **IpNet, Ipv4Net, Ipv6Net**
+///
+///     URL decodes the form value and parses it as a network in CIDR notation,
+///     as in `10.0.0.0/8`. Otherwise, the raw form value is returned as the
+///     `Err` value.
+///
+///   * **time::Date, time::Time, time::PrimitiveDateTime**
+///
+///     URL decodes the form value and parses it in the format submitted by an
+///     HTML `date`, `time`, or `datetime-local` input, respectively:
+///
+///       * `Date`: `YYYY-MM-DD`, as in `2021-03-04`
+///       * `Time`: `HH:MM[:SS[.FFF]]`, as in `10:30` or `10:30:15.5`
+///       * `PrimitiveDateTime`: a `Date` and `Time` separated by a `T`, as in
+///         `2021-03-04T10:30`
+///
+///     If the value is malformed or out of range, the raw form value is
+///     returned as the `Err` value.
+///
+///   * **std::time::Duration, time::Duration**
+///
+///     URL decodes the form value and parses it as a number of seconds with an
+///     optional fractional part of up to nine digits, as in `90` or `1.5`. A
+///     `time::Duration` may also be negative, as in `-1.5`. Otherwise, the raw
+///     form value is returned as the `Err` value.
+///
 ///   * **bool**
 ///
 ///     A value is validated successfully as `true` if the the form value is
@@ -183,6 +216,13 @@ pub trait FromFormValue<'v>: Sized {
     /// If this returns `None`, then the field is required. Otherwise, this
     /// should return `Some(default_value)`. The default implementation simply
     /// returns `None`.
+    ///
+    /// This is used for missing fields in derived [`FromForm`] structures,
+    /// unless overridden with `#[form(default = expr)]`, and for missing
+    /// single-value query parameters in routes. For instance, `bool` returns
+    /// `Some(false)` since browsers don't submit unchecked checkboxes.
+    ///
+    /// [`FromForm`]: crate::request::FromForm
     #[inline(always)]
     fn default() -> Option<Self> {
         None
@@ -260,7 +300,110 @@ macro_rules! impl_with_fromstr_encoded {
 }
 
 impl_with_fromstr_encoded!(
-    IpAddr, Ipv6Addr, SocketAddrV4, SocketAddrV6, SocketAddr
+    IpAddr, Ipv6Addr, SocketAddrV4, SocketAddrV6, SocketAddr,
+    IpNet, Ipv4Net, Ipv6Net
+);
+
+/// Parses `string` as an unsigned integer made up of a number of ASCII digits
+/// in `len`.
+fn digits<T: FromStr, R: RangeBounds<usize>>(string: &str, len: R) -> Option<T> {
+    if !len.contains(&string.len()) || !string.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    string.parse().ok()
+}
+
+/// Parses the digits following the decimal point of a number of seconds as a
+/// number of nanoseconds.
+fn parse_nanos(fraction: &str) -> Option<u32> {
+    let nanos: u32 = digits(fraction, 1..=9)?;
+    Some(nanos * 10u32.pow(9 - fraction.len() as u32))
+}
+
+/// Parses `YYYY-MM-DD`, the format of an HTML `date` input.
+fn parse_date(string: &str) -> Option<Date> {
+    let mut parts = string.splitn(3, '-');
+    let year = digits(parts.next()?, 4..)?;
+    let month = digits(parts.next()?, 2..=2)?;
+    let day = digits(parts.next()?, 2..=2)?;
+    Date::try_from_ymd(year, month, day).ok()
+}
+
+/// Parses `HH:MM[:SS[.FFF]]`, the format of an HTML `time` input.
+fn parse_time(string: &str) -> Option<Time> {
+    let (hms, nanos) = match string.find('.') {
+        Some(i) => (&string[..i], parse_nanos(&string[(i + 1)..])?),
+        None => (string, 0),
+    };
+
+    let mut parts = hms.split(':');
+    let hour = digits(parts.next()?, 2..=2)?;
+    let minute = digits(parts.next()?, 2..=2)?;
+    let second = match parts.next() {
+        Some(second) => digits(second, 2..=2)?,
+        None if hms.len() == string.len() => 0,
+        None => return None,
+    };
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Time::try_from_hms_nano(hour, minute, second, nanos).ok()
+}
+
+/// Parses a date and time separated by a `T` or a space, the format of an HTML
+/// `datetime-local` input.
+fn parse_date_time(string: &str) -> Option<PrimitiveDateTime> {
+    let i = string.find(&['T', ' '][..])?;
+    let date = parse_date(&string[..i])?;
+    let time = parse_time(&string[(i + 1)..])?;
+    Some(PrimitiveDateTime::new(date, time))
+}
+
+/// Parses a non-negative number of seconds with an optional fractional part,
+/// as in `90` or `1.5`.
+fn parse_duration(string: &str) -> Option<std::time::Duration> {
+    let (secs, nanos) = match string.find('.') {
+        Some(i) => (&string[..i], parse_nanos(&string[(i + 1)..])?),
+        None => (string, 0),
+    };
+
+    Some(std::time::Duration::new(digits(secs, 1..)?, nanos))
+}
+
+/// Parses a number of seconds with an optional sign and fractional part, as in
+/// `-90` or `1.5`.
+fn parse_signed_duration(string: &str) -> Option<time::Duration> {
+    let (negative, string) = match string.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, string),
+    };
+
+    let duration = time::Duration::try_from(parse_duration(string)?).ok()?;
+    Some(if negative { -duration } else { duration })
+}
+
+macro_rules! impl_with_parser {
+    ($($T:ty => $parse:ident),+) => ($(
+        impl<'v> FromFormValue<'v> for $T {
+            type Error = &'v RawStr;
+
+            #[inline]
+            fn from_form_value(v: &'v RawStr) -> Result<Self, Self::Error> {
+                v.url_decode().ok().and_then(|s| $parse(&s)).ok_or(v)
+            }
+        }
+    )+)
+}
+
+impl_with_parser!(
+    Date => parse_date,
+    Time => parse_time,
+    PrimitiveDateTime => parse_date_time,
+    std::time::Duration => parse_duration,
+    time::Duration => parse_signed_duration
 );
 
 impl<'v, T: FromFormValue<'v>> FromFormValue<'v> for Option<T> {
@@ -2,14 +2,20 @@
 
 mod form_items;
 mod from_form;
+mod from_form_field;
 mod from_form_value;
 mod lenient;
 mod error;
 mod form;
+mod contextual;
+pub mod validate;
 
 pub use self::form_items::{FormItems, FormItem};
 pub use self::from_form::FromForm;
+pub use self::from_form_field::FromFormField;
+#[doc(hidden)] pub use self::from_form_field::{parse_field, parse_optional_field, field_path, field_values};
 pub use self::from_form_value::FromFormValue;
 pub use self::form::Form;
 pub use self::lenient::LenientForm;
-pub use self::error::{FormError, FormParseError, FormDataError};
+pub use self::contextual::{Contextual, FormContext};
+pub use self::error::{FormError, FormParseError, FormFieldError, FormParseErrors, FormErrorKind, FormDataError};
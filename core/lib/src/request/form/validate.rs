@@ -0,0 +1,301 @@
+//! Validators for use with the `FromForm` derive's `field` attribute.
+//!
+//! A field of a structure deriving [`FromForm`](crate::request::FromForm) can
+//! be validated after it parses with one or more `#[field(validate = ...)]`
+//! attributes. The value of `validate` is a call to a function in this module,
+//! prefixed with `validate::`, or to any function in scope, _without_ its first
+//! argument: the derive passes a reference to the parsed field as the first
+//! argument. Unprefixed calls always resolve in the caller's scope, so a custom
+//! validator never conflicts with one of the same name in this module.
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! #[derive(FromForm)]
+//! struct Signup {
+//!     #[field(validate = validate::len(1..=32))]
+//!     username: String,
+//!     #[field(validate = validate::email())]
+//!     email: String,
+//!     #[field(validate = validate::range(13..))]
+//!     age: u8,
+//!     #[field(validate = validate::len(8..))]
+//!     password: String,
+//!     #[field(validate = validate::eq(&self.password))]
+//!     confirm: String,
+//!     #[field(validate = validate::one_of(&["free", "pro"]))]
+//!     plan: String,
+//!     #[field(validate = not_reserved())]
+//!     referrer: Option<String>,
+//! }
+//!
+//! fn not_reserved(name: &str) -> Result<(), &'static str> {
+//!     if name == "admin" { Err("is reserved") } else { Ok(()) }
+//! }
+//! ```
+//!
+//! A validator returns `Ok(())` if the value is valid and an `Err` with a
+//! message otherwise. The message of a failing validator is reported in a
+//! [`FormFieldError`](crate::request::FormFieldError) of kind
+//! [`FormErrorKind::Invalid`](crate::request::FormErrorKind::Invalid) along
+//! with the field's path and submitted value, once for each value of a field
+//! submitted more than once, such as a vector. Any error type that implements
+//! `Display` can be used as the message of a custom validator.
+//!
+//! Validators of a field of type `Option<T>` are passed a `&T` and only run
+//! when the field is present. A validator can refer to other fields in the
+//! structure via `self`, as `validate::eq(&self.password)` does
+//! above. Such validators
+//! run after every field has parsed successfully, even if other validators
+//! failed; all others run as soon as their field parses.
+//!
+//! The `pattern()` validator, which matches a regular expression, requires the
+//! `regex` feature to be enabled.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::{Bound, RangeBounds};
+
+use crate::http::RawStr;
+use crate::http::uri::Absolute;
+
+/// Trait implemented by values that have a length, for use with [`len()`].
+///
+/// The length of a string is the number of characters in it. The length of a
+/// collection is the number of elements in it.
+pub trait Len {
+    /// Returns the length of `self`.
+    fn len(&self) -> usize;
+}
+
+impl Len for str {
+    fn len(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl Len for String {
+    fn len(&self) -> usize {
+        Len::len(self.as_str())
+    }
+}
+
+impl Len for RawStr {
+    fn len(&self) -> usize {
+        Len::len(self.as_str())
+    }
+}
+
+impl<T> Len for [T] {
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+impl<T> Len for Vec<T> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+}
+
+impl<K, V, S> Len for HashMap<K, V, S> {
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+}
+
+impl<T: Len + ?Sized> Len for &T {
+    fn len(&self) -> usize {
+        T::len(self)
+    }
+}
+
+/// Describes the values in `range`, as in "between 1 and 5" or "at least 3".
+fn describe<T: Display, R: RangeBounds<T>>(range: &R) -> String {
+    let lower = match range.start_bound() {
+        Bound::Included(start) => Some(format!("at least {}", start)),
+        Bound::Excluded(start) => Some(format!("greater than {}", start)),
+        Bound::Unbounded => None,
+    };
+
+    let upper = match range.end_bound() {
+        Bound::Included(end) => Some(format!("at most {}", end)),
+        Bound::Excluded(end) => Some(format!("less than {}", end)),
+        Bound::Unbounded => None,
+    };
+
+    match (range.start_bound(), range.end_bound(), lower, upper) {
+        (Bound::Included(start), Bound::Included(end), ..) => {
+            format!("between {} and {}", start, end)
+        }
+        (.., Some(lower), Some(upper)) => format!("{} and {}", lower, upper),
+        (.., Some(bound), None) | (.., None, Some(bound)) => bound,
+        (.., None, None) => "anything".into(),
+    }
+}
+
+/// Validates that the length of `value`, as defined by [`Len`], is in `range`.
+///
+/// ```rust
+/// use rocket::request::validate::len;
+///
+/// assert!(len(&"Bob".to_string(), 1..=3).is_ok());
+/// assert!(len(&vec![1, 2], 3..).is_err());
+/// assert_eq!(len("", 1..).unwrap_err(), "length must be at least 1");
+/// ```
+pub fn len<T, R>(value: &T, range: R) -> Result<(), Cow<'static, str>>
+    where T: Len + ?Sized, R: RangeBounds<usize>
+{
+    if range.contains(&value.len()) {
+        Ok(())
+    } else {
+        Err(format!("length must be {}", describe(&range)).into())
+    }
+}
+
+/// Validates that `value` is in `range`.
+///
+/// ```rust
+/// use rocket::request::validate::range;
+///
+/// assert!(range(&18, 13..).is_ok());
+/// assert!(range(&1.5, 0.0..1.0).is_err());
+/// assert_eq!(range(&0, 1..=5).unwrap_err(), "must be between 1 and 5");
+/// ```
+pub fn range<T, R>(value: &T, range: R) -> Result<(), Cow<'static, str>>
+    where T: PartialOrd + Display, R: RangeBounds<T>
+{
+    if range.contains(value) {
+        Ok(())
+    } else {
+        Err(format!("must be {}", describe(&range)).into())
+    }
+}
+
+/// Validates that `value` is equal to one of `options`.
+///
+/// ```rust
+/// use rocket::request::validate::one_of;
+///
+/// assert!(one_of(&"pro".to_string(), &["free", "pro"]).is_ok());
+/// assert_eq!(one_of(&3, &[1, 2]).unwrap_err(), "must be one of 1, 2");
+/// ```
+pub fn one_of<T, O>(value: &T, options: &[O]) -> Result<(), Cow<'static, str>>
+    where T: PartialEq<O> + ?Sized, O: Display
+{
+    if options.iter().any(|option| value == option) {
+        return Ok(());
+    }
+
+    let options: Vec<_> = options.iter().map(|option| option.to_string()).collect();
+    Err(format!("must be one of {}", options.join(", ")).into())
+}
+
+/// Validates that `value` is equal to `other`, typically another field.
+///
+/// ```rust
+/// use rocket::request::validate::eq;
+///
+/// let password = "hunter22".to_string();
+/// assert!(eq(&"hunter22".to_string(), &password).is_ok());
+/// assert!(eq(&"hunter2".to_string(), &password).is_err());
+/// ```
+pub fn eq<T, U>(value: &T, other: &U) -> Result<(), Cow<'static, str>>
+    where T: PartialEq<U> + ?Sized, U: ?Sized
+{
+    if value == other {
+        Ok(())
+    } else {
+        Err("does not match".into())
+    }
+}
+
+/// Validates that `value` is shaped like an email address: a non-empty local
+/// part and a domain of at least two non-empty labels separated by an `@`,
+/// without whitespace.
+///
+/// This does not check that the address exists.
+///
+/// ```rust
+/// use rocket::request::validate::email;
+///
+/// assert!(email("bob@example.com").is_ok());
+/// assert!(email("bob@localhost").is_err());
+/// assert!(email("bob at example.com").is_err());
+/// ```
+pub fn email<S: AsRef<str> + ?Sized>(value: &S) -> Result<(), Cow<'static, str>> {
+    let value = value.as_ref();
+    let valid = match value.rfind('@') {
+        Some(_) if value.chars().any(char::is_whitespace) => false,
+        Some(i) => {
+            let (local, domain) = (&value[..i], &value[(i + 1)..]);
+            !local.is_empty()
+                && domain.contains('.')
+                && domain.split('.').all(|label| !label.is_empty())
+        }
+        None => false,
+    };
+
+    if valid { Ok(()) } else { Err("must be an email address".into()) }
+}
+
+/// Validates that `value` is shaped like an absolute URL with a host, such as
+/// `https://rocket.rs/guide`.
+///
+/// ```rust
+/// use rocket::request::validate::url;
+///
+/// assert!(url("https://rocket.rs/guide").is_ok());
+/// assert!(url("/guide").is_err());
+/// assert!(url("mailto:bob").is_err());
+/// ```
+pub fn url<S: AsRef<str> + ?Sized>(value: &S) -> Result<(), Cow<'static, str>> {
+    let has_host = Absolute::parse(value.as_ref()).ok()
+        .and_then(|uri| uri.authority().map(|auth| !auth.host().is_empty()))
+        .unwrap_or(false);
+
+    if has_host { Ok(()) } else { Err("must be a URL".into()) }
+}
+
+/// Validates that `value` matches the regular expression `pattern` in its
+/// entirety or in part; anchor the expression with `^` and `$` to match all of
+/// `value`. Compiled expressions are cached.
+///
+/// This validator is only available when the `regex` feature is enabled.
+///
+/// ```rust
+/// # #[cfg(feature = "regex")] {
+/// use rocket::request::validate::pattern;
+///
+/// assert!(pattern("bob_99", r"^[a-z0-9_]+$").is_ok());
+/// assert!(pattern("Bob!", r"^[a-z0-9_]+$").is_err());
+/// # }
+/// ```
+#[cfg(feature = "regex")]
+pub fn pattern<S>(value: &S, pattern: &'static str) -> Result<(), Cow<'static, str>>
+    where S: AsRef<str> + ?Sized
+{
+    use parking_lot::{Mutex, const_mutex};
+    use regex::Regex;
+
+    static CACHE: Mutex<Option<HashMap<&'static str, Regex>>> = const_mutex(None);
+
+    // Match outside of the lock: a `Regex` is cheap to clone.
+    let cached = CACHE.lock().as_ref().and_then(|cache| cache.get(pattern).cloned());
+    let regex = match cached {
+        Some(regex) => regex,
+        None => match Regex::new(pattern) {
+            Ok(regex) => {
+                CACHE.lock().get_or_insert_with(HashMap::new).insert(pattern, regex.clone());
+                regex
+            }
+            Err(e) => return Err(format!("has an invalid pattern: {}", e).into()),
+        }
+    };
+
+    if regex.is_match(value.as_ref()) {
+        Ok(())
+    } else {
+        Err(format!("must match the pattern `{}`", pattern).into())
+    }
+}
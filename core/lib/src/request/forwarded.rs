@@ -0,0 +1,329 @@
+//! Resolution of the client address, scheme, and host through trusted proxies.
+
+use std::fmt;
+use std::net::IpAddr;
+
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+
+use crate::http::HeaderMap;
+
+/// The header trusted proxies use to report the client's address, protocol,
+/// and host.
+///
+/// Only the configured header is read. Many proxies append to one header while
+/// passing any others a client sent through untouched, so reading any other
+/// header would let clients forge their address.
+///
+/// Headers are configured as strings: `"forwarded"`, `"x-forwarded"`, or
+/// `"x-real-ip"`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProxyHeader {
+    /// The RFC 7239 `Forwarded` header.
+    Forwarded,
+    /// The `X-Forwarded-For`, `X-Forwarded-Proto`, and `X-Forwarded-Host`
+    /// headers.
+    XForwarded,
+    /// The `X-Real-IP` header, which carries only the client's address.
+    XRealIp,
+}
+
+impl ProxyHeader {
+    fn as_str(&self) -> &str {
+        match self {
+            ProxyHeader::Forwarded => "forwarded",
+            ProxyHeader::XForwarded => "x-forwarded",
+            ProxyHeader::XRealIp => "x-real-ip",
+        }
+    }
+}
+
+impl fmt::Display for ProxyHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The information a single proxy recorded about the hop it received a request
+/// on, from a `Forwarded` element, the `X-Forwarded-*` headers, or `X-Real-IP`.
+#[derive(Debug, Default, PartialEq)]
+struct Hop<'a> {
+    /// The address the proxy received the request from. `None` when it is
+    /// `unknown` or obfuscated.
+    for_ip: Option<IpAddr>,
+    proto: Option<&'a str>,
+    host: Option<&'a str>,
+}
+
+/// The client information derived from a request through trusted proxies.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Forwarded<'a> {
+    pub client_ip: Option<IpAddr>,
+    pub proto: Option<&'a str>,
+    pub host: Option<&'a str>,
+}
+
+/// Splits `string` on `sep`, ignoring separators in quoted strings.
+fn split_unquoted(string: &str, sep: char) -> impl Iterator<Item = &str> {
+    let (mut quoted, mut escaped, mut start) = (false, false, 0);
+    let mut pieces = vec![];
+    for (i, c) in string.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == sep && !quoted => {
+                pieces.push(&string[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    pieces.push(&string[start..]);
+    pieces.into_iter().map(|s| s.trim()).filter(|s| !s.is_empty())
+}
+
+/// Parses a `for` node: an IPv4 address or bracketed IPv6 address, either with
+/// an optional port, or `unknown`, or an obfuscated identifier.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim_matches('"');
+    if let Some(rest) = node.strip_prefix('[') {
+        return rest.split(']').next()?.parse().ok();
+    }
+
+    // A bare IPv6 address, as found in `X-Forwarded-For`, has many colons.
+    match node.parse() {
+        Ok(ip) => Some(ip),
+        Err(_) => node.rsplitn(2, ':').nth(1)?.parse().ok(),
+    }
+}
+
+/// Returns the hops in the `Forwarded` headers, from the client outward.
+fn forwarded_hops<'a>(headers: &'a HeaderMap<'_>) -> Vec<Hop<'a>> {
+    let elements = headers.get("Forwarded").flat_map(|h| split_unquoted(h, ','));
+    elements.map(|element| {
+        let mut hop = Hop::default();
+        for pair in split_unquoted(element, ';') {
+            let (key, value) = match pair.find('=') {
+                Some(i) => (pair[..i].trim(), pair[(i + 1)..].trim()),
+                None => continue,
+            };
+
+            let value = value.trim_matches('"');
+            match key {
+                k if k.eq_ignore_ascii_case("for") => hop.for_ip = parse_node(value),
+                k if k.eq_ignore_ascii_case("proto") => hop.proto = Some(value),
+                k if k.eq_ignore_ascii_case("host") => hop.host = Some(value),
+                _ => {}
+            }
+        }
+
+        hop
+    }).collect()
+}
+
+/// Returns the hops in the `X-Forwarded-For`, `X-Forwarded-Proto`, and
+/// `X-Forwarded-Host` headers, from the client outward. When the latter two
+/// list one value per hop, each hop gets its own; otherwise, every hop gets
+/// the last value.
+fn x_forwarded_hops<'a>(headers: &'a HeaderMap<'_>) -> Vec<Hop<'a>> {
+    let list = |name| -> Vec<&'a str> {
+        headers.get(name).flat_map(|h| split_unquoted(h, ',')).collect()
+    };
+
+    let (fors, protos, hosts) = (list("X-Forwarded-For"), list("X-Forwarded-Proto"),
+        list("X-Forwarded-Host"));
+
+    // A proxy may set only `X-Forwarded-Proto` or `X-Forwarded-Host`.
+    let len = std::cmp::max(fors.len(), (!protos.is_empty() || !hosts.is_empty()) as usize);
+    let nth = |values: &[&'a str], i: usize| match values.len() == len {
+        true => values.get(i).copied(),
+        false => values.last().copied(),
+    };
+
+    (0..len)
+        .map(|i| Hop {
+            for_ip: fors.get(i).and_then(|node| parse_node(node)),
+            proto: nth(&protos, i),
+            host: nth(&hosts, i),
+        })
+        .collect()
+}
+
+/// Returns the single hop in the `X-Real-IP` header, if there is one.
+fn real_ip_hops<'a>(headers: &'a HeaderMap<'_>) -> Vec<Hop<'a>> {
+    headers.get_one("X-Real-IP")
+        .map(|ip| Hop { for_ip: ip.trim().parse().ok(), ..Hop::default() })
+        .into_iter()
+        .collect()
+}
+
+/// Derives the client's address, the request's protocol, and the requested
+/// host from the connection's `remote` address and the `header` in `headers`,
+/// believing only proxies whose addresses are in `trusted`.
+///
+/// Hops are walked from the nearest proxy outward, stopping at the first hop
+/// whose reported address isn't trusted, since any hops before it may have
+/// been forged.
+pub(crate) fn resolve<'a>(
+    remote: Option<IpAddr>,
+    headers: &'a HeaderMap<'_>,
+    header: ProxyHeader,
+    trusted: &[IpNet],
+) -> Forwarded<'a> {
+    let is_trusted = |ip: &IpAddr| trusted.iter().any(|net| net.contains(ip));
+    let mut forwarded = Forwarded { client_ip: remote, proto: None, host: None };
+    if !remote.map_or(false, |ip| is_trusted(&ip)) {
+        return forwarded;
+    }
+
+    let hops = match header {
+        ProxyHeader::Forwarded => forwarded_hops(headers),
+        ProxyHeader::XForwarded => x_forwarded_hops(headers),
+        ProxyHeader::XRealIp => real_ip_hops(headers),
+    };
+
+    for hop in hops.iter().rev() {
+        forwarded.proto = hop.proto.or(forwarded.proto);
+        forwarded.host = hop.host.or(forwarded.host);
+        match hop.for_ip {
+            Some(ip) => forwarded.client_ip = Some(ip),
+            None => break,
+        }
+
+        if !forwarded.client_ip.map_or(false, |ip| is_trusted(&ip)) {
+            break;
+        }
+    }
+
+    forwarded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::Header;
+
+    fn resolve_with<'a>(
+        remote: &str,
+        headers: &'a HeaderMap<'static>,
+        header: ProxyHeader,
+        trusted: &[&str],
+    ) -> Forwarded<'a> {
+        let trusted: Vec<IpNet> = trusted.iter().map(|net| net.parse().unwrap()).collect();
+        resolve(Some(remote.parse().unwrap()), headers, header, &trusted)
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap<'static> {
+        let mut headers = HeaderMap::new();
+        for &(name, value) in pairs {
+            headers.add(Header::new(name, value));
+        }
+
+        headers
+    }
+
+    fn ip(string: &str) -> Option<IpAddr> {
+        Some(string.parse().unwrap())
+    }
+
+    #[test]
+    fn test_untrusted_remote() {
+        let header = ProxyHeader::Forwarded;
+        let h = headers(&[
+            ("Forwarded", "for=1.1.1.1;proto=https;host=rocket.rs"),
+            ("X-Forwarded-For", "2.2.2.2"),
+            ("X-Real-IP", "3.3.3.3"),
+        ]);
+
+        let resolved = resolve_with("10.0.0.1", &h, header, &[]);
+        assert_eq!(resolved, Forwarded { client_ip: ip("10.0.0.1"), proto: None, host: None });
+
+        let resolved = resolve_with("10.0.0.1", &h, header, &["192.168.0.0/16"]);
+        assert_eq!(resolved.client_ip, ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn test_forwarded() {
+        let header = ProxyHeader::Forwarded;
+        let h = headers(&[
+            ("Forwarded", r#"for=1.1.1.1;proto=https;host=rocket.rs, for="[2001:db8::1]:80""#),
+            ("Forwarded", "for=10.0.0.2"),
+            ("X-Forwarded-For", "2.2.2.2"),
+        ]);
+
+        let resolved = resolve_with("10.0.0.1", &h, header, &["10.0.0.0/8"]);
+        assert_eq!(resolved.client_ip, ip("2001:db8::1"));
+        assert_eq!(resolved.proto, None);
+
+        let resolved = resolve_with("10.0.0.1", &h, header, &["10.0.0.0/8", "2001:db8::/32"]);
+        assert_eq!(resolved, Forwarded {
+            client_ip: ip("1.1.1.1"),
+            proto: Some("https"),
+            host: Some("rocket.rs"),
+        });
+
+        let h = headers(&[("Forwarded", "for=unknown;proto=https, for=10.0.0.2")]);
+        let resolved = resolve_with("10.0.0.1", &h, header, &["10.0.0.0/8"]);
+        assert_eq!(resolved.client_ip, ip("10.0.0.2"));
+        assert_eq!(resolved.proto, Some("https"));
+    }
+
+    #[test]
+    fn test_x_forwarded() {
+        let header = ProxyHeader::XForwarded;
+        let h = headers(&[
+            ("X-Forwarded-For", "6.6.6.6, 1.1.1.1"),
+            ("X-Forwarded-For", "10.0.0.3"),
+            ("X-Forwarded-Proto", "https"),
+            ("X-Forwarded-Host", "rocket.rs"),
+        ]);
+
+        // `6.6.6.6` was supplied by the client and can't be trusted.
+        let resolved = resolve_with("10.0.0.1", &h, header, &["10.0.0.0/8"]);
+        assert_eq!(resolved, Forwarded {
+            client_ip: ip("1.1.1.1"),
+            proto: Some("https"),
+            host: Some("rocket.rs"),
+        });
+
+        let h = headers(&[("X-Forwarded-Proto", "https")]);
+        let resolved = resolve_with("10.0.0.1", &h, header, &["10.0.0.1/32"]);
+        assert_eq!(resolved.client_ip, ip("10.0.0.1"));
+        assert_eq!(resolved.proto, Some("https"));
+
+        let h = headers(&[("X-Forwarded-For", "2001:db8::1")]);
+        let resolved = resolve_with("10.0.0.1", &h, header, &["10.0.0.1/32"]);
+        assert_eq!(resolved.client_ip, ip("2001:db8::1"));
+    }
+
+    #[test]
+    fn test_real_ip() {
+        let header = ProxyHeader::XRealIp;
+        let h = headers(&[("X-Real-IP", "1.1.1.1")]);
+        assert_eq!(resolve_with("10.0.0.1", &h, header, &["10.0.0.0/8"]).client_ip, ip("1.1.1.1"));
+        assert_eq!(resolve_with("10.0.0.1", &h, header, &[]).client_ip, ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn test_other_headers_ignored() {
+        // The proxy appends to `X-Forwarded-For`; `Forwarded` is the client's.
+        let h = headers(&[
+            ("Forwarded", "for=10.0.0.2, for=1.1.1.1;proto=https;host=evil.com"),
+            ("X-Forwarded-For", "2.2.2.2"),
+            ("X-Real-IP", "3.3.3.3"),
+        ]);
+
+        let resolved = resolve_with("10.0.0.1", &h, ProxyHeader::XForwarded, &["10.0.0.0/8"]);
+        assert_eq!(resolved, Forwarded { client_ip: ip("2.2.2.2"), proto: None, host: None });
+
+        let resolved = resolve_with("10.0.0.1", &h, ProxyHeader::XRealIp, &["10.0.0.0/8"]);
+        assert_eq!(resolved.client_ip, ip("3.3.3.3"));
+
+        let h = headers(&[("Forwarded", "for=1.1.1.1"), ("X-Real-IP", "3.3.3.3")]);
+        let resolved = resolve_with("10.0.0.1", &h, ProxyHeader::XForwarded, &["10.0.0.0/8"]);
+        assert_eq!(resolved.client_ip, ip("10.0.0.1"));
+    }
+}
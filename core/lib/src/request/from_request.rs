@@ -4,7 +4,7 @@ use std::net::{IpAddr, SocketAddr};
 use futures::future::BoxFuture;
 
 use crate::router::Route;
-use crate::request::Request;
+use crate::request::{Request, RequestId};
 use crate::outcome::{self, IntoOutcome};
 use crate::outcome::Outcome::*;
 
@@ -140,6 +140,12 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 ///     For information on when an `&Route` is available, see
 ///     [`Request::route()`].
 ///
+///   * **&RequestId**
+///
+///     Extracts the [`RequestId`] of the incoming request.
+///
+///     _This implementation always returns successfully._
+///
 ///   * **&CookieJar**
 ///
 ///     Returns a borrow to the [`CookieJar`] in the incoming request. Note that
@@ -414,6 +420,15 @@ impl<'a, 'r> FromRequest<'a, 'r> for &'r Route {
     }
 }
 
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for &'a RequestId {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'a Request<'r>) -> Outcome<Self, Self::Error> {
+        Success(request.id())
+    }
+}
+
 #[crate::async_trait]
 impl<'a, 'r> FromRequest<'a, 'r> for &'a CookieJar<'r> {
     type Error = std::convert::Infallible;
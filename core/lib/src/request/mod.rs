@@ -6,20 +6,26 @@ mod form;
 mod from_request;
 mod state;
 mod query;
+pub(crate) mod forwarded;
+mod request_id;
 
 #[cfg(test)]
 mod tests;
 
-#[doc(hidden)] pub use rocket_codegen::{FromForm, FromFormValue};
+#[doc(hidden)] pub use rocket_codegen::{FromForm, FromFormValue, FromParam};
 
 pub use self::request::Request;
+pub(crate) use self::request::ConnectionMeta;
 pub use self::from_request::{FromRequest, Outcome};
 pub use self::param::{FromParam, FromSegments};
-pub use self::form::{FromForm, FromFormValue};
-pub use self::form::{Form, LenientForm, FormItems, FormItem};
-pub use self::form::{FormError, FormParseError, FormDataError};
+pub use self::form::{FromForm, FromFormField, FromFormValue};
+#[doc(hidden)] pub use self::form::{parse_field, parse_optional_field, field_path, field_values};
+pub use self::form::validate;
+pub use self::form::{Form, LenientForm, Contextual, FormContext, FormItems, FormItem};
+pub use self::form::{FormError, FormParseError, FormFieldError, FormParseErrors, FormErrorKind, FormDataError};
 pub use self::state::State;
 pub use self::query::{Query, FromQuery};
+pub use self::request_id::RequestId;
 
 #[doc(inline)]
 pub use crate::response::flash::FlashMessage;
@@ -3,6 +3,9 @@ use std::path::PathBuf;
 use std::fmt::Debug;
 use std::borrow::Cow;
 
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
+
+use crate::request::FromFormValue;
 use crate::http::{RawStr, uri::{Segments, SegmentError}};
 
 /// Trait to convert a dynamic path segment string to a concrete value.
@@ -83,6 +86,14 @@ use crate::http::{RawStr, uri::{Segments, SegmentError}};
 ///     type returns successfully. Otherwise, the raw path segment is returned
 ///     in the `Err` value.
 ///
+///   * **IpNet, Ipv4Net, Ipv6Net, time::Date, time::Time,
+///     time::PrimitiveDateTime, std::time::Duration, time::Duration**
+///
+///     The path segment is decoded and parsed exactly as the corresponding
+///     [`FromFormValue`] implementation does. For instance, a `time::Date` is
+///     parsed from a segment such as `2021-03-04`. If the parse fails, the raw
+///     path segment is returned in the `Err` value.
+///
 ///   * **[`&RawStr`](RawStr)**
 ///
 ///     _This implementation always returns successfully._
@@ -116,6 +127,33 @@ use crate::http::{RawStr, uri::{Segments, SegmentError}};
 ///     The path segment is parsed by `T`'s `FromParam` implementation. The
 ///     returned `Result` value is returned.
 ///
+/// # Deriving
+///
+/// `FromParam` can be derived for enums whose variants have no fields, which
+/// parse from the (case-insensitive) name of a variant, and for tuple structs
+/// with one field, which parse with the field's implementation:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[derive(FromParam, UriDisplayPath)]
+/// enum Kind {
+///     #[param(value = "rss")]
+///     Rss,
+///     #[param(value = "json")]
+///     Json,
+/// }
+///
+/// #[derive(FromParam, UriDisplayPath)]
+/// struct Id(u32);
+///
+/// #[get("/feed/<kind>/<id>")]
+/// fn feed(kind: Kind, id: Id) { /* .. */ }
+///
+/// assert_eq!(uri!(feed: Kind::Json, Id(7)).to_string(), "/feed/json/7");
+/// ```
+///
+/// See the [`FromParam` derive](rocket_codegen::FromParam) for details.
+///
 /// # Example
 ///
 /// Say you want to parse a segment of the form:
@@ -254,6 +292,25 @@ impl_with_fromstr! {
     bool, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6, SocketAddr
 }
 
+macro_rules! impl_with_form_value {
+    ($($T:ty),+) => ($(
+        impl<'a> FromParam<'a> for $T {
+            type Error = &'a RawStr;
+
+            #[inline(always)]
+            fn from_param(param: &'a RawStr) -> Result<Self, Self::Error> {
+                <$T as FromFormValue<'a>>::from_form_value(param)
+            }
+        }
+    )+)
+}
+
+impl_with_form_value! {
+    IpNet, Ipv4Net, Ipv6Net,
+    time::Date, time::Time, time::PrimitiveDateTime,
+    std::time::Duration, time::Duration
+}
+
 impl<'a, T: FromParam<'a>> FromParam<'a> for Result<T, T::Error> {
     type Error = std::convert::Infallible;
 
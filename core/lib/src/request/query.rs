@@ -1,4 +1,5 @@
 use crate::request::{FormItems, FormItem, Form, LenientForm, FromForm};
+use crate::http::Status;
 
 /// Iterator over form items in a query string.
 ///
@@ -105,18 +106,20 @@ impl<'q> Iterator for Query<'q> {
 ///
 /// If the conversion fails, signaled by returning an `Err` from a `FromQuery`
 /// implementation, the incoming request will be forwarded to the next matching
-/// route, if any. For instance, in the `item` route above, if a query string is
-/// missing either a `name` or `account` key/value pair, or there is a query
-/// item with a key that is not `id`, `name`, or `account`, the request will be
-/// forwarded. Note that this strictness is imposed by the [`Form`] type. As an
-/// example, using the [`LenientForm`] type instead would allow extra form items
-/// to be ignored without forwarding. Alternatively, _not_ having a trailing
-/// parameter at all would result in the same.
+/// route, if any, unless [`FromQuery::failure_status()`] returns a status to
+/// fail the request with instead. For instance, in the `item` route above, if
+/// a query string is missing either a `name` or `account` key/value pair, or
+/// there is a query item with a key that is not `id`, `name`, or `account`,
+/// the request will be forwarded. Note that this strictness is imposed by the
+/// [`Form`] type. As an example, using the [`LenientForm`] type instead would
+/// allow extra form items to be ignored without forwarding. Alternatively,
+/// _not_ having a trailing parameter at all would result in the same.
 ///
 /// # Provided Implementations
 ///
 /// Rocket implements `FromQuery` for several standard types. Their behavior is
-/// documented here.
+/// documented here. None of them override [`FromQuery::failure_status()`], so
+/// a failure to parse any of them forwards the request.
 ///
 ///   * **Form&lt;T>** _where_ **T: FromForm**
 ///
@@ -196,6 +199,15 @@ pub trait FromQuery<'q>: Sized {
     /// Parses and validates an instance of `Self` from a query or returns an
     /// `Error` if parsing or validation fails.
     fn from_query(query: Query<'q>) -> Result<Self, Self::Error>;
+
+    /// Returns the status to fail the request with when parsing fails with
+    /// `error`, or `None` to forward the request instead. The default
+    /// implementation returns `None`.
+    #[inline(always)]
+    fn failure_status(error: &Self::Error) -> Option<Status> {
+        let _ = error;
+        None
+    }
 }
 
 impl<'q, T: FromForm<'q>> FromQuery<'q> for Form<T> {
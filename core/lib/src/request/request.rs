@@ -10,14 +10,16 @@ use futures::future::BoxFuture;
 use atomic::{Atomic, Ordering};
 
 use crate::request::{FromParam, FromSegments, FromRequest, Outcome};
-use crate::request::{FromFormValue, FormItems, FormItem};
+use crate::request::{FromFormValue, FormItems, FormItem, RequestId};
+use crate::request::forwarded::{self, Forwarded};
 
 use crate::{Rocket, Config, Shutdown, Route};
 use crate::http::{hyper, uri::{Origin, Segments}};
-use crate::http::{Method, Header, HeaderMap, uncased::UncasedStr};
+use crate::http::{Method, Version, Header, HeaderMap, uncased::UncasedStr};
 use crate::http::{RawStr, ContentType, Accept, MediaType, CookieJar, Cookie};
-use crate::http::private::{Indexed, SmallVec};
+use crate::http::private::{Indexed, SmallVec, Connection, RawCertificate};
 use crate::data::Limits;
+use crate::trace::Span;
 
 /// The type of an incoming web request.
 ///
@@ -28,11 +30,30 @@ use crate::data::Limits;
 pub struct Request<'r> {
     method: Atomic<Method>,
     uri: Origin<'r>,
+    version: Version,
     headers: HeaderMap<'r>,
-    remote: Option<SocketAddr>,
+    pub(crate) connection: ConnectionMeta,
     pub(crate) state: RequestState<'r>,
 }
 
+/// Information about the connection a request was received on.
+#[derive(Clone, Default)]
+pub(crate) struct ConnectionMeta {
+    pub remote: Option<SocketAddr>,
+    pub peer_certs: Option<Arc<Vec<RawCertificate>>>,
+    pub server_name: Option<Arc<str>>,
+}
+
+impl<C: Connection> From<&C> for ConnectionMeta {
+    fn from(conn: &C) -> Self {
+        ConnectionMeta {
+            remote: conn.remote_addr(),
+            peer_certs: conn.peer_certificates().map(Arc::new),
+            server_name: conn.server_name().map(Arc::from),
+        }
+    }
+}
+
 pub(crate) struct RequestState<'r> {
     pub config: &'r Config,
     pub managed: &'r Container,
@@ -43,6 +64,8 @@ pub(crate) struct RequestState<'r> {
     pub cookies: CookieJar<'r>,
     pub accept: Storage<Option<Accept>>,
     pub content_type: Storage<Option<ContentType>>,
+    pub request_id: Storage<RequestId>,
+    pub span: Span,
     pub cache: Arc<Container>,
 }
 
@@ -51,8 +74,9 @@ impl Request<'_> {
         Request {
             method: Atomic::new(self.method()),
             uri: self.uri.clone(),
+            version: self.version,
             headers: self.headers.clone(),
-            remote: self.remote.clone(),
+            connection: self.connection.clone(),
             state: self.state.clone(),
         }
     }
@@ -70,6 +94,8 @@ impl RequestState<'_> {
             cookies: self.cookies.clone(),
             accept: self.accept.clone(),
             content_type: self.content_type.clone(),
+            request_id: self.request_id.clone(),
+            span: self.span.clone(),
             cache: self.cache.clone(),
         }
     }
@@ -86,8 +112,9 @@ impl<'r> Request<'r> {
         let mut request = Request {
             uri,
             method: Atomic::new(method),
+            version: Version::Http11,
             headers: HeaderMap::new(),
-            remote: None,
+            connection: ConnectionMeta::default(),
             state: RequestState {
                 path_segments: SmallVec::new(),
                 query_items: None,
@@ -98,6 +125,8 @@ impl<'r> Request<'r> {
                 cookies: CookieJar::new(&rocket.config.secret_key),
                 accept: Storage::new(),
                 content_type: Storage::new(),
+                request_id: Storage::new(),
+                span: Span::none(),
                 cache: Arc::new(Container::new()),
             }
         };
@@ -181,17 +210,37 @@ impl<'r> Request<'r> {
         self.update_cached_uri_info();
     }
 
+    /// Returns the HTTP protocol version negotiated for the connection this
+    /// request was received on. For requests that did not originate from a
+    /// network connection, such as local requests, this is `HTTP/1.1`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// use rocket::http::Version;
+    ///
+    /// # Request::example(Method::Get, "/uri", |request| {
+    /// assert_eq!(request.version(), Version::Http11);
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
     /// Returns the address of the remote connection that initiated this
     /// request if the address is known. If the address is not known, `None` is
     /// returned.
     ///
     /// Because it is common for proxies to forward connections for clients, the
     /// remote address may contain information about the proxy instead of the
-    /// client. For this reason, proxies typically set the "X-Real-IP" header
-    /// with the client's true IP. To extract this IP from the request, use the
-    /// [`real_ip()`] or [`client_ip()`] methods.
+    /// client. For this reason, proxies typically report the client's true IP
+    /// via the "Forwarded", "X-Forwarded-For", or "X-Real-IP" headers. To
+    /// extract this IP from the request through trusted proxies, use the
+    /// [`client_ip()`] method.
     ///
-    /// [`real_ip()`]: #method.real_ip
     /// [`client_ip()`]: #method.client_ip
     ///
     /// # Example
@@ -205,7 +254,7 @@ impl<'r> Request<'r> {
     /// ```
     #[inline(always)]
     pub fn remote(&self) -> Option<SocketAddr> {
-        self.remote
+        self.connection.remote
     }
 
     /// Sets the remote address of `self` to `address`.
@@ -229,12 +278,76 @@ impl<'r> Request<'r> {
     /// ```
     #[inline(always)]
     pub fn set_remote(&mut self, address: SocketAddr) {
-        self.remote = Some(address);
+        self.connection.remote = Some(address);
+    }
+
+    /// Returns the server name the client requested via TLS Server Name
+    /// Indication (SNI), if any. The name is always lowercase. Returns `None`
+    /// if the request was not received over TLS or if the client did not send
+    /// a server name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// # Request::example(Method::Get, "/uri", |request| {
+    /// assert!(request.server_name().is_none());
+    /// # });
+    /// ```
+    ///
+    /// A request guard that only succeeds for requests to `api.rocket.rs`:
+    ///
+    /// ```rust
+    /// use rocket::request::{self, Request, FromRequest};
+    ///
+    /// struct Api;
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'a, 'r> FromRequest<'a, 'r> for Api {
+    ///     type Error = ();
+    ///
+    ///     async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+    ///         match req.server_name() {
+    ///             Some("api.rocket.rs") => request::Outcome::Success(Api),
+    ///             _ => request::Outcome::Forward(())
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn server_name(&self) -> Option<&str> {
+        self.connection.server_name.as_deref()
+    }
+
+    /// Sets the TLS SNI server name of `self` to `name`, lowercased.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// request.set_server_name("API.rocket.rs");
+    /// assert_eq!(request.server_name(), Some("api.rocket.rs"));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn set_server_name(&mut self, name: &str) {
+        self.connection.server_name = Some(name.to_ascii_lowercase().into());
     }
 
     /// Returns the IP address in the "X-Real-IP" header of the request if such
     /// a header exists and contains a valid IP address.
     ///
+    /// The header is returned as-is, irrespective of whether it was set by a
+    /// trusted proxy, and is thus trivially spoofable. Prefer [`client_ip()`],
+    /// which only believes headers set by proxies in
+    /// [`Config::trusted_proxies`].
+    ///
+    /// [`client_ip()`]: #method.client_ip
+    /// [`Config::trusted_proxies`]: crate::Config::trusted_proxies
+    ///
     /// # Example
     ///
     /// ```rust
@@ -257,12 +370,19 @@ impl<'r> Request<'r> {
             })
     }
 
-    /// Attempts to return the client's IP address by first inspecting the
-    /// "X-Real-IP" header and then using the remote connection's IP address.
+    /// Returns the client's IP address, as determined through trusted proxies.
     ///
-    /// If the "X-Real-IP" header exists and contains a valid IP address, that
-    /// address is returned. Otherwise, if the address of the remote connection
-    /// is known, that address is returned. Otherwise, `None` is returned.
+    /// If the remote connection's address is not in one of the networks in
+    /// [`Config::trusted_proxies`], which is empty by default, that address is
+    /// returned and all forwarding headers are ignored. Otherwise, the hops
+    /// recorded in the header named by [`Config::proxy_header`] are walked from
+    /// the nearest proxy outward, stopping at the first hop whose address
+    /// isn't trusted: that address is returned. Every other forwarding header
+    /// is ignored. If the address of the remote connection is unknown, `None`
+    /// is returned.
+    ///
+    /// [`Config::trusted_proxies`]: crate::Config::trusted_proxies
+    /// [`Config::proxy_header`]: crate::Config::proxy_header
     ///
     /// # Example
     ///
@@ -272,21 +392,84 @@ impl<'r> Request<'r> {
     /// # use std::net::{SocketAddr, IpAddr, Ipv4Addr};
     ///
     /// # Request::example(Method::Get, "/uri", |mut request| {
-    /// // starting without an "X-Real-IP" header or remote addresss
+    /// // starting without a remote addresss
     /// assert!(request.client_ip().is_none());
     ///
     /// // add a remote address; this is done by Rocket automatically
     /// request.set_remote("127.0.0.1:8000".parse().unwrap());
     /// assert_eq!(request.client_ip(), Some("127.0.0.1".parse().unwrap()));
     ///
-    /// // now with an X-Real-IP header
-    /// request.add_header(Header::new("X-Real-IP", "8.8.8.8"));
-    /// assert_eq!(request.client_ip(), Some("8.8.8.8".parse().unwrap()));
+    /// // forwarding headers are ignored since no proxies are trusted
+    /// request.add_header(Header::new("X-Forwarded-For", "8.8.8.8"));
+    /// assert_eq!(request.client_ip(), Some("127.0.0.1".parse().unwrap()));
     /// # });
     /// ```
     #[inline]
     pub fn client_ip(&self) -> Option<IpAddr> {
-        self.real_ip().or_else(|| self.remote().map(|r| r.ip()))
+        self.forwarded().client_ip
+    }
+
+    /// Returns the protocol the client used to make the request, as determined
+    /// through trusted proxies.
+    ///
+    /// If the remote connection is from a trusted proxy (see [`client_ip()`]),
+    /// the `proto` reported by the outermost trusted hop in the configured
+    /// "Forwarded" or "X-Forwarded-Proto" header is returned as-is. Otherwise,
+    /// returns `"https"` if TLS is enabled and `"http"` if it is not.
+    ///
+    /// [`client_ip()`]: #method.client_ip
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// # Request::example(Method::Get, "/uri", |request| {
+    /// assert_eq!(request.scheme(), "http");
+    /// # });
+    /// ```
+    #[inline]
+    pub fn scheme(&self) -> &str {
+        match self.forwarded().proto {
+            Some(proto) => proto,
+            None if self.state.config.tls_enabled() => "https",
+            None => "http",
+        }
+    }
+
+    /// Returns the host the client requested, as determined through trusted
+    /// proxies.
+    ///
+    /// If the remote connection is from a trusted proxy (see [`client_ip()`]),
+    /// the `host` reported by the outermost trusted hop in the configured
+    /// "Forwarded" or "X-Forwarded-Host" header is returned. Otherwise, the
+    /// value of the "Host" header, if any, is returned.
+    ///
+    /// [`client_ip()`]: #method.client_ip
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::{Header, Method};
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// assert_eq!(request.host(), None);
+    ///
+    /// request.add_header(Header::new("Host", "rocket.rs"));
+    /// request.add_header(Header::new("X-Forwarded-Host", "evil.com"));
+    /// assert_eq!(request.host(), Some("rocket.rs"));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn host(&self) -> Option<&str> {
+        self.forwarded().host.or_else(|| self.headers().get_one("Host"))
+    }
+
+    /// Resolves the client's address, protocol, and host through the proxies
+    /// in `trusted_proxies`.
+    fn forwarded(&self) -> Forwarded<'_> {
+        let (remote, config) = (self.remote().map(|addr| addr.ip()), self.state.config);
+        forwarded::resolve(remote, self.headers(), config.proxy_header, &config.trusted_proxies)
     }
 
     /// Returns a wrapped borrow to the cookies in `self`.
@@ -485,6 +668,48 @@ impl<'r> Request<'r> {
         &self.state.config.limits
     }
 
+    /// Returns the ID of this request.
+    ///
+    /// The ID is taken from the header named by the
+    /// [`request_id_header`](crate::Config::request_id_header) configuration
+    /// parameter if it's present and valid, and is otherwise generated. See
+    /// [`RequestId`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::{Header, Method};
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// request.add_header(Header::new("X-Request-Id", "abc-123"));
+    /// assert_eq!(request.id().as_str(), "abc-123");
+    /// # });
+    /// ```
+    pub fn id(&self) -> &RequestId {
+        self.state.request_id.get_or_set(|| {
+            let name = &self.state.config.request_id_header;
+            RequestId::resolve(self.headers().get_one(name))
+        })
+    }
+
+    /// Sets the ID of this request, if it hasn't yet been set or retrieved.
+    #[inline(always)]
+    pub(crate) fn set_id(&self, id: RequestId) {
+        self.state.request_id.set(id);
+    }
+
+    /// The span this request is being handled in.
+    #[inline(always)]
+    pub(crate) fn span(&self) -> &Span {
+        &self.state.span
+    }
+
+    /// Sets the span this request is being handled in.
+    #[inline(always)]
+    pub(crate) fn set_span(&mut self, span: Span) {
+        self.state.span = span;
+    }
+
     /// Get the presently matched route, if any.
     ///
     /// This method returns `Some` any time a handler or its guards are being
@@ -847,13 +1072,18 @@ impl<'r> Request<'r> {
     pub(crate) fn from_hyp(
         rocket: &'r Rocket,
         h_method: hyper::Method,
+        h_version: hyper::Version,
         h_headers: hyper::HeaderMap<hyper::HeaderValue>,
         h_uri: &'r hyper::Uri,
-        h_addr: SocketAddr,
+        connection: ConnectionMeta,
     ) -> Result<Request<'r>, String> {
         // Get a copy of the URI (only supports path-and-query) for later use.
-        let uri = match (h_uri.scheme(), h_uri.authority(), h_uri.path_and_query()) {
-            (None, None, Some(paq)) => paq.as_str(),
+        // HTTP/2 requests carry the scheme and authority as pseudo-headers,
+        // which hyper folds into the URI; we move the authority to `Host`.
+        let is_h2 = h_version == hyper::Version::HTTP_2;
+        let (uri, authority) = match (h_uri.scheme(), h_uri.authority(), h_uri.path_and_query()) {
+            (None, None, Some(paq)) => (paq.as_str(), None),
+            (Some(_), Some(auth), Some(paq)) if is_h2 => (paq.as_str(), Some(auth)),
             _ => return Err(format!("Bad URI: {}", h_uri)),
         };
 
@@ -863,12 +1093,19 @@ impl<'r> Request<'r> {
             None => return Err(format!("Unknown or invalid method: {}", h_method))
         };
 
+        // Ensure that the protocol version is known.
+        let version = match Version::from_hyp(h_version) {
+            Some(version) => version,
+            None => return Err(format!("Unknown protocol version: {:?}", h_version))
+        };
+
         // We need to re-parse the URI since we don't trust Hyper... :(
         let uri = Origin::parse(uri).map_err(|e| e.to_string())?;
 
         // Construct the request object.
         let mut request = Request::new(rocket, method, uri);
-        request.set_remote(h_addr);
+        request.version = version;
+        request.connection = connection;
 
         // Set the request cookies, if they exist.
         for header in h_headers.get_all("Cookie") {
@@ -892,6 +1129,12 @@ impl<'r> Request<'r> {
             request.add_header(header);
         }
 
+        if let Some(authority) = authority {
+            if !request.headers().contains("Host") {
+                request.add_header(Header::new("Host", authority.to_string()));
+            }
+        }
+
         Ok(request)
     }
 }
@@ -901,6 +1144,7 @@ impl fmt::Debug for Request<'_> {
         fmt.debug_struct("Request")
             .field("method", &self.method)
             .field("uri", &self.uri)
+            .field("version", &self.version)
             .field("headers", &self.headers())
             .field("remote", &self.remote())
             .field("cookies", &self.cookies())
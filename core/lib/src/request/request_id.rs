@@ -0,0 +1,103 @@
+use std::fmt;
+
+/// An identifier for a request, for correlating logs and responses.
+///
+/// Every request is assigned an ID. If the request carries a valid ID in the
+/// header named by the [`request_id_header`](crate::Config::request_id_header)
+/// configuration parameter, `X-Request-Id` by default, that ID is used.
+/// Otherwise, a random ID of 32 hexadecimal digits is generated. A valid ID is
+/// between 1 and 128 characters of visible ASCII.
+///
+/// The ID is echoed in the same header of every response, including responses
+/// from catchers, and tags every log message emitted while the request is
+/// being handled in either [log format](crate::config::LogFormat): `pretty`
+/// logs prefix each line with it, and `json` logs include it as `request_id`.
+///
+/// # Request Guard
+///
+/// A `&RequestId` can be retrieved via [`Request::id()`] or used as a request
+/// guard. As a guard, it always succeeds:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::RequestId;
+///
+/// #[get("/")]
+/// fn index(id: &RequestId) -> String {
+///     format!("Handling request {}.", id)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// The maximum length of an ID accepted from a request.
+    const MAX_LEN: usize = 128;
+
+    /// Generates a new, random ID.
+    pub(crate) fn generate() -> RequestId {
+        RequestId(format!("{:032x}", rand::random::<u128>()))
+    }
+
+    /// Returns an ID for a request whose request ID header has the value
+    /// `incoming`, if any: `incoming` if it is a valid ID, or a new ID if not.
+    pub(crate) fn resolve(incoming: Option<&str>) -> RequestId {
+        match incoming {
+            Some(id) if Self::is_valid(id) => RequestId(id.into()),
+            _ => RequestId::generate(),
+        }
+    }
+
+    fn is_valid(id: &str) -> bool {
+        (1..=Self::MAX_LEN).contains(&id.len()) && id.bytes().all(|b| b.is_ascii_graphic())
+    }
+
+    /// Returns the ID as a string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// # Request::example(Method::Get, "/uri", |request| {
+    /// let id = request.id();
+    /// assert_eq!(id.as_str().len(), 32);
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    #[inline(always)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl AsRef<str> for RequestId {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RequestId;
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(RequestId::resolve(Some("abc-123")).as_str(), "abc-123");
+
+        let long = "a".repeat(129);
+        for invalid in &["", "a b", "a\"\n", &long] {
+            let id = RequestId::resolve(Some(invalid));
+            assert_ne!(id.as_str(), *invalid);
+            assert_eq!(id.as_str().len(), 32);
+        }
+
+        assert_ne!(RequestId::resolve(None), RequestId::resolve(None));
+    }
+}
@@ -2,6 +2,7 @@ use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::collections::HashMap;
 
 use crate::{Rocket, Request, Config};
+use crate::request::ConnectionMeta;
 use crate::http::hyper;
 
 macro_rules! assert_headers {
@@ -21,7 +22,9 @@ macro_rules! assert_headers {
 
         // Dispatch the request and check that the headers are what we expect.
         let r = Rocket::custom(Config::default());
-        let req = Request::from_hyp(&r, h_method, h_headers, &h_uri, h_addr).unwrap();
+        let h_version = hyper::Version::HTTP_11;
+        let conn = ConnectionMeta { remote: Some(h_addr), ..Default::default() };
+        let req = Request::from_hyp(&r, h_method, h_version, h_headers, &h_uri, conn).unwrap();
         let actual_headers = req.headers();
         for (key, values) in expected.iter() {
             let actual: Vec<_> = actual_headers.get(key).collect();
@@ -46,3 +49,22 @@ fn test_multiple_headers_merge_into_one_from_hyp() {
     assert_headers!("friend" => ["alice"], "friend" => ["bob"], "friend" => ["carol"]);
     assert_headers!("friend" => ["alice"], "friend" => ["bob"], "enemy" => ["carol"]);
 }
+
+#[test]
+fn test_http2_authority_becomes_host() {
+    let rocket = Rocket::custom(Config::default());
+    let h_uri = "https://rocket.rs/hello?a=b".parse().unwrap();
+    let conn = ConnectionMeta::default();
+    let req = Request::from_hyp(&rocket, hyper::Method::GET, hyper::Version::HTTP_2,
+        hyper::HeaderMap::new(), &h_uri, conn).unwrap();
+
+    assert_eq!(req.uri().path(), "/hello");
+    assert_eq!(req.uri().query(), Some("a=b"));
+    assert_eq!(req.headers().get_one("Host"), Some("rocket.rs"));
+
+    let conn = ConnectionMeta::default();
+    let res = Request::from_hyp(&rocket, hyper::Method::GET, hyper::Version::HTTP_11,
+        hyper::HeaderMap::new(), &h_uri, conn);
+
+    assert!(res.is_err());
+}
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use yansi::Paint;
 use state::Container;
 use figment::Figment;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Semaphore};
 use futures::future::FutureExt;
 
 use crate::logger;
@@ -28,6 +29,11 @@ pub struct Rocket {
     pub(crate) fairings: Fairings,
     pub(crate) shutdown_receiver: Option<mpsc::Receiver<()>>,
     pub(crate) shutdown_handle: Shutdown,
+    pub(crate) request_permits: Option<Arc<Semaphore>>,
+    #[cfg(feature = "tls")]
+    pub(crate) tls_reload_receiver: Option<mpsc::Receiver<()>>,
+    #[cfg(feature = "tls")]
+    pub(crate) tls_reloader: crate::TlsReloader,
 }
 
 impl Rocket {
@@ -82,11 +88,13 @@ impl Rocket {
     #[inline]
     pub fn custom<T: figment::Provider>(provider: T) -> Rocket {
         let (config, figment) = (Config::from(&provider), Figment::from(provider));
-        logger::try_init(config.log_level, config.cli_colors, false);
+        logger::try_init(config.log_level, config.log_format, config.cli_colors, false);
         config.pretty_print(&figment);
 
         let managed_state = Container::new();
         let (shutdown_sender, shutdown_receiver) = mpsc::channel(1);
+        #[cfg(feature = "tls")]
+        let (reload_sender, reload_receiver) = mpsc::channel(1);
         Rocket {
             config, figment,
             managed_state,
@@ -96,6 +104,11 @@ impl Rocket {
             catchers: HashMap::new(),
             fairings: Fairings::new(),
             shutdown_receiver: Some(shutdown_receiver),
+            request_permits: None,
+            #[cfg(feature = "tls")]
+            tls_reload_receiver: Some(reload_receiver),
+            #[cfg(feature = "tls")]
+            tls_reloader: crate::TlsReloader(reload_sender),
         }
     }
 
@@ -108,9 +121,9 @@ impl Rocket {
     /// Panics if the `base` mount point is not a valid static path: a valid
     /// origin URI without dynamic parameters.
     ///
-    /// Panics if any route's URI is not a valid origin URI. This kind of panic
-    /// is guaranteed not to occur if the routes were generated using Rocket's
-    /// code generation.
+    /// Panics if any route's URI is not a valid origin URI or its concurrency
+    /// limit is `0`. This kind of panic is guaranteed not to occur if the
+    /// routes were generated using Rocket's code generation.
     ///
     /// # Examples
     ///
@@ -178,6 +191,11 @@ impl Rocket {
                     panic!("Invalid route URI.");
                 });
 
+            if route.concurrency == Some(0) {
+                error_!("Route `{}` has a concurrency limit of `0`.", route);
+                panic!("Invalid route concurrency limit.");
+            }
+
             info_!("{}", route);
             self.router.add(route);
         }
@@ -326,6 +344,11 @@ impl Rocket {
 
     /// Returns the active configuration.
     ///
+    /// When called during or after launch, such as from an `on_launch`
+    /// fairing, the `address` and `port` reflect the local address the server
+    /// actually bound to. In particular, when `port` is configured as `0`, the
+    /// port chosen by the operating system is returned.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -470,6 +493,29 @@ impl Rocket {
         self.shutdown_handle.clone()
     }
 
+    /// Returns a handle that can be used to reload the TLS certificate chain
+    /// and private key once the server is running. Reload requests made
+    /// before launch are handled once the server has launched. Requests made
+    /// to a server launched without TLS are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// let rocket = rocket::ignite();
+    /// let reloader = rocket.tls_reloader();
+    ///
+    /// // Later, perhaps when a certificate rotation job completes:
+    /// reloader.reload();
+    /// # });
+    /// ```
+    #[cfg(feature = "tls")]
+    #[cfg_attr(nightly, doc(cfg(feature = "tls")))]
+    #[inline(always)]
+    pub fn tls_reloader(&self) -> crate::TlsReloader {
+        self.tls_reloader.clone()
+    }
+
     /// Perform "pre-launch" checks: verify that there are no routing colisions
     /// and that there were no fairing failures.
     pub(crate) async fn prelaunch_check(&mut self) -> Result<(), Error> {
@@ -510,7 +556,7 @@ impl Rocket {
     pub async fn launch(mut self) -> Result<(), Error> {
         use std::net::ToSocketAddrs;
         use futures::future::Either;
-        use crate::http::private::bind_tcp;
+        use crate::http::private::{bind_tcp, LimitedListener, ProxyProtocolListener};
 
         self.prelaunch_check().await?;
 
@@ -527,24 +573,60 @@ impl Rocket {
             false => futures::future::pending().boxed(),
         };
 
+        // Stop accepting connections while `max_connections` are open,
+        // counting those whose handshakes are still in progress.
+        let max_connections = self.config.max_connections;
+        let handshake_timeout = self.config.timeouts.handshake_duration();
+
         #[cfg(feature = "tls")]
         let server = {
-            use crate::http::tls::bind_tls;
+            use crate::http::tls::{TlsListener, server_config};
 
             if let Some(tls_config) = &self.config.tls {
-                let (certs, key) = tls_config.to_readers().map_err(ErrorKind::Io)?;
-                let l = bind_tls(addr, certs, key).await.map_err(ErrorKind::Bind)?;
-                self.listen_on(l).boxed()
+                let alpn = self.config.http2.alpn_protocols();
+                let config = tls_config.to_native_config(alpn).map_err(ErrorKind::Tls)?;
+                let (config, resolver) = server_config(config).map_err(ErrorKind::Tls)?;
+                let l = bind_tcp(addr).await.map_err(ErrorKind::Bind)?;
+                let l = LimitedListener::new(l, max_connections);
+                let l = TlsListener::new(l, config, self.config.proxy_protocol, handshake_timeout);
+                // Stop reloading certificates once the server has shut down.
+                let (stop_reload, shutdown) = tokio::sync::oneshot::channel();
+                if let Some(requests) = self.tls_reload_receiver.take() {
+                    use crate::reload::{reload_tls, WATCH_INTERVAL};
+
+                    let config = tls_config.clone();
+                    tokio::spawn(reload_tls(config, resolver, requests, WATCH_INTERVAL, shutdown));
+                }
+
+                self.listen_on(l).map(move |result| {
+                    let _ = stop_reload.send(());
+                    result
+                }).boxed()
             } else {
+                // Nothing reloads certificates without TLS. Closing the channel
+                // makes `TlsReloader::reload()` ignore requests silently.
+                self.tls_reload_receiver = None;
+
                 let l = bind_tcp(addr).await.map_err(ErrorKind::Bind)?;
-                self.listen_on(l).boxed()
+                let l = LimitedListener::new(l, max_connections);
+                match self.config.proxy_protocol {
+                    true => {
+                        let l = ProxyProtocolListener::new(l, handshake_timeout);
+                        self.listen_on(l).boxed()
+                    }
+                    false => self.listen_on(l).boxed(),
+                }
             }
         };
 
         #[cfg(not(feature = "tls"))]
         let server = {
             let l = bind_tcp(addr).await.map_err(ErrorKind::Bind)?;
-            self.listen_on(l).boxed()
+            let l = LimitedListener::new(l, max_connections);
+            match self.config.proxy_protocol {
+                true => self.listen_on(ProxyProtocolListener::new(l, handshake_timeout)).boxed(),
+                false => self.listen_on(l).boxed(),
+            }
         };
 
         match futures::future::select(shutdown_signal, server).await {
@@ -2,6 +2,9 @@ mod collider;
 mod route;
 
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
 
 use crate::request::Request;
 use crate::http::Method;
@@ -22,7 +25,8 @@ impl Router {
         Router { routes: HashMap::new() }
     }
 
-    pub fn add(&mut self, route: Route) {
+    pub fn add(&mut self, mut route: Route) {
+        route.permits = route.concurrency.map(|n| Arc::new(Semaphore::new(n)));
         let selector = route.method;
         let entries = self.routes.entry(selector).or_insert_with(|| vec![]);
         let i = entries.binary_search_by_key(&route.rank, |r| r.rank)
@@ -1,5 +1,8 @@
 use std::fmt::{self, Display};
 use std::convert::From;
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
 
 use yansi::Paint;
 
@@ -31,6 +34,16 @@ pub struct Route {
     pub rank: isize,
     /// The media type this route matches against, if any.
     pub format: Option<MediaType>,
+    /// The number of seconds this route's handler may take, overriding the
+    /// configured `timeouts.handler`, if any. `Some(0)` disables the timeout.
+    pub timeout: Option<u32>,
+    /// The maximum number of requests this route handles at once, if any.
+    /// Requests beyond the limit are forwarded to the next matching route and,
+    /// if no route handles them, rejected with a `503 Service Unavailable`.
+    /// A limit of `Some(0)` is invalid: mounting such a route panics.
+    pub concurrency: Option<usize>,
+    /// Permits for `concurrency`, set when the route is mounted.
+    pub(crate) permits: Option<Arc<Semaphore>>,
     /// Cached metadata that aids in routing later.
     pub(crate) metadata: Metadata,
 }
@@ -180,6 +193,9 @@ impl Route {
             uri: route_path,
             name: None,
             format: None,
+            timeout: None,
+            concurrency: None,
+            permits: None,
             base: Origin::dummy(),
             handler: Box::new(handler),
             metadata: Metadata::default(),
@@ -311,6 +327,8 @@ impl fmt::Debug for Route {
             .field("uri", &self.uri)
             .field("rank", &self.rank)
             .field("format", &self.format)
+            .field("timeout", &self.timeout)
+            .field("concurrency", &self.concurrency)
             .field("metadata", &self.metadata)
             .finish()
     }
@@ -322,6 +340,8 @@ impl From<StaticRouteInfo> for Route {
         // This should never panic since `info.path` is statically checked.
         let mut route = Route::new(info.method, info.path, info.handler);
         route.format = info.format;
+        route.timeout = info.timeout;
+        route.concurrency = info.concurrency;
         route.name = Some(info.name);
         if let Some(rank) = info.rank {
             route.rank = rank;
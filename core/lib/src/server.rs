@@ -3,33 +3,43 @@ use std::sync::Arc;
 
 use futures::stream::StreamExt;
 use futures::future::{Future, BoxFuture};
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, Semaphore, OwnedSemaphorePermit, TryAcquireError};
 use yansi::Paint;
 
 use crate::Rocket;
 use crate::handler;
-use crate::request::{Request, FormItems};
+use crate::request::{Request, RequestId, FormItems, ConnectionMeta};
 use crate::data::Data;
 use crate::response::{Body, Response};
 use crate::outcome::Outcome;
 use crate::error::{Error, ErrorKind};
-use crate::logger::PaintExt;
+use crate::logger::{self, PaintExt, RequestContext};
 use crate::ext::AsyncReadExt;
+use crate::trace::Span;
 
 use crate::http::{Method, Status, Header, hyper};
-use crate::http::private::{Listener, Connection, Incoming};
+use crate::http::private::{Listener, Incoming, InFlight, TimedListener, TimedConnection};
 use crate::http::uri::Origin;
 
 // A token returned to force the execution of one method before another.
 pub(crate) struct Token;
 
+// The number of seconds clients are asked to wait, via `Retry-After`, before
+// retrying a request that was rejected because of a concurrency limit.
+const RETRY_AFTER: &str = "1";
+
 // This function tries to hide all of the Hyper-ness from Rocket. It essentially
 // converts Hyper types into Rocket types, then calls the `dispatch` function,
 // which knows nothing about Hyper. Because responding depends on the
 // `HyperResponse` type, this function does the actual response processing.
+//
+// The request is considered in flight on its connection until `in_flight` is
+// dropped, after the response has been written.
 async fn hyper_service_fn(
     rocket: Arc<Rocket>,
-    h_addr: std::net::SocketAddr,
+    conn: ConnectionMeta,
+    conn_span: Span,
+    in_flight: InFlight,
     hyp_req: hyper::Request<hyper::Body>,
 ) -> Result<hyper::Response<hyper::Body>, io::Error> {
     // This future must return a hyper::Response, but the response body might
@@ -37,13 +47,26 @@ async fn hyper_service_fn(
     // sends the response metadata (and a body channel) prior.
     let (tx, rx) = oneshot::channel();
 
-    tokio::spawn(async move {
+    // Accept the request's ID from its headers or generate a new one.
+    let id_header = rocket.config.request_id_header.as_str();
+    let id = RequestId::resolve(hyp_req.headers().get(id_header).and_then(|v| v.to_str().ok()));
+
+    // Include the request in every message logged while it's being handled.
+    let (method, uri) = (hyp_req.method().as_str(), hyp_req.uri().to_string());
+    let context = RequestContext::new(id.to_string(), method, uri);
+
+    let (method, uri) = (hyp_req.method().as_str(), hyp_req.uri());
+    let span = conn_span.request(id.as_str(), method, uri);
+    let request_span = span.clone();
+    tokio::spawn(span.instrument(logger::scoped(context, async move {
+        let _in_flight = in_flight;
+
         // Get all of the information from Hyper.
         let (h_parts, h_body) = hyp_req.into_parts();
 
         // Convert the Hyper request into a Rocket request.
         let req_res = Request::from_hyp(
-            &rocket, h_parts.method, h_parts.headers, &h_parts.uri, h_addr
+            &rocket, h_parts.method, h_parts.version, h_parts.headers, &h_parts.uri, conn
         );
 
         let mut req = match req_res {
@@ -54,33 +77,67 @@ async fn hyper_service_fn(
                 // fabricate one. This is weird. We should let the user know
                 // that we failed to parse a request (by invoking some special
                 // handler) instead of doing this.
-                let dummy = Request::new(&rocket, Method::Get, Origin::dummy());
-                let r = rocket.handle_error(Status::BadRequest, &dummy).await;
-                return rocket.send_response(r, tx).await;
+                let mut dummy = Request::new(&rocket, Method::Get, Origin::dummy());
+                dummy.set_id(id);
+                dummy.set_span(request_span);
+                let mut r = rocket.handle_error(Status::BadRequest, &dummy).await;
+                r.set_header(rocket.request_id_header(&dummy));
+                return rocket.send_response(r, &dummy, tx).await;
             }
         };
 
+        req.set_id(id);
+        req.set_span(request_span);
+
+        // Reject the request early if the server is at capacity. The permit,
+        // if any, is held until the response has been written.
+        let permit = rocket.request_permit();
+        if permit.is_err() {
+            warn!("Server at capacity. Rejecting {}.", req);
+            let mut r = rocket.overloaded(&req).await;
+            r.set_header(rocket.request_id_header(&req));
+            return rocket.send_response(r, &req, tx).await;
+        }
+
         // Retrieve the data from the hyper body.
-        let mut data = Data::from_hyp(h_body).await;
+        let body_timeout = rocket.config.timeouts.body_duration();
+        let mut data = Data::from_hyp(h_body, body_timeout).await;
 
         // Dispatch the request to get a response, then write that response out.
         let token = rocket.preprocess_request(&mut req, &mut data).await;
-        let r = rocket.dispatch(token, &mut req, data).await;
-        rocket.send_response(r, tx).await;
-    });
+        let r = rocket.dispatch(token, &req, data).await;
+        rocket.send_response(r, &req, tx).await;
+    })));
 
     // Receive the response written to `tx` by the task above.
     rx.await.map_err(|e| io::Error::new(io::ErrorKind::Other, e))
 }
 
 impl Rocket {
+    /// Acquires a permit to handle a request if `max_concurrent_requests` is
+    /// set. Fails if the server is at capacity.
+    fn request_permit(&self) -> Result<Option<OwnedSemaphorePermit>, TryAcquireError> {
+        match self.request_permits {
+            Some(ref permits) => permits.clone().try_acquire_owned().map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the header echoing the ID of `request` in its response.
+    fn request_id_header(&self, request: &Request<'_>) -> Header<'static> {
+        Header::new(self.config.request_id_header.clone(), request.id().to_string())
+    }
+
     /// Wrapper around `make_response` to log a success or failure.
     #[inline]
     async fn send_response(
         &self,
         response: Response<'_>,
+        request: &Request<'_>,
         tx: oneshot::Sender<hyper::Response<hyper::Body>>,
     ) {
+        logger::set_status(response.status());
+        request.span().record_status(response.status());
         match self.make_response(response, tx).await {
             Ok(()) => info_!("{}", Paint::green("Response succeeded.")),
             Err(e) => error_!("Failed to write response: {:?}.", e),
@@ -199,6 +256,9 @@ impl Rocket {
             response.set_header(Header::new("Server", "Rocket"));
         }
 
+        // Echo the request's ID, including in responses from catchers.
+        response.set_header(self.request_id_header(request));
+
         // Run the response fairings.
         self.fairings.handle_response(request, &mut response).await;
 
@@ -263,14 +323,56 @@ impl Rocket {
     ) -> impl Future<Output = handler::Outcome<'r>> + 's {
         async move {
             // Go through the list of matching routes until we fail or succeed.
+            let body_timed_out = data.timed_out();
+            let mut at_capacity = false;
             let matches = self.router.route(request);
             for route in matches {
                 // Retrieve and set the requests parameters.
-                info_!("Matched: {}", route);
                 request.set_route(route);
+                logger::set_route(route.name);
+                match route.name {
+                    Some(name) => request.span().record_route(&name),
+                    None => request.span().record_route(&route.uri),
+                }
 
-                // Dispatch the request to the handler.
-                let outcome = route.handler.handle(request, data).await;
+                info_!("Matched: {}", route);
+
+                // Skip the route if it's at its concurrency limit. Otherwise,
+                // hold a permit until the handler completes.
+                let _permit = match route.permits {
+                    Some(ref permits) => match permits.clone().try_acquire_owned() {
+                        Ok(permit) => Some(permit),
+                        Err(_) => {
+                            warn_!("Route at its concurrency limit. Forwarding.");
+                            at_capacity = true;
+                            continue;
+                        }
+                    },
+                    None => None,
+                };
+
+                // Dispatch the request to the handler, failing the request if
+                // the handler exceeds its timeout.
+                let handle = route.handler.handle(request, data);
+                let outcome = match self.config.timeouts.handler_duration(route) {
+                    Some(duration) => match tokio::time::timeout(duration, handle).await {
+                        Ok(outcome) => outcome,
+                        Err(_) => {
+                            error_!("Handler timed out after {}s.", duration.as_secs());
+                            Outcome::Failure(Status::ServiceUnavailable)
+                        }
+                    },
+                    None => handle.await,
+                };
+
+                // A stalled body fails the request, whatever the handler did.
+                let outcome = match body_timed_out() {
+                    true => {
+                        error_!("Timed out waiting for the request body.");
+                        Outcome::Failure(Status::RequestTimeout)
+                    }
+                    false => outcome,
+                };
 
                 // Check if the request processing completed (Some) or if the
                 // request needs to be forwarded. If it does, continue the loop
@@ -282,6 +384,13 @@ impl Rocket {
                 }
             }
 
+            // Reject the request if a matching route was only skipped because
+            // it was at its concurrency limit.
+            if at_capacity {
+                warn_!("Rejecting {}: matching routes are at capacity.", request);
+                return Outcome::Success(self.overloaded(request).await);
+            }
+
             error_!("No matching routes for {}.", request);
             Outcome::Forward(data)
         }
@@ -297,7 +406,7 @@ impl Rocket {
         status: Status,
         req: &'r Request<'s>
     ) -> impl Future<Output = Response<'r>> + 's {
-        async move {
+        Span::catcher(status).instrument(async move {
             warn_!("Responding with {} catcher.", Paint::red(&status));
 
             // For now, we reset the delta state to prevent any modifications
@@ -327,6 +436,19 @@ impl Rocket {
                     default.expect("Rocket has default 500 response")
                 }
             }
+        })
+    }
+
+    // Responds to a request that was rejected because the server or its route
+    // was at capacity with a 503 and a `Retry-After` header.
+    fn overloaded<'s, 'r: 's>(
+        &'s self,
+        req: &'r Request<'s>
+    ) -> impl Future<Output = Response<'r>> + 's {
+        async move {
+            let mut response = self.handle_error(Status::ServiceUnavailable, req).await;
+            response.set_header(Header::new("Retry-After", RETRY_AFTER));
+            response
         }
     }
 
@@ -342,10 +464,17 @@ impl Rocket {
         // Freeze managed state for synchronization-free accesses later.
         self.managed_state.freeze();
 
-        // Determine the address and port we actually bound to.
-        self.config.port = listener.local_addr().map(|a| a.port()).unwrap_or(0);
+        // Determine the address and port we actually bound to. This reflects
+        // the OS-chosen port when the configured port is `0`.
+        if let Some(addr) = listener.local_addr() {
+            self.config.address = addr.ip();
+            self.config.port = addr.port();
+        } else {
+            self.config.port = 0;
+        }
+
         let proto = self.config.tls.as_ref().map_or("http://", |_| "https://");
-        let full_addr = format!("{}:{}", self.config.address, self.config.port);
+        let full_addr = std::net::SocketAddr::new(self.config.address, self.config.port);
 
         // Run the launch fairings.
         self.fairings.pretty_print_counts();
@@ -364,17 +493,33 @@ impl Rocket {
             n => Some(std::time::Duration::from_secs(n as u64))
         };
 
+        // Determine which protocols to speak. HTTP/2 on TLS is negotiated via
+        // ALPN; on plaintext, it's only spoken with prior knowledge (h2c).
+        let http2 = self.config.http2.clone();
+        let http1_only = http2.http1_only(self.config.tls_enabled());
+
         // We need to get this before moving `self` into an `Arc`.
         let mut shutdown_receiver = self.shutdown_receiver.take()
             .expect("shutdown receiver has already been used");
 
+        // Close connections that are idle or slow to send headers.
+        let listener = TimedListener::new(listener, self.config.timeouts.header_duration());
+
+        // Limit the number of requests handled at once.
+        let max_requests = self.config.max_concurrent_requests;
+        self.request_permits = max_requests.map(|n| Arc::new(Semaphore::new(n)));
+
         let rocket = Arc::new(self);
-        let service = hyper::make_service_fn(move |conn: &<L as Listener>::Connection| {
+        let service = hyper::make_service_fn(move |conn: &TimedConnection<L::Connection>| {
             let rocket = rocket.clone();
-            let remote = conn.remote_addr().unwrap_or_else(|| ([0, 0, 0, 0], 0).into());
+            let activity = conn.activity().clone();
+            let conn = ConnectionMeta::from(conn);
+            let span = Span::connection(conn.remote);
             async move {
                 Ok::<_, std::convert::Infallible>(hyper::service_fn(move |req| {
-                    hyper_service_fn(rocket.clone(), remote, req)
+                    let in_flight = activity.begin();
+                    let (rocket, conn, span) = (rocket.clone(), conn.clone(), span.clone());
+                    hyper_service_fn(rocket, conn, span, in_flight, req)
                 }))
             }
         });
@@ -382,7 +527,12 @@ impl Rocket {
         // NOTE: `hyper` uses `tokio::spawn()` as the default executor.
         hyper::Server::builder(Incoming::from_listener(listener))
             .http1_keepalive(http1_keepalive)
+            .http1_only(http1_only)
             .http2_keep_alive_interval(http2_keep_alive)
+            .http2_max_concurrent_streams(http2.max_concurrent_streams)
+            .http2_initial_stream_window_size(http2.initial_stream_window_size)
+            .http2_initial_connection_window_size(http2.initial_connection_window_size)
+            .http2_adaptive_window(http2.adaptive_window)
             .serve(service)
             .with_graceful_shutdown(async move { shutdown_receiver.recv().await; })
             .await
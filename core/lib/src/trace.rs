@@ -0,0 +1,252 @@
+//! Spans emitted when the `tracing` feature is enabled.
+//!
+//! With the `tracing` feature, Rocket runs each connection and request inside a
+//! [`tracing`] span. Request guards, data guards, fairings, and catchers run in
+//! child spans of their request's span:
+//!
+//! | Span            | Fields                                                 |
+//! |-----------------|--------------------------------------------------------|
+//! | `connection`    | `remote`                                               |
+//! | `request`       | `request_id`, `method`, `uri`, `route`, `status`       |
+//! | `request_guard` | `type`                                                 |
+//! | `data_guard`    | `type`                                                 |
+//! | `fairing`       | `name`, `kind` (`request` or `response`)               |
+//! | `catcher`       | `status`                                               |
+//!
+//! The `route` field of a `request` span is the matched route's name, or its
+//! URI template if it's unnamed, and is recorded once the request is routed.
+//! The `status` field is recorded once a response is produced.
+//!
+//! Rocket's log messages continue to be emitted through the `log` crate. To
+//! emit them as `tracing` events instead, disable Rocket's logger by setting
+//! `log_level` to `off` and install a `log` compatibility layer such as
+//! `tracing_log::LogTracer`.
+//!
+//! Without the `tracing` feature, these spans are no-ops.
+
+use std::future::Future;
+use std::net::SocketAddr;
+
+use crate::http::Status;
+
+/// A span, or nothing if the `tracing` feature is disabled.
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct Span {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl Span {
+    #[cfg(feature = "tracing")]
+    fn new(span: tracing::Span) -> Span {
+        Span { span }
+    }
+
+    /// A span that records nothing.
+    pub(crate) fn none() -> Span {
+        #[cfg(feature = "tracing")] {
+            Span::new(tracing::Span::none())
+        }
+
+        #[cfg(not(feature = "tracing"))] {
+            Span { }
+        }
+    }
+
+    /// The span of a connection from `remote`.
+    pub(crate) fn connection(remote: Option<SocketAddr>) -> Span {
+        #[cfg(feature = "tracing")] {
+            use tracing::field::{display, Empty};
+
+            let span = tracing::info_span!(parent: None, "connection", remote = Empty);
+            if let Some(remote) = remote {
+                span.record("remote", &display(remote));
+            }
+
+            Span::new(span)
+        }
+
+        #[cfg(not(feature = "tracing"))] {
+            let _ = remote;
+            Span::none()
+        }
+    }
+
+    /// The span of a request with ID `id` on the connection with span `self`.
+    pub(crate) fn request(&self, id: &str, method: &str, uri: &dyn std::fmt::Display) -> Span {
+        #[cfg(feature = "tracing")] {
+            use tracing::field::{display, Empty};
+
+            Span::new(tracing::info_span!(parent: &self.span, "request",
+                request_id = id, method, uri = display(uri), route = Empty, status = Empty))
+        }
+
+        #[cfg(not(feature = "tracing"))] {
+            let _ = (id, method, uri);
+            Span::none()
+        }
+    }
+
+    /// The span of the request guard of type `ty`.
+    #[doc(hidden)]
+    pub fn request_guard(ty: &'static str) -> Span {
+        #[cfg(feature = "tracing")] {
+            Span::new(tracing::info_span!("request_guard", r#type = ty))
+        }
+
+        #[cfg(not(feature = "tracing"))] {
+            let _ = ty;
+            Span::none()
+        }
+    }
+
+    /// The span of the data guard of type `ty`.
+    #[doc(hidden)]
+    pub fn data_guard(ty: &'static str) -> Span {
+        #[cfg(feature = "tracing")] {
+            Span::new(tracing::info_span!("data_guard", r#type = ty))
+        }
+
+        #[cfg(not(feature = "tracing"))] {
+            let _ = ty;
+            Span::none()
+        }
+    }
+
+    /// The span of the fairing named `name`'s `kind` callback.
+    pub(crate) fn fairing(name: &'static str, kind: &'static str) -> Span {
+        #[cfg(feature = "tracing")] {
+            Span::new(tracing::info_span!("fairing", name, kind))
+        }
+
+        #[cfg(not(feature = "tracing"))] {
+            let _ = (name, kind);
+            Span::none()
+        }
+    }
+
+    /// The span of the catcher for `status`.
+    pub(crate) fn catcher(status: Status) -> Span {
+        #[cfg(feature = "tracing")] {
+            Span::new(tracing::info_span!("catcher", status = status.code))
+        }
+
+        #[cfg(not(feature = "tracing"))] {
+            let _ = status;
+            Span::none()
+        }
+    }
+
+    /// Runs `fut` inside of `self`.
+    #[doc(hidden)]
+    pub fn instrument<F: Future>(&self, fut: F) -> impl Future<Output = F::Output> {
+        #[cfg(feature = "tracing")] {
+            tracing::Instrument::instrument(fut, self.span.clone())
+        }
+
+        #[cfg(not(feature = "tracing"))] {
+            fut
+        }
+    }
+
+    /// Records the matched route, `name`, on this request span.
+    pub(crate) fn record_route(&self, name: &dyn std::fmt::Display) {
+        #[cfg(feature = "tracing")]
+        self.span.record("route", &tracing::field::display(name));
+
+        #[cfg(not(feature = "tracing"))]
+        let _ = name;
+    }
+
+    /// Records the response's `status` on this request span.
+    pub(crate) fn record_status(&self, status: Status) {
+        #[cfg(feature = "tracing")]
+        self.span.record("status", &status.code);
+
+        #[cfg(not(feature = "tracing"))]
+        let _ = status;
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use tracing::{Event, Metadata, Subscriber};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+
+    use super::Span;
+    use crate::http::Status;
+
+    /// The name and fields, as `(name, value)`, of every span created.
+    type Spans = Arc<Mutex<Vec<(&'static str, Vec<(&'static str, String)>)>>>;
+
+    struct Recorder {
+        spans: Spans,
+        next_id: AtomicU64,
+    }
+
+    struct Fields<'a>(&'a mut Vec<(&'static str, String)>);
+
+    impl Visit for Fields<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.push((field.name(), format!("{:?}", value)));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+            let mut fields = vec![];
+            attrs.record(&mut Fields(&mut fields));
+            self.spans.lock().unwrap().push((attrs.metadata().name(), fields));
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst))
+        }
+
+        fn record(&self, id: &Id, values: &Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            let (_, fields) = &mut spans[id.into_u64() as usize - 1];
+            values.record(&mut Fields(fields));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {}
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_request_span_records_route_and_status() {
+        let spans = Spans::default();
+        let recorder = Recorder { spans: spans.clone(), next_id: AtomicU64::new(1) };
+        tracing::subscriber::with_default(recorder, || {
+            let conn = Span::connection(None);
+            let request = conn.request("abc", "GET", &"/a?b");
+
+            // Recording must not depend on which span, if any, is entered.
+            let _entered = conn.span.enter();
+            request.record_route(&"index");
+            request.record_status(Status::NotFound);
+        });
+
+        let spans = spans.lock().unwrap();
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].0, "connection");
+
+        let (name, fields) = &spans[1];
+        assert_eq!(*name, "request");
+        let field = |name| fields.iter().find(|(k, _)| *k == name).map(|(_, v)| v.as_str());
+        assert_eq!(field("request_id"), Some("\"abc\""));
+        assert_eq!(field("method"), Some("\"GET\""));
+        assert_eq!(field("uri"), Some("/a?b"));
+        assert_eq!(field("route"), Some("index"));
+        assert_eq!(field("status"), Some("404"));
+    }
+}
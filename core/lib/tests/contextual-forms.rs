@@ -0,0 +1,74 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Form, Contextual};
+
+#[derive(FromForm)]
+struct Address {
+    city: String,
+}
+
+#[derive(FromForm)]
+struct Signup {
+    name: String,
+    age: u8,
+    address: Address,
+}
+
+#[post("/", data = "<form>")]
+fn signup(form: Form<Contextual<'_, Signup>>) -> String {
+    if let Some(ref signup) = form.value {
+        return format!("{}, {}, {}", signup.name, signup.age, signup.address.city);
+    }
+
+    let ctxt = &form.context;
+    let errors: Vec<_> = ctxt.errors().iter().map(|e| e.to_string()).collect();
+    let age_value = ctxt.field_errors("age").next().and_then(|e| e.value).map(|v| v.as_str());
+    format!("{} | name={:?} age={:?}", errors.join("; "), ctxt.field_value("name"), age_value)
+}
+
+#[post("/parsed", data = "<form>")]
+fn parsed(form: Form<Contextual<'_, Signup>>) -> String {
+    let ctxt = &form.context;
+    let city = ctxt.field::<Address>("address").map(|address| address.city);
+    format!("name={:?} age={:?} city={:?} address.city={:?}", ctxt.field::<String>("name"),
+        ctxt.field::<u8>("age"), city, ctxt.field::<String>("address.city"))
+}
+
+mod contextual_forms_tests {
+    use rocket::local::blocking::Client;
+    use rocket::http::{Status, ContentType};
+
+    fn submit_to(uri: &str, body: &str) -> (Status, String) {
+        let routes = routes![super::signup, super::parsed];
+        let client = Client::tracked(rocket::ignite().mount("/", routes)).unwrap();
+        let response = client.post(uri).header(ContentType::Form).body(body).dispatch();
+        (response.status(), response.into_string().unwrap())
+    }
+
+    fn submit(body: &str) -> (Status, String) {
+        submit_to("/", body)
+    }
+
+    #[test]
+    fn contextual_form_has_value() {
+        let (status, body) = submit("name=Bob&age=30&address.city=Paris");
+        assert_eq!(status, Status::Ok);
+        assert_eq!(body, "Bob, 30, Paris");
+    }
+
+    #[test]
+    fn contextual_form_has_all_errors_and_values() {
+        let (status, body) = submit("name=Bob%20Smith&age=old&extra=1");
+        assert_eq!(status, Status::Ok);
+        assert_eq!(body, "age: invalid value; address: missing field; \
+            extra: unexpected field | name=Some(\"Bob Smith\") age=Some(\"old\")");
+    }
+
+    #[test]
+    fn contextual_form_has_parsed_fields() {
+        let (status, body) = submit_to("/parsed", "name=Bob%20Smith&age=old&address.city=Paris");
+        assert_eq!(status, Status::Ok);
+        assert_eq!(body, "name=Some(\"Bob Smith\") age=None \
+            city=Some(\"Paris\") address.city=Some(\"Paris\")");
+    }
+}
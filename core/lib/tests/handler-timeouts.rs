@@ -0,0 +1,80 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+#[get("/slow")]
+async fn slow() -> &'static str {
+    rocket::tokio::time::sleep(Duration::from_secs(2)).await;
+    "done"
+}
+
+#[get("/patient", timeout = 4)]
+async fn patient() -> &'static str {
+    rocket::tokio::time::sleep(Duration::from_secs(2)).await;
+    "done"
+}
+
+#[catch(503)]
+fn timed_out() -> &'static str {
+    "timed out"
+}
+
+mod handler_timeouts_tests {
+    use std::future::Future;
+
+    use rocket::local::asynchronous::Client;
+    use rocket::http::Status;
+    use rocket::tokio;
+
+    /// Runs `test` with time paused, so that sleeps and timeouts complete
+    /// immediately, in the order of their deadlines.
+    fn paused<F: Future>(test: F) -> F::Output {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("create tokio runtime");
+
+        runtime.block_on(async {
+            tokio::time::pause();
+            test.await
+        })
+    }
+
+    async fn client(handler_timeout: u32) -> Client {
+        let config = rocket::Config::figment().merge(("timeouts.handler", handler_timeout));
+        let rocket = rocket::custom(config)
+            .mount("/", routes![super::slow, super::patient])
+            .register(catchers![super::timed_out]);
+
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    #[test]
+    fn handler_timeout_fails_with_503() {
+        paused(async {
+            let client = client(1).await;
+            let response = client.get("/slow").dispatch().await;
+            assert_eq!(response.status(), Status::ServiceUnavailable);
+            assert_eq!(response.into_string().await.unwrap(), "timed out");
+        })
+    }
+
+    #[test]
+    fn route_timeout_overrides_config() {
+        paused(async {
+            let client = client(1).await;
+            let response = client.get("/patient").dispatch().await;
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.into_string().await.unwrap(), "done");
+        })
+    }
+
+    #[test]
+    fn handler_timeout_is_disabled_by_default() {
+        paused(async {
+            let client = client(0).await;
+            let response = client.get("/slow").dispatch().await;
+            assert_eq!(response.status(), Status::Ok);
+        })
+    }
+}
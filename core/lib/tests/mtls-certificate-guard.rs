@@ -0,0 +1,40 @@
+#![cfg(feature = "mtls")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::mtls::Certificate;
+use rocket::local::blocking::Client;
+
+macro_rules! crate_relative {
+    ($path:expr) => {
+        std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path))
+    };
+}
+
+#[get("/")]
+fn cert(cert: Certificate<'_>) -> String {
+    let cn = cert.subject().iter_common_name().next().unwrap().as_str().unwrap();
+    format!("{}|{}|{}", cn, cert.serial(), cert.has_dns_name("client.rocket.rs"))
+}
+
+#[get("/", rank = 2)]
+fn no_cert() -> &'static str {
+    "no certificate"
+}
+
+#[test]
+fn certificate_guard() {
+    let rocket = rocket::ignite().mount("/", routes![cert, no_cert]);
+    let client = Client::tracked(rocket).unwrap();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "no certificate");
+
+    let pem = crate_relative!("../../examples/tls/private/client_cert.pem");
+    let file = std::fs::File::open(pem).unwrap();
+    let response = client.get("/").identity(file).dispatch();
+    assert_eq!(response.into_string().unwrap(), "Rocket Client|10:01|true");
+
+    // Invalid PEM data results in no certificate at all.
+    let response = client.get("/").identity(&b"not a certificate"[..]).dispatch();
+    assert_eq!(response.into_string().unwrap(), "no certificate");
+}
@@ -0,0 +1,90 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Form, LenientForm};
+
+#[derive(FromForm)]
+struct Item {
+    name: String,
+    quantity: usize,
+}
+
+#[derive(FromForm)]
+struct Order {
+    items: Vec<Item>,
+    tags: Vec<String>,
+}
+
+impl Order {
+    fn summary(&self) -> String {
+        let items: Vec<_> = self.items.iter()
+            .map(|item| format!("{}x{}", item.quantity, item.name))
+            .collect();
+
+        format!("{} [{}]", items.join(","), self.tags.join(","))
+    }
+}
+
+#[post("/strict", data = "<order>")]
+fn strict(order: Form<Order>) -> String {
+    order.summary()
+}
+
+#[post("/lenient", data = "<order>")]
+fn lenient(order: LenientForm<Order>) -> String {
+    order.summary()
+}
+
+#[get("/query?<order..>")]
+fn query(order: Form<Order>) -> String {
+    order.summary()
+}
+
+mod nested_forms_tests {
+    use rocket::local::blocking::Client;
+    use rocket::http::{Status, ContentType};
+
+    const ORDER: &str = "items[0].name=a&items[0].quantity=2&items[1].name=b\
+        &items[1].quantity=1&tags=x&tags=y";
+
+    fn client() -> Client {
+        let routes = routes![super::strict, super::lenient, super::query];
+        Client::tracked(rocket::ignite().mount("/", routes)).unwrap()
+    }
+
+    #[test]
+    fn nested_forms_parse_in_data_and_query() {
+        let client = client();
+        for uri in &["/strict", "/lenient"] {
+            let response = client.post(*uri).header(ContentType::Form).body(ORDER).dispatch();
+            assert_eq!(response.into_string().unwrap(), "2xa,1xb [x,y]");
+        }
+
+        let query = ORDER.replace('[', "%5B").replace(']', "%5D");
+        let response = client.get(format!("/query?{}", query)).dispatch();
+        assert_eq!(response.into_string().unwrap(), "2xa,1xb [x,y]");
+
+        let response = client.get("/query").dispatch();
+        assert_eq!(response.into_string().unwrap(), " []");
+    }
+
+    #[test]
+    fn vec_elements_are_ordered_by_index() {
+        let client = client();
+        let body = "items[1].name=b&items[1].quantity=1&items[0].name=a\
+            &items[0].quantity=2&items[10].name=c&items[10].quantity=3&tags=x";
+
+        let response = client.post("/strict").header(ContentType::Form).body(body).dispatch();
+        assert_eq!(response.into_string().unwrap(), "2xa,1xb,3xc [x]");
+    }
+
+    #[test]
+    fn nested_forms_respect_strictness() {
+        let client = client();
+        let body = format!("{}&items[0].color=red", ORDER);
+        let response = client.post("/strict").header(ContentType::Form).body(&body).dispatch();
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+
+        let response = client.post("/lenient").header(ContentType::Form).body(&body).dispatch();
+        assert_eq!(response.into_string().unwrap(), "2xa,1xb [x,y]");
+    }
+}
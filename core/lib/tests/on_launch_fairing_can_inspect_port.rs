@@ -1,3 +1,5 @@
+use std::net::Ipv4Addr;
+
 use rocket::config::Config;
 use rocket::fairing::AdHoc;
 use rocket::futures::channel::oneshot;
@@ -13,3 +15,27 @@ async fn on_launch_fairing_can_inspect_port() {
     rocket::tokio::spawn(rocket.launch());
     assert_ne!(rx.await.unwrap(), 0);
 }
+
+#[rocket::async_test]
+async fn on_launch_fairing_sees_bound_port() {
+    let (tx, rx) = oneshot::channel();
+    let config = Config {
+        address: Ipv4Addr::LOCALHOST.into(),
+        port: 0,
+        ..Default::default()
+    };
+
+    let rocket = rocket::custom(config)
+        .attach(AdHoc::on_launch("Send Address -> Channel", move |rocket| {
+            let config = rocket.config();
+            tx.send((config.address, config.port)).unwrap();
+        }));
+
+    rocket::tokio::spawn(rocket.launch());
+    let (address, port) = rx.await.unwrap();
+    assert_eq!(address, Ipv4Addr::LOCALHOST);
+
+    // The reported port must be the one that was actually bound to.
+    let stream = rocket::tokio::net::TcpStream::connect((address, port)).await;
+    assert!(stream.is_ok());
+}
@@ -0,0 +1,51 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::RequestId;
+
+#[get("/")]
+fn id(id: &RequestId) -> String {
+    id.to_string()
+}
+
+mod request_id_tests {
+    use rocket::local::blocking::Client;
+    use rocket::http::{Header, Status};
+
+    fn client() -> Client {
+        let config = rocket::Config::figment().merge(("request_id_header", "X-Trace-Id"));
+        let rocket = rocket::custom(config).mount("/", routes![super::id]);
+        Client::tracked(rocket).unwrap()
+    }
+
+    #[test]
+    fn request_id_is_generated_and_echoed() {
+        let client = client();
+        let response = client.get("/").dispatch();
+        let echoed = response.headers().get_one("X-Trace-Id").map(|id| id.to_string());
+        let id = response.into_string().unwrap();
+        assert_eq!(id.len(), 32);
+        assert_eq!(echoed, Some(id.clone()));
+
+        let response = client.get("/").dispatch();
+        assert_ne!(response.into_string().unwrap(), id);
+    }
+
+    #[test]
+    fn request_id_is_accepted_from_header() {
+        let client = client();
+        let response = client.get("/").header(Header::new("X-Trace-Id", "abc-123")).dispatch();
+        assert_eq!(response.headers().get_one("X-Trace-Id"), Some("abc-123"));
+        assert_eq!(response.into_string().unwrap(), "abc-123");
+
+        let response = client.get("/").header(Header::new("X-Trace-Id", "a b")).dispatch();
+        assert_ne!(response.into_string().unwrap(), "a b");
+    }
+
+    #[test]
+    fn request_id_is_echoed_by_catchers() {
+        let client = client();
+        let response = client.get("/missing").header(Header::new("X-Trace-Id", "xyz")).dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.headers().get_one("X-Trace-Id"), Some("xyz"));
+    }
+}
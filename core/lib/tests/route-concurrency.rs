@@ -0,0 +1,78 @@
+#[macro_use] extern crate rocket;
+
+use rocket::State;
+use rocket::tokio::sync::{mpsc, Notify};
+
+struct Gate {
+    entered: mpsc::Sender<()>,
+    release: Notify,
+}
+
+#[get("/limited", concurrency = 1)]
+async fn limited(gate: State<'_, Gate>) -> &'static str {
+    gate.entered.send(()).await.unwrap();
+    gate.release.notified().await;
+    "done"
+}
+
+#[get("/limited", rank = 2)]
+fn fallback() -> &'static str {
+    "fallback"
+}
+
+mod route_concurrency_tests {
+    use rocket::local::asynchronous::Client;
+    use rocket::http::Status;
+    use rocket::tokio::{self, sync::{mpsc, Notify}};
+
+    use super::Gate;
+
+    async fn client() -> (Client, mpsc::Receiver<()>) {
+        let (entered, receiver) = mpsc::channel(1);
+        let rocket = rocket::ignite()
+            .mount("/", routes![super::limited])
+            .mount("/forwarding", routes![super::limited, super::fallback])
+            .manage(Gate { entered, release: Notify::new() });
+
+        (Client::tracked(rocket).await.unwrap(), receiver)
+    }
+
+    fn gate(client: &Client) -> &Gate {
+        client.rocket().state::<Gate>().unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn excess_requests_are_rejected() {
+        let (client, mut entered) = client().await;
+        let (first, second) = tokio::join!(client.get("/limited").dispatch(), async {
+            entered.recv().await;
+            let response = client.get("/limited").dispatch().await;
+            gate(&client).release.notify_one();
+            response
+        });
+
+        assert_eq!(first.status(), Status::Ok);
+        assert_eq!(second.status(), Status::ServiceUnavailable);
+        assert_eq!(second.headers().get_one("Retry-After"), Some("1"));
+
+        // The permit is released once the first request completes.
+        gate(&client).release.notify_one();
+        let third = client.get("/limited").dispatch().await;
+        assert_eq!(third.status(), Status::Ok);
+    }
+
+    #[rocket::async_test]
+    async fn excess_requests_are_forwarded() {
+        let (client, mut entered) = client().await;
+        let (first, second) = tokio::join!(client.get("/forwarding/limited").dispatch(), async {
+            entered.recv().await;
+            let response = client.get("/forwarding/limited").dispatch().await;
+            gate(&client).release.notify_one();
+            response
+        });
+
+        assert_eq!(first.into_string().await.unwrap(), "done");
+        assert_eq!(second.status(), Status::Ok);
+        assert_eq!(second.into_string().await.unwrap(), "fallback");
+    }
+}
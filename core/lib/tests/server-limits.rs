@@ -0,0 +1,135 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Arc;
+
+use rocket::State;
+use rocket::tokio::sync::{mpsc, Notify};
+
+struct Gate {
+    entered: mpsc::Sender<()>,
+    release: Arc<Notify>,
+}
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[get("/wait")]
+async fn wait(gate: State<'_, Gate>) -> &'static str {
+    gate.entered.send(()).await.unwrap();
+    gate.release.notified().await;
+    "done"
+}
+
+mod server_limits_tests {
+    use std::net::{Ipv4Addr, SocketAddr};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use rocket::config::Config;
+    use rocket::fairing::AdHoc;
+    use rocket::futures::channel::oneshot;
+    use rocket::tokio::{self, net::TcpStream, sync::{mpsc, Notify}};
+    use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::Gate;
+
+    /// Launches a server with `config` and returns its address, a receiver of
+    /// a message for every request that enters the `/wait` handler, and the
+    /// `Notify` that lets those requests complete.
+    async fn launch(config: Config) -> (SocketAddr, mpsc::Receiver<()>, Arc<Notify>) {
+        let config = Config { address: Ipv4Addr::LOCALHOST.into(), port: 0, ..config };
+        let (entered, receiver) = mpsc::channel(8);
+        let release = Arc::new(Notify::new());
+        let (tx, rx) = oneshot::channel();
+        let rocket = rocket::custom(config)
+            .mount("/", routes![super::index, super::wait])
+            .manage(Gate { entered, release: release.clone() })
+            .attach(AdHoc::on_launch("Send Address", move |rocket| {
+                let config = rocket.config();
+                tx.send(SocketAddr::new(config.address, config.port)).unwrap();
+            }));
+
+        tokio::spawn(rocket.launch());
+        (rx.await.unwrap(), receiver, release)
+    }
+
+    /// Writes a `GET` request for `path` to `stream`.
+    async fn request(stream: &mut TcpStream, path: &str) {
+        let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path);
+        stream.write_all(request.as_bytes()).await.unwrap();
+    }
+
+    /// Reads a response from `stream`, returning its head and body.
+    async fn response(stream: &mut TcpStream) -> (String, String) {
+        let mut bytes = vec![];
+        let head_len = loop {
+            if let Some(i) = bytes.windows(4).position(|w| w == b"\r\n\r\n") {
+                break i + 4;
+            }
+
+            let mut buf = [0; 1024];
+            let n = stream.read(&mut buf).await.unwrap();
+            assert_ne!(n, 0, "connection closed before a response");
+            bytes.extend_from_slice(&buf[..n]);
+        };
+
+        let head = String::from_utf8(bytes[..head_len].to_vec()).unwrap();
+        let content_length: usize = head.lines()
+            .find_map(|line| line.strip_prefix("content-length: "))
+            .map_or(0, |len| len.parse().unwrap());
+
+        while bytes.len() < head_len + content_length {
+            let mut buf = [0; 1024];
+            let n = stream.read(&mut buf).await.unwrap();
+            assert_ne!(n, 0, "connection closed before the body");
+            bytes.extend_from_slice(&buf[..n]);
+        }
+
+        (head, String::from_utf8(bytes[head_len..].to_vec()).unwrap())
+    }
+
+    #[rocket::async_test]
+    async fn max_connections_stops_accepting() {
+        let config = Config { max_connections: Some(1), ..Config::debug_default() };
+        let (addr, _, _) = launch(config).await;
+
+        let mut first = TcpStream::connect(addr).await.unwrap();
+        request(&mut first, "/").await;
+        assert_eq!(response(&mut first).await.1, "index");
+
+        // The second connection isn't accepted while the first is open...
+        let mut second = TcpStream::connect(addr).await.unwrap();
+        request(&mut second, "/").await;
+        let pending = tokio::time::timeout(Duration::from_millis(200), response(&mut second));
+        assert!(pending.await.is_err());
+
+        // ...but it is once the first is closed.
+        drop(first);
+        let accepted = tokio::time::timeout(Duration::from_secs(5), response(&mut second));
+        assert_eq!(accepted.await.expect("second connection accepted").1, "index");
+    }
+
+    #[rocket::async_test]
+    async fn max_concurrent_requests_rejects_excess() {
+        let config = Config { max_concurrent_requests: Some(1), ..Config::debug_default() };
+        let (addr, mut entered, release) = launch(config).await;
+
+        let mut first = TcpStream::connect(addr).await.unwrap();
+        request(&mut first, "/wait").await;
+        entered.recv().await;
+
+        let mut second = TcpStream::connect(addr).await.unwrap();
+        request(&mut second, "/").await;
+        let (head, _) = response(&mut second).await;
+        assert!(head.starts_with("HTTP/1.1 503"));
+        assert!(head.contains("retry-after: 1\r\n"));
+
+        // The rejection doesn't affect the request being handled.
+        release.notify_one();
+        let (head, body) = response(&mut first).await;
+        assert!(head.starts_with("HTTP/1.1 200"));
+        assert_eq!(body, "done");
+    }
+}
@@ -0,0 +1,102 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use ipnet::Ipv4Net;
+use time::{Date, Time, PrimitiveDateTime};
+
+use rocket::request::{Form, FromFormValue};
+
+#[derive(FromForm)]
+struct Report {
+    start: PrimitiveDateTime,
+    network: Ipv4Net,
+    interval: Duration,
+}
+
+#[get("/reports/<date>")]
+fn report(date: Date) -> String {
+    format!("{} {}", date, uri!(report: date))
+}
+
+#[get("/reports?<date>&<at>")]
+fn scheduled(date: Date, at: Time) -> String {
+    format!("{} {}", uri!(scheduled: date, at), uri!(report: date))
+}
+
+#[post("/reports", data = "<form>")]
+fn create(form: Form<Report>) -> String {
+    let uri = uri!(scheduled: form.start.date(), form.start.time());
+    format!("{} {} {:?}", uri, form.network, form.interval)
+}
+
+macro_rules! assert_from_form_value {
+    ($string:literal as $T:ty, $expected:expr) => (
+        let value: Result<$T, _> = FromFormValue::from_form_value($string.into());
+        assert_eq!(value.ok(), $expected);
+    )
+}
+
+#[test]
+fn html_input_values() {
+    let date = Date::try_from_ymd(2021, 3, 4).unwrap();
+    let time = Time::try_from_hms(10, 30, 0).unwrap();
+
+    assert_from_form_value!("2021-03-04" as Date, Some(date));
+    assert_from_form_value!("2021-3-4" as Date, None);
+    assert_from_form_value!("2021-02-30" as Date, None);
+    assert_from_form_value!("10%3A30" as Time, Some(time));
+    assert_from_form_value!("10:30:15.25" as Time,
+        Some(Time::try_from_hms_milli(10, 30, 15, 250).unwrap()));
+    assert_from_form_value!("10:30." as Time, None);
+    assert_from_form_value!("24:00" as Time, None);
+    assert_from_form_value!("2021-03-04T10:30" as PrimitiveDateTime,
+        Some(PrimitiveDateTime::new(date, time)));
+    assert_from_form_value!("2021-03-04+10:30" as PrimitiveDateTime,
+        Some(PrimitiveDateTime::new(date, time)));
+    assert_from_form_value!("2021-03-04" as PrimitiveDateTime, None);
+
+    assert_from_form_value!("90" as Duration, Some(Duration::from_secs(90)));
+    assert_from_form_value!("1.5" as Duration, Some(Duration::from_millis(1500)));
+    assert_from_form_value!("-1" as Duration, None);
+    assert_from_form_value!("-1.5" as time::Duration,
+        Some(time::Duration::milliseconds(-1500)));
+
+    assert_from_form_value!("10.0.0.0%2F8" as Ipv4Net, Some("10.0.0.0/8".parse().unwrap()));
+    assert_from_form_value!("10.0.0.0/33" as Ipv4Net, None);
+}
+
+mod time_and_network_values_tests {
+    use rocket::local::blocking::Client;
+    use rocket::http::{Status, ContentType};
+
+    fn client() -> Client {
+        let routes = routes![super::report, super::scheduled, super::create];
+        Client::tracked(rocket::ignite().mount("/", routes)).unwrap()
+    }
+
+    #[test]
+    fn path_and_query_values() {
+        let client = client();
+        let response = client.get("/reports/2021-03-04").dispatch();
+        assert_eq!(response.into_string().unwrap(), "2021-03-04 /reports/2021-03-04");
+
+        let response = client.get("/reports/2021-13-04").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.get("/reports?date=2021-03-04&at=10:30:15").dispatch();
+        assert_eq!(response.into_string().unwrap(),
+            "/reports?date=2021-03-04&at=10:30:15 /reports/2021-03-04");
+    }
+
+    #[test]
+    fn form_values() {
+        let response = client().post("/reports")
+            .header(ContentType::Form)
+            .body("start=2021-03-04T10%3A30&network=10.0.0.0%2F8&interval=0.25")
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(),
+            "/reports?date=2021-03-04&at=10:30 10.0.0.0/8 250ms");
+    }
+}
@@ -0,0 +1,83 @@
+#[macro_use] extern crate rocket;
+
+use std::net::IpAddr;
+
+use rocket::Request;
+
+#[get("/")]
+fn client(ip: IpAddr, request: &Request<'_>) -> String {
+    format!("{} {} {}", ip, request.scheme(), request.host().unwrap_or("-"))
+}
+
+mod trusted_proxies_tests {
+    use rocket::local::blocking::Client;
+    use rocket::http::Header;
+
+    fn client(trusted: &[&str], header: &str) -> Client {
+        let config = rocket::Config::figment()
+            .merge(("trusted_proxies", trusted))
+            .merge(("proxy_header", header));
+
+        let rocket = rocket::custom(config).mount("/", routes![super::client]);
+        Client::tracked(rocket).unwrap()
+    }
+
+    fn get(client: &Client, remote: &str, headers: &[(&'static str, &'static str)]) -> String {
+        let mut request = client.get("/").remote(remote.parse().unwrap());
+        for &(name, value) in headers {
+            request.add_header(Header::new(name, value));
+        }
+
+        request.dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn untrusted_headers_are_ignored() {
+        let client = client(&[], "x-forwarded");
+        let headers = [
+            ("Host", "rocket.rs"),
+            ("X-Forwarded-For", "1.1.1.1"),
+            ("X-Forwarded-Proto", "https"),
+            ("X-Forwarded-Host", "evil.com"),
+            ("X-Real-IP", "2.2.2.2"),
+        ];
+
+        assert_eq!(get(&client, "10.0.0.1:80", &headers), "10.0.0.1 http rocket.rs");
+    }
+
+    #[test]
+    fn trusted_hops_are_followed() {
+        let forwarded = client(&["10.0.0.0/8", "192.168.1.1"], "forwarded");
+        let headers = [
+            ("Host", "internal"),
+            ("Forwarded", "for=6.6.6.6, for=1.1.1.1;proto=https;host=rocket.rs"),
+            ("Forwarded", "for=\"192.168.1.1:4000\""),
+        ];
+
+        assert_eq!(get(&forwarded, "10.0.0.1:80", &headers), "1.1.1.1 https rocket.rs");
+        assert_eq!(get(&forwarded, "8.8.8.8:80", &headers), "8.8.8.8 http internal");
+
+        let x_forwarded = client(&["10.0.0.0/8"], "x-forwarded");
+        let headers = [("X-Forwarded-For", "1.1.1.1, 10.1.1.1"), ("X-Forwarded-Proto", "https")];
+        assert_eq!(get(&x_forwarded, "10.0.0.1:80", &headers), "1.1.1.1 https -");
+
+        let real_ip = client(&["192.168.1.1"], "x-real-ip");
+        let headers = [("X-Real-IP", "1.1.1.1")];
+        assert_eq!(get(&real_ip, "192.168.1.1:80", &headers), "1.1.1.1 http -");
+    }
+
+    #[test]
+    fn forged_forwarded_header_is_ignored() {
+        // The proxy appends the client's address to `X-Forwarded-For` but passes
+        // the client's own `Forwarded` header through untouched.
+        let client = client(&["10.0.0.0/8"], "x-forwarded");
+        let headers = [
+            ("Host", "rocket.rs"),
+            ("Forwarded", "for=10.0.0.2, for=1.2.3.4;proto=https;host=evil.com"),
+            ("X-Forwarded-For", "8.8.8.8"),
+            ("X-Real-IP", "1.2.3.4"),
+        ];
+
+        assert_eq!(get(&client, "10.0.0.1:80", &headers), "8.8.8.8 http rocket.rs");
+    }
+}
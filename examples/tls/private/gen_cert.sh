@@ -10,6 +10,9 @@ CA_SUBJECT="/C=US/ST=CA/O=Rocket CA/CN=Rocket Root CA"
 SUBJECT="/C=US/ST=CA/O=Rocket/CN=localhost"
 ALT="DNS:localhost"
 
+CLIENT_SUBJECT="/C=US/ST=CA/O=Rocket/CN=Rocket Client"
+CLIENT_ALT="DNS:client.rocket.rs,email:client@rocket.rs"
+
 openssl genrsa -out ca_key.pem 4096
 openssl req -new -x509 -days 3650 -key ca_key.pem -subj "${CA_SUBJECT}" -out ca_cert.pem
 
@@ -18,4 +21,26 @@ openssl x509 -req -sha256 -extfile <(printf "subjectAltName=${ALT}") -days 3650
     -CA ca_cert.pem -CAkey ca_key.pem -CAcreateserial \
     -in server.csr -out cert.pem
 
-rm ca_cert.srl server.csr
+openssl req -newkey rsa:4096 -nodes -sha256 -keyout client_key.pem \
+    -subj "${CLIENT_SUBJECT}" -out client.csr
+openssl x509 -req -sha256 -days 3650 -set_serial 0x1001 \
+    -extfile <(printf "subjectAltName=${CLIENT_ALT}\nextendedKeyUsage=clientAuth") \
+    -CA ca_cert.pem -CAkey ca_key.pem \
+    -in client.csr -out client_cert.pem
+
+openssl ecparam -genkey -name prime256v1 -noout -out ecdsa_nistp256_sha256_key_sec1.pem
+openssl pkcs8 -topk8 -nocrypt -in ecdsa_nistp256_sha256_key_sec1.pem \
+    -out ecdsa_nistp256_sha256_key_pkcs8.pem
+openssl req -new -nodes -sha256 -key ecdsa_nistp256_sha256_key_pkcs8.pem \
+    -subj "${SUBJECT}" -out ecdsa.csr
+openssl x509 -req -sha256 -extfile <(printf "subjectAltName=${ALT}") -days 3650 \
+    -CA ca_cert.pem -CAkey ca_key.pem -CAcreateserial \
+    -in ecdsa.csr -out ecdsa_nistp256_sha256_cert.pem
+
+openssl genpkey -algorithm ED25519 -out ed25519_key.pem
+openssl req -new -key ed25519_key.pem -subj "${SUBJECT}" -out ed25519.csr
+openssl x509 -req -extfile <(printf "subjectAltName=${ALT}") -days 3650 \
+    -CA ca_cert.pem -CAkey ca_key.pem -CAcreateserial \
+    -in ed25519.csr -out ed25519_cert.pem
+
+rm ca_cert.srl server.csr client.csr ecdsa.csr ed25519.csr
@@ -2,12 +2,19 @@
 
 #[cfg(test)] mod tests;
 
+use rocket::mtls::Certificate;
+
 #[get("/")]
+fn mutual(cert: Certificate<'_>) -> String {
+    format!("Hello, {}!", cert.subject())
+}
+
+#[get("/", rank = 2)]
 fn hello() -> &'static str {
     "Hello, world!"
 }
 
 #[launch]
 fn rocket() -> rocket::Rocket {
-    rocket::ignite().mount("/", routes![hello])
+    rocket::ignite().mount("/", routes![hello, mutual])
 }
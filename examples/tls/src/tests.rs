@@ -6,3 +6,12 @@ fn hello_world() {
     let response = client.get("/").dispatch();
     assert_eq!(response.into_string(), Some("Hello, world!".into()));
 }
+
+#[test]
+fn hello_mutual() {
+    let client = Client::tracked(super::rocket()).unwrap();
+    let cert = std::fs::File::open("private/client_cert.pem").unwrap();
+    let response = client.get("/").identity(cert).dispatch();
+    assert_eq!(response.into_string(),
+        Some("Hello, C=US, ST=CA, O=Rocket, CN=Rocket Client!".into()));
+}
@@ -82,10 +82,15 @@ if [ "$1" = "--contrib" ]; then
   FEATURES=(
     json
     msgpack
+    serde_query
+    negotiated
+    ndjson
     tera_templates
     handlebars_templates
     serve
     helmet
+    access_log
+    metrics
     diesel_postgres_pool
     diesel_sqlite_pool
     diesel_mysql_pool
@@ -112,6 +117,8 @@ elif [ "$1" = "--core" ]; then
   FEATURES=(
     secrets
     tls
+    tracing
+    regex
   )
 
   pushd "${CORE_LIB_ROOT}" > /dev/null 2>&1